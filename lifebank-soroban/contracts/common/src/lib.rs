@@ -0,0 +1,5 @@
+#![no_std]
+
+//! Constants shared by the lifebank contracts.
+
+pub mod ttl;
@@ -0,0 +1,19 @@
+//! Storage TTL thresholds shared by every lifebank contract.
+//!
+//! Each pair is passed to `extend_ttl(threshold, extend_to)`: an entry is
+//! only bumped once its remaining TTL drops below the threshold.
+
+/// Ledgers closed per day at the ~5 second close time
+pub const DAY_IN_LEDGERS: u32 = 17_280;
+
+/// Instance storage (admin, counters, config) is bumped on every admin read
+pub const INSTANCE_TTL_THRESHOLD: u32 = 30 * DAY_IN_LEDGERS;
+pub const INSTANCE_TTL_EXTEND_TO: u32 = 120 * DAY_IN_LEDGERS;
+
+/// Records still in use, plus shared indexes and registries
+pub const ACTIVE_TTL_THRESHOLD: u32 = 14 * DAY_IN_LEDGERS;
+pub const ACTIVE_TTL_EXTEND_TO: u32 = 60 * DAY_IN_LEDGERS;
+
+/// Terminal records kept for audit, which are rarely touched again
+pub const ARCHIVE_TTL_THRESHOLD: u32 = 60 * DAY_IN_LEDGERS;
+pub const ARCHIVE_TTL_EXTEND_TO: u32 = 180 * DAY_IN_LEDGERS;
@@ -18,6 +18,8 @@ pub enum ContractError {
     InvalidTimestamp = 15,
     InvalidQuantity = 16,
     InvalidExpiration = 17,
+    InvalidDin = 18,
+    InvalidProductCode = 19,
 
     // State errors (20-29)
     AlreadyExists = 20,
@@ -30,8 +32,33 @@ pub enum ContractError {
     InsufficientBalance = 30,
     InsufficientPermissions = 31,
     NotAuthorizedBloodBank = 32,
+    NotAuthorizedLab = 33,
+    RoleAlreadyAssigned = 34,
 
     // Blood-specific errors (40-49)
     BloodUnitNotAvailable = 40,
     InvalidStatusTransition = 41,
+    UnitNotQuarantined = 42,
+    ScreeningIncomplete = 43,
+    DiscardReasonRequired = 44,
+    TransferPending = 45,
+    NoPendingTransfer = 46,
+    ReservationExpired = 47,
+    ReservationStillActive = 48,
+    UnitNotReserved = 49,
+
+    // Metadata errors (50-59)
+    MetadataKeyNotAllowed = 50,
+    MetadataValueTooLong = 51,
+    MetadataLimitExceeded = 52,
+
+    // Custody errors (60-69)
+    HandoffPending = 60,
+    NoPendingHandoff = 61,
+    NotCustodian = 62,
+
+    // Donor errors (70-79)
+    DonorDeferred = 70,
+    DonorPermanentlyDeferred = 71,
+    DonationIntervalNotMet = 72,
 }
@@ -1,58 +1,276 @@
-use crate::types::{BloodRegisteredEvent, BloodType, StatusChangeEvent};
-use soroban_sdk::{Address, Env, String, Symbol};
+// Events keep the tuple-topic `publish` format that indexers already consume.
+#![allow(deprecated)]
+
+use crate::types::{
+    BankTransfer, BankTransferEvent, BatchRegisteredEvent, BloodRegisteredEvent, BloodUnit,
+    Collection, CustodyHandoff, CustodyHandoffEvent, DiscardReason, DonorDeferral,
+    DonorFollowUpEvent, DonorRecallEvent, LookbackEntry, MetadataUpdatedEvent, RecallReason,
+    RecallReport, Reservation, ReservationEvent, Role, ScreeningRecordedEvent, ScreeningResult,
+    StatusChangeEvent, StatusDetail, StatusHistorySummary, StockAlertEvent, StockLevel, TtiMarker,
+    UnitDiscardedEvent, UnitRecalledEvent,
+};
+use soroban_sdk::{Address, Env, String, Symbol, Vec};
 
 /// Emit a BloodRegistered event
 ///
 /// # Arguments
 /// * `env` - Contract environment
-/// * `blood_unit_id` - Unique ID of the registered blood unit
-/// * `bank_id` - Blood bank that registered the unit
-/// * `blood_type` - Type of blood
-/// * `quantity_ml` - Quantity in milliliters
-/// * `expiration_timestamp` - When the unit expires
-pub fn emit_blood_registered(
-    env: &Env,
-    blood_unit_id: u64,
-    bank_id: &Address,
-    blood_type: BloodType,
-    quantity_ml: u32,
-    expiration_timestamp: u64,
-) {
-    let registered_at = env.ledger().timestamp();
-
+/// * `blood_unit` - The newly registered blood unit
+pub fn emit_blood_registered(env: &Env, blood_unit: &BloodUnit) {
     let event = BloodRegisteredEvent {
-        blood_unit_id,
-        bank_id: bank_id.clone(),
-        blood_type,
-        quantity_ml,
-        expiration_timestamp,
-        registered_at,
+        blood_unit_id: blood_unit.id,
+        bank_id: blood_unit.bank_id.clone(),
+        blood_type: blood_unit.blood_type,
+        component: blood_unit.component,
+        din: blood_unit.din.clone(),
+        quantity_ml: blood_unit.quantity_ml,
+        expiration_timestamp: blood_unit.expiration_timestamp,
+        registered_at: env.ledger().timestamp(),
     };
 
     env.events()
         .publish((Symbol::new(env, "blood_registered"),), event);
 }
 
+/// Emit a single BatchRegistered event summarizing a batch registration
+pub fn emit_batch_registered(env: &Env, bank_id: &Address, blood_unit_ids: &Vec<u64>) {
+    let event = BatchRegisteredEvent {
+        bank_id: bank_id.clone(),
+        blood_unit_ids: blood_unit_ids.clone(),
+        registered_at: env.ledger().timestamp(),
+    };
+
+    env.events()
+        .publish((Symbol::new(env, "blood_batch_registered"),), event);
+}
 
+#[allow(clippy::too_many_arguments)]
 pub fn emit_status_change(
     env: &Env,
+    history_id: u64,
     blood_unit_id: u64,
     from_status: crate::types::BloodStatus,
     to_status: crate::types::BloodStatus,
     authorized_by: &Address,
+    role: Role,
     reason: Option<String>,
+    detail: StatusDetail,
 ) {
     let changed_at = env.ledger().timestamp();
 
     let event = StatusChangeEvent {
+        history_id,
         blood_unit_id,
         from_status,
         to_status,
         authorized_by: authorized_by.clone(),
+        role,
         changed_at,
         reason,
+        detail,
     };
 
     env.events()
         .publish((Symbol::new(env, "status_changed"),), event);
 }
+
+/// Emit a UnitDiscarded event for wastage reporting
+pub fn emit_unit_discarded(
+    env: &Env,
+    blood_unit: &BloodUnit,
+    reason: DiscardReason,
+    discarded_by: &Address,
+) {
+    let event = UnitDiscardedEvent {
+        blood_unit_id: blood_unit.id,
+        bank_id: blood_unit.bank_id.clone(),
+        reason,
+        quantity_ml: blood_unit.quantity_ml,
+        discarded_by: discarded_by.clone(),
+        discarded_at: env.ledger().timestamp(),
+    };
+
+    env.events()
+        .publish((Symbol::new(env, "unit_discarded"),), event);
+}
+
+/// Emit a ScreeningRecorded event when a lab records a marker result
+pub fn emit_screening_recorded(
+    env: &Env,
+    blood_unit_id: u64,
+    marker: TtiMarker,
+    result: ScreeningResult,
+    lab: &Address,
+) {
+    let event = ScreeningRecordedEvent {
+        blood_unit_id,
+        marker,
+        result,
+        lab: lab.clone(),
+        tested_at: env.ledger().timestamp(),
+    };
+
+    env.events()
+        .publish((Symbol::new(env, "screening_recorded"),), event);
+}
+
+/// Emit a DonorFollowUp event so the donor can be recalled for counselling
+/// and confirmatory testing after a reactive result
+pub fn emit_donor_follow_up(env: &Env, donor_id: &Address, blood_unit_id: u64, marker: TtiMarker) {
+    let event = DonorFollowUpEvent {
+        donor_id: donor_id.clone(),
+        blood_unit_id,
+        marker,
+        flagged_at: env.ledger().timestamp(),
+    };
+
+    env.events()
+        .publish((Symbol::new(env, "donor_follow_up"),), event);
+}
+
+/// Emit a DonorRecall summary event
+pub fn emit_donor_recall(env: &Env, report: &RecallReport) {
+    let event = DonorRecallEvent {
+        donor_id: report.donor_id.clone(),
+        reason: report.reason,
+        quarantined_count: report.quarantined_units.len(),
+        lookback_count: report.lookback.len(),
+        recalled_at: report.recalled_at,
+    };
+
+    env.events()
+        .publish((Symbol::new(env, "donor_recall"),), event);
+}
+
+/// Emit a UnitRecalled event for a unit that already reached a hospital
+///
+/// Topics include the unit ID so hospitals can match it against their
+/// receiving records.
+pub fn emit_unit_recalled(
+    env: &Env,
+    donor_id: &Address,
+    reason: RecallReason,
+    entry: &LookbackEntry,
+) {
+    let event = UnitRecalledEvent {
+        blood_unit_id: entry.blood_unit_id,
+        donor_id: donor_id.clone(),
+        reason,
+        status: entry.status,
+        destination: entry.destination.clone(),
+        delivered_at: entry.delivered_at,
+        recalled_at: env.ledger().timestamp(),
+    };
+
+    env.events().publish(
+        (Symbol::new(env, "unit_recalled"), entry.blood_unit_id),
+        event,
+    );
+}
+
+/// Emit a MetadataUpdated event when unit metadata is set or removed
+pub fn emit_metadata_updated(
+    env: &Env,
+    blood_unit_id: u64,
+    key: &Symbol,
+    value: Option<String>,
+    changed_by: &Address,
+) {
+    let event = MetadataUpdatedEvent {
+        blood_unit_id,
+        key: key.clone(),
+        value,
+        changed_by: changed_by.clone(),
+        changed_at: env.ledger().timestamp(),
+    };
+
+    env.events()
+        .publish((Symbol::new(env, "metadata_updated"),), event);
+}
+
+/// Emit a BankTransfer event under the given topic
+/// (`transfer_initiated` or `transfer_received`)
+pub fn emit_bank_transfer(env: &Env, topic: &str, blood_unit_id: u64, transfer: &BankTransfer) {
+    let event = BankTransferEvent {
+        blood_unit_id,
+        from_bank: transfer.from_bank.clone(),
+        to_bank: transfer.to_bank.clone(),
+        timestamp: env.ledger().timestamp(),
+    };
+
+    env.events()
+        .publish((Symbol::new(env, topic), blood_unit_id), event);
+}
+
+/// Emit a stock alert under the given topic (`low_stock` or `restocked`)
+pub fn emit_stock_alert(env: &Env, topic: &str, bank_id: &Address, level: &StockLevel) {
+    let event = StockAlertEvent {
+        bank_id: bank_id.clone(),
+        blood_type: level.blood_type,
+        component: level.component,
+        available_ml: level.available_ml,
+        min_available_ml: level.min_available_ml,
+        timestamp: env.ledger().timestamp(),
+    };
+
+    env.events()
+        .publish((Symbol::new(env, topic), bank_id.clone()), event);
+}
+
+/// Emit a Reservation event under the given topic
+/// (`unit_reserved` or `reservation_released`)
+pub fn emit_reservation(env: &Env, topic: &str, blood_unit_id: u64, reservation: &Reservation) {
+    let event = ReservationEvent {
+        blood_unit_id,
+        holder: reservation.holder.clone(),
+        request_id: reservation.request_id,
+        expires_at: reservation.expires_at,
+        timestamp: env.ledger().timestamp(),
+    };
+
+    env.events()
+        .publish((Symbol::new(env, topic), blood_unit_id), event);
+}
+
+/// Emit a CustodyHandoff event under the given topic
+/// (`handoff_initiated`, `handoff_accepted` or `handoff_cancelled`)
+pub fn emit_custody_handoff(env: &Env, topic: &str, blood_unit_id: u64, handoff: &CustodyHandoff) {
+    let event = CustodyHandoffEvent {
+        blood_unit_id,
+        from: handoff.from.clone(),
+        to: handoff.to.clone(),
+        location: handoff.location.clone(),
+        timestamp: env.ledger().timestamp(),
+    };
+
+    env.events()
+        .publish((Symbol::new(env, topic), blood_unit_id), event);
+}
+
+/// Emit a HistoryArchived event carrying the unit's updated history summary
+pub fn emit_history_archived(env: &Env, summary: &StatusHistorySummary) {
+    env.events().publish(
+        (Symbol::new(env, "history_archived"), summary.blood_unit_id),
+        summary.clone(),
+    );
+}
+
+/// Emit a CollectionRecorded event carrying the new collection
+pub fn emit_collection_recorded(env: &Env, collection: &Collection) {
+    env.events().publish(
+        (
+            Symbol::new(env, "collection_recorded"),
+            collection.donor_id.clone(),
+        ),
+        collection.clone(),
+    );
+}
+
+/// Emit a DonorDeferral record under the given topic
+/// (`donor_deferred` or `deferral_lifted`)
+pub fn emit_donor_deferral(env: &Env, topic: &str, deferral: &DonorDeferral) {
+    env.events().publish(
+        (Symbol::new(env, topic), deferral.donor_id.clone()),
+        deferral.clone(),
+    );
+}
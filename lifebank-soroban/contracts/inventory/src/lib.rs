@@ -6,10 +6,17 @@ mod storage;
 mod types;
 mod validation;
 
-use crate::error::ContractError;
-use crate::types::{BloodStatus, BloodType, BloodUnit, DataKey};
+pub use crate::error::ContractError;
+pub use crate::types::{
+    BankTransfer, BatchStatusUpdate, BatchUpdateOutcome, BatchUpdateResult, BloodComponent,
+    BloodStatus, BloodType, BloodUnit, Collection, CustodyHandoff, DataKey, DeferralReason,
+    DeliveryRecord, DiscardReason, DonationType, DonorDeferral, LookbackEntry, MetadataChange,
+    NewUnit, RecallReason, RecallReport, Reservation, Role, ScreeningRecord, ScreeningResult,
+    StatusChangeHistory, StatusDetail, StatusHistoryPage, StatusHistorySummary, StockLevel,
+    TtiMarker, WastageStats,
+};
 
-use soroban_sdk::{contract, contractimpl, Address, Env, Map, String, Vec};
+use soroban_sdk::{contract, contractimpl, Address, Env, Map, String, Symbol, Vec};
 #[contract]
 pub struct InventoryContract;
 
@@ -37,12 +44,169 @@ impl InventoryContract {
         Ok(())
     }
 
+    /// Grant a supply-chain role to an address
+    ///
+    /// Each address holds one role. Granting the role it already holds is a
+    /// no-op; a different role must be revoked first.
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `account` - Address receiving the role
+    /// * `role` - Role to grant (`Admin` and `System` can't be granted)
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `InvalidInput`: Role can't be granted
+    /// - `RoleAlreadyAssigned`: Address already holds a different role
+    pub fn grant_role(env: Env, account: Address, role: Role) -> Result<(), ContractError> {
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(ContractError::NotInitialized);
+        }
+
+        // Only admin manages roles
+        let admin = storage::get_admin(&env);
+        admin.require_auth();
+
+        if matches!(role, Role::Admin | Role::System) {
+            return Err(ContractError::InvalidInput);
+        }
+
+        Self::assign_role(&env, &account, role)
+    }
+
+    /// Revoke an address's role
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `NotFound`: Address holds no role
+    pub fn revoke_role(env: Env, account: Address) -> Result<(), ContractError> {
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(ContractError::NotInitialized);
+        }
+
+        let admin = storage::get_admin(&env);
+        admin.require_auth();
+
+        let role = storage::get_role(&env, &account).ok_or(ContractError::NotFound)?;
+        if role == Role::Admin {
+            return Err(ContractError::InvalidInput);
+        }
+        storage::remove_role(&env, &account, role);
+
+        Ok(())
+    }
+
+    /// Get the role an address holds, if any
+    pub fn get_role(env: Env, account: Address) -> Option<Role> {
+        storage::get_role(&env, &account)
+    }
+
+    /// Authorize a lab to record infectious-disease screening results
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `lab` - Lab address to authorize
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `RoleAlreadyAssigned`: Address already holds a different role
+    pub fn authorize_lab(env: Env, lab: Address) -> Result<(), ContractError> {
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(ContractError::NotInitialized);
+        }
+
+        // Only admin can authorize labs
+        let admin = storage::get_admin(&env);
+        admin.require_auth();
+
+        Self::assign_role(&env, &lab, Role::Lab)
+    }
+
+    /// Revoke lab authorization
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `lab` - Lab address to revoke
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    pub fn revoke_lab(env: Env, lab: Address) -> Result<(), ContractError> {
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(ContractError::NotInitialized);
+        }
+
+        let admin = storage::get_admin(&env);
+        admin.require_auth();
+
+        storage::remove_role(&env, &lab, Role::Lab);
+
+        Ok(())
+    }
+
+    /// Check if a lab is authorized to record screening results
+    pub fn is_lab_authorized(env: Env, lab: Address) -> bool {
+        storage::is_authorized_lab(&env, &lab)
+    }
+
+    /// Authorize a blood bank to hold inventory
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `bank` - Bank address to authorize
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `RoleAlreadyAssigned`: Address already holds a different role
+    pub fn authorize_bank(env: Env, bank: Address) -> Result<(), ContractError> {
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(ContractError::NotInitialized);
+        }
+
+        // Only admin can authorize banks
+        let admin = storage::get_admin(&env);
+        admin.require_auth();
+
+        Self::assign_role(&env, &bank, Role::BankOperator)
+    }
+
+    /// Revoke blood bank authorization
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    pub fn revoke_bank(env: Env, bank: Address) -> Result<(), ContractError> {
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(ContractError::NotInitialized);
+        }
+
+        let admin = storage::get_admin(&env);
+        admin.require_auth();
+
+        storage::remove_role(&env, &bank, Role::BankOperator);
+
+        Ok(())
+    }
+
+    /// Check if a blood bank is authorized
+    pub fn is_bank_authorized(env: Env, bank: Address) -> bool {
+        storage::is_authorized_bank(&env, &bank)
+    }
+
     /// Register a new blood donation into the inventory
     ///
+    /// The unit starts in `Quarantined` and only becomes `Available` once
+    /// every required TTI marker has been screened negative.
+    ///
+    /// A donor's unit joins their open collection at this bank (see
+    /// `record_collection`) and takes its collection time. Without one, a
+    /// whole blood collection is opened for the donor.
+    ///
     /// # Arguments
     /// * `env` - Contract environment
     /// * `bank_id` - Blood bank's address (must be authorized)
+    /// * `din` - ISBT 128 DIN with check character (14 characters)
+    /// * `product_code` - ISBT 128 product code (8 characters)
     /// * `blood_type` - Type of blood (A+, A-, B+, B-, AB+, AB-, O+, O-)
+    /// * `component` - Blood product component
     /// * `quantity_ml` - Quantity in milliliters (100-600ml)
     /// * `expiration_timestamp` - Unix timestamp when blood expires
     /// * `donor_id` - Optional donor address (None for anonymous)
@@ -53,15 +217,26 @@ impl InventoryContract {
     /// # Errors
     /// - `NotInitialized`: Contract not initialized
     /// - `NotAuthorizedBloodBank`: Bank is not authorized
+    /// - `InvalidDin`: DIN format or check character is invalid
+    /// - `InvalidProductCode`: Product code format is invalid
+    /// - `DuplicateBloodUnit`: A unit with this DIN is already registered
     /// - `InvalidQuantity`: Quantity outside acceptable range
     /// - `InvalidExpiration`: Expiration date is invalid
+    /// - `DonorDeferred`: Donor is under a temporary deferral
+    /// - `DonorPermanentlyDeferred`: Donor is permanently deferred
+    /// - `DonationIntervalNotMet`: Unit would open a collection before the
+    ///   donor's last one allows
     ///
     /// # Events
     /// Emits `BloodRegistered` event with all blood unit details
+    #[allow(clippy::too_many_arguments)]
     pub fn register_blood(
         env: Env,
         bank_id: Address,
+        din: String,
+        product_code: String,
         blood_type: BloodType,
+        component: BloodComponent,
         quantity_ml: u32,
         expiration_timestamp: u64,
         donor_id: Option<Address>,
@@ -80,25 +255,31 @@ impl InventoryContract {
         }
 
         // 4. Validate input parameters
-        validation::validate_blood_registration(&env, quantity_ml, expiration_timestamp)?;
-        validation::validate_minimum_shelf_life(&env, expiration_timestamp)?;
+        let new_unit = NewUnit {
+            din,
+            product_code,
+            blood_type,
+            component,
+            quantity_ml,
+            expiration_timestamp,
+            donor_id,
+        };
+        validation::validate_new_unit(&env, &new_unit)?;
+        if let Some(donor) = &new_unit.donor_id {
+            validation::validate_donor_registration(&env, &bank_id, donor)?;
+        }
+
+        if storage::get_unit_id_by_din(&env, &new_unit.din).is_some() {
+            return Err(ContractError::DuplicateBloodUnit);
+        }
 
         // 5. Generate unique blood unit ID
         let blood_unit_id = storage::increment_blood_unit_id(&env);
 
         // 6. Create blood unit struct
         let current_time = env.ledger().timestamp();
-        let blood_unit = BloodUnit {
-            id: blood_unit_id,
-            blood_type,
-            quantity_ml,
-            bank_id: bank_id.clone(),
-            donor_id: donor_id.clone(),
-            donation_timestamp: current_time,
-            expiration_timestamp,
-            status: BloodStatus::Available,
-            metadata: Map::new(&env),
-        };
+        let mut blood_unit = Self::new_blood_unit(&env, blood_unit_id, &bank_id, new_unit);
+        Self::attach_to_collection(&env, &mut blood_unit);
 
         // 7. Validate the complete blood unit
         blood_unit.validate(current_time)?;
@@ -111,21 +292,136 @@ impl InventoryContract {
         storage::add_to_bank_index(&env, &blood_unit);
         storage::add_to_status_index(&env, &blood_unit);
         storage::add_to_donor_index(&env, &blood_unit);
+        storage::set_din_index(&env, &blood_unit);
+        Self::update_stock(&env, &bank_id, blood_type, component, |level| {
+            level.add(BloodStatus::Quarantined, quantity_ml)
+        });
 
         // 10. Emit event
-        events::emit_blood_registered(
-            &env,
-            blood_unit_id,
-            &bank_id,
-            blood_type,
-            quantity_ml,
-            expiration_timestamp,
-        );
+        events::emit_blood_registered(&env, &blood_unit);
 
         // 11. Return blood unit ID
         Ok(blood_unit_id)
     }
 
+    /// Register a batch of donations in one call (e.g. after a donation drive)
+    ///
+    /// Every entry is validated before anything is written. IDs are assigned
+    /// contiguously and each index is written once for the whole batch.
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `bank_id` - Blood bank's address (must be authorized)
+    /// * `units` - Units to register (1-100 entries)
+    ///
+    /// # Returns
+    /// Assigned blood unit IDs, in the order of `units`
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `NotAuthorizedBloodBank`: Bank is not authorized
+    /// - `InvalidInput`: Batch is empty or too large
+    /// - `DuplicateBloodUnit`: A DIN is already registered or repeated in the batch
+    /// - Any error from single registration validation
+    ///
+    /// # Events
+    /// Emits `BloodRegistered` per unit and one `BatchRegistered`
+    pub fn register_blood_batch(
+        env: Env,
+        bank_id: Address,
+        units: Vec<NewUnit>,
+    ) -> Result<Vec<u64>, ContractError> {
+        bank_id.require_auth();
+
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(ContractError::NotInitialized);
+        }
+
+        if !storage::is_authorized_bank(&env, &bank_id) {
+            return Err(ContractError::NotAuthorizedBloodBank);
+        }
+
+        if units.is_empty() || units.len() > storage::MAX_REGISTRATION_BATCH_SIZE {
+            return Err(ContractError::InvalidInput);
+        }
+
+        // Validate every entry before writing anything
+        let mut dins = Vec::new(&env);
+        for new_unit in units.iter() {
+            validation::validate_new_unit(&env, &new_unit)?;
+            if let Some(donor) = &new_unit.donor_id {
+                validation::validate_donor_registration(&env, &bank_id, donor)?;
+            }
+
+            if dins.contains(&new_unit.din)
+                || storage::get_unit_id_by_din(&env, &new_unit.din).is_some()
+            {
+                return Err(ContractError::DuplicateBloodUnit);
+            }
+            dins.push_back(new_unit.din.clone());
+        }
+
+        let first_id = storage::allocate_blood_unit_ids(&env, units.len() as u64);
+        let current_time = env.ledger().timestamp();
+
+        let mut unit_ids = Vec::new(&env);
+        let mut by_type: Map<BloodType, Vec<u64>> = Map::new(&env);
+        let mut by_donor: Map<Address, Vec<u64>> = Map::new(&env);
+        let mut stock = storage::get_bank_stock(&env, &bank_id);
+
+        for (offset, new_unit) in units.iter().enumerate() {
+            let blood_unit_id = first_id + offset as u64;
+            let mut blood_unit = Self::new_blood_unit(&env, blood_unit_id, &bank_id, new_unit);
+            Self::attach_to_collection(&env, &mut blood_unit);
+            blood_unit.validate(current_time)?;
+
+            storage::set_blood_unit(&env, &blood_unit);
+            storage::set_din_index(&env, &blood_unit);
+
+            let mut type_ids = by_type.get(blood_unit.blood_type).unwrap_or(Vec::new(&env));
+            type_ids.push_back(blood_unit_id);
+            by_type.set(blood_unit.blood_type, type_ids);
+
+            if let Some(donor) = &blood_unit.donor_id {
+                let mut donor_ids = by_donor.get(donor.clone()).unwrap_or(Vec::new(&env));
+                donor_ids.push_back(blood_unit_id);
+                by_donor.set(donor.clone(), donor_ids);
+            }
+
+            let quantity_ml = blood_unit.quantity_ml;
+            Self::apply_stock_change(
+                &env,
+                &bank_id,
+                &mut stock,
+                blood_unit.blood_type,
+                blood_unit.component,
+                |level| level.add(BloodStatus::Quarantined, quantity_ml),
+            );
+
+            events::emit_blood_registered(&env, &blood_unit);
+            unit_ids.push_back(blood_unit_id);
+        }
+
+        // One write per index for the whole batch
+        storage::append_to_index(&env, &DataKey::BankIndex(bank_id.clone()), &unit_ids);
+        storage::append_to_index(
+            &env,
+            &DataKey::StatusIndex(BloodStatus::Quarantined),
+            &unit_ids,
+        );
+        for (blood_type, ids) in by_type.iter() {
+            storage::append_to_index(&env, &DataKey::BloodTypeIndex(blood_type), &ids);
+        }
+        for (donor, ids) in by_donor.iter() {
+            storage::append_to_index(&env, &DataKey::DonorIndex(donor), &ids);
+        }
+        storage::set_bank_stock(&env, &bank_id, &stock);
+
+        events::emit_batch_registered(&env, &bank_id, &unit_ids);
+
+        Ok(unit_ids)
+    }
+
     /// Get blood unit details by ID
     ///
     /// # Arguments
@@ -141,6 +437,29 @@ impl InventoryContract {
         storage::get_blood_unit(&env, blood_unit_id).ok_or(ContractError::NotFound)
     }
 
+    /// Resolve a scanned ISBT 128 DIN to its blood unit
+    ///
+    /// # Errors
+    /// - `NotFound`: No unit is registered under this DIN
+    pub fn get_unit_by_din(env: Env, din: String) -> Result<BloodUnit, ContractError> {
+        let unit_id = storage::get_unit_id_by_din(&env, &din).ok_or(ContractError::NotFound)?;
+        storage::get_blood_unit(&env, unit_id).ok_or(ContractError::NotFound)
+    }
+
+    /// Move a blood unit to a new status
+    ///
+    /// The caller's role must be allowed to make the transition under the
+    /// policy table in `validation::validate_role_transition`. Bank
+    /// operators may only move their own units; couriers and hospital
+    /// receivers only units in their custody or reserved for them.
+    ///
+    /// # Errors
+    /// - `Unauthorized`: Caller has no role, or is a bank that doesn't hold the unit
+    /// - `NotFound`: Blood unit doesn't exist
+    /// - `InsufficientPermissions`: Caller's role can't make this transition
+    /// - `NotCustodian`: Courier or hospital receiver doesn't hold the unit
+    /// - `InvalidStatusTransition`: State machine doesn't allow the transition
+    /// - `BloodUnitExpired`: Unit has expired and the new status isn't `Expired`
     pub fn update_status(
         env: Env,
         unit_id: u64,
@@ -150,51 +469,43 @@ impl InventoryContract {
     ) -> Result<BloodUnit, ContractError> {
         authorized_by.require_auth();
 
-        let admin = storage::get_admin(&env);
-        if authorized_by != admin {
-            return Err(ContractError::Unauthorized);
-        }
+        let role = storage::get_role(&env, &authorized_by).ok_or(ContractError::Unauthorized)?;
 
         let mut blood_unit =
             storage::get_blood_unit(&env, unit_id).ok_or(ContractError::NotFound)?;
 
-        let current_time = env.ledger().timestamp();
-
-        if blood_unit.is_expired(current_time) {
-            return Err(ContractError::BloodUnitExpired);
-        }
-
-        if blood_unit.status.is_terminal() {
-            return Err(ContractError::InvalidStatusTransition);
+        // Banks may only move their own stock
+        if role == Role::BankOperator && authorized_by != blood_unit.bank_id {
+            return Err(ContractError::Unauthorized);
         }
 
-        validation::validate_status_transition(blood_unit.status, new_status)?;
-
-        let old_status = blood_unit.status;
-        blood_unit.status = new_status;
-        storage::set_blood_unit(&env, &blood_unit);
+        validation::validate_role_transition(role, blood_unit.status, new_status)?;
 
-        storage::record_status_change(
-            &env,
-            unit_id,
-            old_status,
-            new_status,
-            &authorized_by,
-            reason.clone(),
-        );
+        // Couriers and hospitals may only move units they hold
+        if matches!(role, Role::Courier | Role::HospitalReceiver)
+            && !Self::holds_unit(&env, &blood_unit, &authorized_by)
+        {
+            return Err(ContractError::NotCustodian);
+        }
 
-        events::emit_status_change(
-            &env,
-            unit_id,
-            old_status,
-            new_status,
-            &authorized_by,
-            reason,
-        );
+        Self::transition_unit(&env, &mut blood_unit, new_status, &authorized_by, reason)?;
 
         Ok(blood_unit)
     }
 
+    /// Record that a unit in transit has reached its destination
+    ///
+    /// Goes through `update_status`, so the caller's role must allow the
+    /// transition.
+    ///
+    /// # Errors
+    /// - `Unauthorized`: Caller has no role, or is a bank that doesn't hold the unit
+    /// - `NotFound`: Blood unit doesn't exist
+    /// - `InsufficientPermissions`: Caller's role can't deliver units
+    /// - `NotCustodian`: Courier or hospital receiver doesn't hold the unit
+    /// - `InvalidStatusTransition`: Unit is not in transit
+    /// - `TransferPending`: Unit is moving between banks
+    /// - `BloodUnitExpired`: Unit has expired
     pub fn mark_delivered(
         env: Env,
         unit_id: u64,
@@ -210,6 +521,16 @@ impl InventoryContract {
         )
     }
 
+    /// Take a unit past its shelf life out of stock
+    ///
+    /// Goes through `update_status`, so the caller's role must allow the
+    /// transition.
+    ///
+    /// # Errors
+    /// - `Unauthorized`: Caller has no role, or is a bank that doesn't hold the unit
+    /// - `NotFound`: Blood unit doesn't exist
+    /// - `InsufficientPermissions`: Caller's role can't expire units
+    /// - `InvalidStatusTransition`: Unit is already in a terminal state
     pub fn mark_expired(
         env: Env,
         unit_id: u64,
@@ -225,13 +546,89 @@ impl InventoryContract {
         )
     }
 
-    pub fn batch_update_status(
+    /// Record that a delivered unit has been transfused
+    ///
+    /// Goes through `update_status`, so the caller's role must allow the
+    /// transition.
+    ///
+    /// # Errors
+    /// - `Unauthorized`: Caller has no role, or is a bank that doesn't hold the unit
+    /// - `NotFound`: Blood unit doesn't exist
+    /// - `InsufficientPermissions`: Caller's role can't record transfusions
+    /// - `NotCustodian`: Hospital receiver doesn't hold the unit
+    /// - `InvalidStatusTransition`: Unit has not been delivered
+    /// - `BloodUnitExpired`: Unit has expired
+    pub fn mark_transfused(
         env: Env,
-        unit_ids: Vec<u64>,
-        new_status: BloodStatus,
+        unit_id: u64,
         authorized_by: Address,
-        reason: Option<String>,
-    ) -> Result<u64, ContractError> {
+    ) -> Result<BloodUnit, ContractError> {
+        let reason = String::from_str(&env, "Transfused");
+        Self::update_status(
+            env,
+            unit_id,
+            BloodStatus::Transfused,
+            authorized_by,
+            Some(reason),
+        )
+    }
+
+    /// Discard a blood unit with a structured reason
+    ///
+    /// Can be called by admin or the bank holding the unit. Units can be
+    /// discarded from any non-terminal status, including after expiry of
+    /// their shelf life, so that disposal is always recorded.
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `unit_id` - ID of the blood unit to discard
+    /// * `authorized_by` - Admin or holding bank
+    /// * `reason` - Structured discard reason
+    /// * `note` - Optional free-text note recorded in history
+    ///
+    /// # Errors
+    /// - `NotFound`: Blood unit doesn't exist
+    /// - `Unauthorized`: Caller is neither admin nor the holding bank
+    /// - `InvalidStatusTransition`: Unit is already in a terminal state
+    ///
+    /// # Events
+    /// Emits `StatusChange` and `UnitDiscarded`
+    pub fn discard_unit(
+        env: Env,
+        unit_id: u64,
+        authorized_by: Address,
+        reason: DiscardReason,
+        note: Option<String>,
+    ) -> Result<BloodUnit, ContractError> {
+        authorized_by.require_auth();
+
+        let mut blood_unit =
+            storage::get_blood_unit(&env, unit_id).ok_or(ContractError::NotFound)?;
+
+        let admin = storage::get_admin(&env);
+        if authorized_by != admin && authorized_by != blood_unit.bank_id {
+            return Err(ContractError::Unauthorized);
+        }
+
+        validation::validate_status_transition(blood_unit.status, BloodStatus::Discarded)?;
+
+        Self::discard(&env, &mut blood_unit, &authorized_by, reason, note);
+
+        Ok(blood_unit)
+    }
+
+    /// Get wastage counters for a bank and discard reason
+    pub fn get_wastage(env: Env, bank: Address, reason: DiscardReason) -> WastageStats {
+        storage::get_wastage(&env, &bank, reason)
+    }
+
+    pub fn batch_update_status(
+        env: Env,
+        unit_ids: Vec<u64>,
+        new_status: BloodStatus,
+        authorized_by: Address,
+        reason: Option<String>,
+    ) -> Result<u64, ContractError> {
         authorized_by.require_auth();
 
         let admin = storage::get_admin(&env);
@@ -239,7 +636,6 @@ impl InventoryContract {
             return Err(ContractError::Unauthorized);
         }
 
-        let current_time = env.ledger().timestamp();
         let mut updated_count = 0u64;
 
         for i in 0..unit_ids.len() {
@@ -247,37 +643,13 @@ impl InventoryContract {
             let mut blood_unit =
                 storage::get_blood_unit(&env, unit_id).ok_or(ContractError::NotFound)?;
 
-            if blood_unit.is_expired(current_time) {
-                return Err(ContractError::BloodUnitExpired);
-            }
-
-            if blood_unit.status.is_terminal() {
-                return Err(ContractError::InvalidStatusTransition);
-            }
-
-            validation::validate_status_transition(blood_unit.status, new_status)?;
-
-            let old_status = blood_unit.status;
-            blood_unit.status = new_status;
-            storage::set_blood_unit(&env, &blood_unit);
-
-            storage::record_status_change(
-                &env,
-                unit_id,
-                old_status,
-                new_status,
-                &authorized_by,
-                reason.clone(),
-            );
-
-            events::emit_status_change(
+            Self::transition_unit(
                 &env,
-                unit_id,
-                old_status,
+                &mut blood_unit,
                 new_status,
                 &authorized_by,
                 reason.clone(),
-            );
+            )?;
 
             updated_count += 1;
         }
@@ -285,13 +657,1676 @@ impl InventoryContract {
         Ok(updated_count)
     }
 
-    pub fn get_status_history(env: Env, unit_id: u64) -> Vec<crate::types::StatusChangeHistory> {
+    /// Apply a batch status update, skipping units that can't transition
+    ///
+    /// Unlike `batch_update_status`, one expired or invalid unit doesn't
+    /// abort the call: every valid transition is applied and each unit gets
+    /// its own outcome.
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `update` - Units, target status and optional reason
+    /// * `authorized_by` - Admin address
+    ///
+    /// # Returns
+    /// One result per unit, in the order of `update.blood_unit_ids`
+    ///
+    /// # Errors
+    /// - `Unauthorized`: Caller is not admin
+    pub fn batch_update_status_partial(
+        env: Env,
+        update: BatchStatusUpdate,
+        authorized_by: Address,
+    ) -> Result<Vec<BatchUpdateResult>, ContractError> {
+        authorized_by.require_auth();
+
+        let admin = storage::get_admin(&env);
+        if authorized_by != admin {
+            return Err(ContractError::Unauthorized);
+        }
+
+        let mut results = Vec::new(&env);
+
+        for unit_id in update.blood_unit_ids.iter() {
+            let outcome = match storage::get_blood_unit(&env, unit_id) {
+                None => BatchUpdateOutcome::NotFound,
+                Some(mut blood_unit) => match Self::transition_unit(
+                    &env,
+                    &mut blood_unit,
+                    update.new_status,
+                    &authorized_by,
+                    update.reason.clone(),
+                ) {
+                    Ok(()) => BatchUpdateOutcome::Updated,
+                    Err(error) => BatchUpdateOutcome::Skipped(error as u32),
+                },
+            };
+
+            results.push_back(BatchUpdateResult {
+                blood_unit_id: unit_id,
+                outcome,
+            });
+        }
+
+        Ok(results)
+    }
+
+    /// Get a unit's status history still in storage, oldest first
+    ///
+    /// Entries rolled up by `archive_status_history` are not included.
+    pub fn get_status_history(env: Env, unit_id: u64) -> Vec<StatusChangeHistory> {
         storage::get_status_history(&env, unit_id)
     }
 
+    /// Get one page of a unit's status history, oldest first
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `unit_id` - Blood unit ID
+    /// * `cursor` - Position to start from (0 for the first page, then `next_cursor`)
+    /// * `limit` - Maximum entries to return (capped at `MAX_HISTORY_PAGE_SIZE`)
+    pub fn get_status_history_page(
+        env: Env,
+        unit_id: u64,
+        cursor: u64,
+        limit: u32,
+    ) -> StatusHistoryPage {
+        storage::get_status_history_page(&env, unit_id, cursor, limit)
+    }
+
+    /// Get a status change record by its history ID
+    ///
+    /// # Errors
+    /// - `NotFound`: No record with that ID (never recorded, or archived)
+    pub fn get_status_change(
+        env: Env,
+        history_id: u64,
+    ) -> Result<StatusChangeHistory, ContractError> {
+        storage::get_status_change(&env, history_id).ok_or(ContractError::NotFound)
+    }
+
+    /// Get the total number of status changes recorded for a unit,
+    /// including archived ones
     pub fn get_status_change_count(env: Env, unit_id: u64) -> u64 {
         storage::get_blood_unit_status_change_count(&env, unit_id)
     }
+
+    /// Get the hash-chained digest of a unit's archived history, if any
+    pub fn get_status_history_summary(env: Env, unit_id: u64) -> Option<StatusHistorySummary> {
+        storage::get_status_history_summary(&env, unit_id)
+    }
+
+    /// Roll a unit's older status history into its hash-chained summary
+    ///
+    /// Removes every entry except the newest `keep_latest` from storage and
+    /// folds them into the unit's `StatusHistorySummary`. Archived entries no
+    /// longer appear in history queries. Only units in a terminal state can
+    /// be archived; their delivery record is kept for recall lookback.
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `unit_id` - Blood unit ID
+    /// * `keep_latest` - Number of most recent entries to keep in storage
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `NotFound`: Blood unit doesn't exist
+    /// - `InvalidStatus`: Unit is not in a terminal state
+    /// - `InvalidInput`: No entries older than `keep_latest` to archive
+    pub fn archive_status_history(
+        env: Env,
+        unit_id: u64,
+        keep_latest: u64,
+    ) -> Result<StatusHistorySummary, ContractError> {
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(ContractError::NotInitialized);
+        }
+
+        let admin = storage::get_admin(&env);
+        admin.require_auth();
+
+        let blood_unit = storage::get_blood_unit(&env, unit_id).ok_or(ContractError::NotFound)?;
+        if !blood_unit.status.is_terminal() {
+            return Err(ContractError::InvalidStatus);
+        }
+
+        let summary = storage::archive_status_history(&env, unit_id, keep_latest)
+            .ok_or(ContractError::InvalidInput)?;
+
+        events::emit_history_archived(&env, &summary);
+
+        Ok(summary)
+    }
+
+    /// Record a transfusion-transmissible infection screening result
+    ///
+    /// Once every required marker is negative the unit is released to
+    /// `Available`. A positive result discards the unit immediately and,
+    /// for non-anonymous donations, raises a donor follow-up. Positive
+    /// results are recorded even after the unit has expired, so an infected
+    /// donor is still followed up.
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `unit_id` - ID of the quarantined blood unit
+    /// * `lab` - Lab recording the result (must be authorized)
+    /// * `marker` - Marker that was tested
+    /// * `result` - Test outcome
+    ///
+    /// # Returns
+    /// The blood unit after the result has been applied
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `NotAuthorizedLab`: Lab is not authorized
+    /// - `NotFound`: Blood unit doesn't exist
+    /// - `UnitNotQuarantined`: Unit is not awaiting screening
+    /// - `BloodUnitExpired`: Unit expired while in quarantine (negative
+    ///   results only)
+    /// - `AlreadyExists`: A result for this marker was already recorded
+    ///
+    /// # Events
+    /// Emits `ScreeningRecorded`, plus `StatusChange` on release or discard
+    /// and `DonorFollowUp` on a positive result
+    pub fn record_screening_result(
+        env: Env,
+        unit_id: u64,
+        lab: Address,
+        marker: TtiMarker,
+        result: ScreeningResult,
+    ) -> Result<BloodUnit, ContractError> {
+        lab.require_auth();
+
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(ContractError::NotInitialized);
+        }
+
+        if !storage::is_authorized_lab(&env, &lab) {
+            return Err(ContractError::NotAuthorizedLab);
+        }
+
+        let mut blood_unit =
+            storage::get_blood_unit(&env, unit_id).ok_or(ContractError::NotFound)?;
+
+        if blood_unit.status != BloodStatus::Quarantined {
+            return Err(ContractError::UnitNotQuarantined);
+        }
+
+        let current_time = env.ledger().timestamp();
+        if result == ScreeningResult::Negative && blood_unit.is_expired(current_time) {
+            return Err(ContractError::BloodUnitExpired);
+        }
+
+        if storage::get_screening_results(&env, unit_id).contains_key(marker) {
+            return Err(ContractError::AlreadyExists);
+        }
+
+        let record = ScreeningRecord {
+            marker,
+            result,
+            lab: lab.clone(),
+            tested_at: current_time,
+        };
+        storage::set_screening_result(&env, unit_id, &record);
+        events::emit_screening_recorded(&env, unit_id, marker, result, &lab);
+
+        match result {
+            ScreeningResult::Positive => {
+                Self::discard(
+                    &env,
+                    &mut blood_unit,
+                    &lab,
+                    DiscardReason::FailedScreening,
+                    None,
+                );
+
+                if let Some(donor) = &blood_unit.donor_id {
+                    events::emit_donor_follow_up(&env, donor, unit_id, marker);
+                }
+            }
+            ScreeningResult::Negative => {
+                let results = storage::get_screening_results(&env, unit_id);
+                if validation::validate_screening_cleared(&results).is_ok() {
+                    Self::apply_status_change(
+                        &env,
+                        &mut blood_unit,
+                        BloodStatus::Available,
+                        &lab,
+                        Some(String::from_str(&env, "Screening cleared")),
+                        StatusDetail::None,
+                    );
+                }
+            }
+        }
+
+        Ok(blood_unit)
+    }
+
+    /// Get all screening results recorded for a blood unit
+    pub fn get_screening_results(env: Env, unit_id: u64) -> Vec<ScreeningRecord> {
+        storage::get_screening_results(&env, unit_id).values()
+    }
+
+    /// Recall every unit collected from a donor
+    ///
+    /// Used when a donor later tests positive or reports a post-donation
+    /// illness. Units still in bank custody are moved back to `Quarantined`;
+    /// units already delivered or transfused are returned in the lookback
+    /// section of the report so the receiving hospitals can be traced.
+    ///
+    /// Screening results of quarantined units are cleared, so every marker
+    /// must be re-tested before a recalled unit can return to stock. The
+    /// earlier results remain in the `ScreeningRecorded` events.
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `donor` - Donor whose units are recalled
+    /// * `reason` - Why the recall was raised
+    ///
+    /// # Returns
+    /// Recall report listing quarantined units and lookback entries
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    ///
+    /// # Events
+    /// Emits `StatusChange` for each quarantined unit, `UnitRecalled` for
+    /// each lookback entry and a final `DonorRecall` summary
+    pub fn recall_donor_units(
+        env: Env,
+        donor: Address,
+        reason: RecallReason,
+    ) -> Result<RecallReport, ContractError> {
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(ContractError::NotInitialized);
+        }
+
+        // Only admin can raise a recall
+        let admin = storage::get_admin(&env);
+        admin.require_auth();
+
+        let note = Some(String::from_str(&env, "Donor recall"));
+        let mut quarantined_units = Vec::new(&env);
+        let mut lookback = Vec::new(&env);
+
+        for unit_id in storage::get_units_by_donor(&env, &donor).iter() {
+            let Some(mut blood_unit) = storage::get_blood_unit(&env, unit_id) else {
+                continue;
+            };
+
+            match blood_unit.status {
+                BloodStatus::Quarantined => {
+                    storage::clear_screening_results(&env, unit_id);
+                    quarantined_units.push_back(unit_id);
+                }
+                BloodStatus::Available | BloodStatus::Reserved | BloodStatus::InTransit => {
+                    storage::clear_screening_results(&env, unit_id);
+                    Self::apply_status_change(
+                        &env,
+                        &mut blood_unit,
+                        BloodStatus::Quarantined,
+                        &admin,
+                        note.clone(),
+                        StatusDetail::None,
+                    );
+                    quarantined_units.push_back(unit_id);
+                }
+                BloodStatus::Delivered | BloodStatus::Transfused => {
+                    lookback.push_back(Self::lookback_entry(&env, &blood_unit));
+                }
+                BloodStatus::Expired | BloodStatus::Discarded => {}
+            }
+        }
+
+        let report = RecallReport {
+            donor_id: donor.clone(),
+            reason,
+            recalled_at: env.ledger().timestamp(),
+            quarantined_units,
+            lookback,
+        };
+
+        for entry in report.lookback.iter() {
+            events::emit_unit_recalled(&env, &donor, reason, &entry);
+        }
+        events::emit_donor_recall(&env, &report);
+
+        Ok(report)
+    }
+
+    /// Record a donor's collection before its units are registered
+    ///
+    /// Units registered for the donor at this bank within
+    /// `COLLECTION_WINDOW_SECONDS` join the collection, so the donation
+    /// interval is applied once per collection and follows the donation
+    /// type rather than the components made from it.
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `bank_id` - Blood bank taking the collection (must be authorized)
+    /// * `donor_id` - Donor the collection is taken from
+    /// * `donation_type` - How the donation was collected
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `NotAuthorizedBloodBank`: Bank is not authorized
+    /// - `DonorDeferred`: Donor is under a temporary deferral
+    /// - `DonorPermanentlyDeferred`: Donor is permanently deferred
+    /// - `DonationIntervalNotMet`: Donor's last collection is too recent
+    ///
+    /// # Events
+    /// Emits `collection_recorded` with the new collection
+    pub fn record_collection(
+        env: Env,
+        bank_id: Address,
+        donor_id: Address,
+        donation_type: DonationType,
+    ) -> Result<Collection, ContractError> {
+        bank_id.require_auth();
+
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(ContractError::NotInitialized);
+        }
+
+        if !storage::is_authorized_bank(&env, &bank_id) {
+            return Err(ContractError::NotAuthorizedBloodBank);
+        }
+
+        validation::validate_donor_eligibility(&env, &donor_id)?;
+
+        Ok(Self::open_collection(
+            &env,
+            &bank_id,
+            &donor_id,
+            donation_type,
+        ))
+    }
+
+    /// Get the collection a blood unit came from, if it has a donor
+    pub fn get_unit_collection(env: Env, blood_unit_id: u64) -> Option<Collection> {
+        let collection_id = storage::get_unit_collection(&env, blood_unit_id)?;
+        storage::get_collection(&env, collection_id)
+    }
+
+    /// Defer a donor from donating, temporarily or permanently
+    ///
+    /// Registrations for a deferred donor are rejected until the deferral
+    /// ends. An existing deferral can only be replaced by a longer one.
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `bank_id` - Blood bank recording the deferral (must be authorized)
+    /// * `donor_id` - Donor to defer
+    /// * `reason` - Why the donor is deferred
+    /// * `until` - When the deferral ends (None for a permanent deferral)
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `NotAuthorizedBloodBank`: Bank is not authorized
+    /// - `InvalidTimestamp`: `until` is not in the future
+    /// - `InvalidInput`: Donor already has a deferral lasting at least as long
+    pub fn defer_donor(
+        env: Env,
+        bank_id: Address,
+        donor_id: Address,
+        reason: DeferralReason,
+        until: Option<u64>,
+    ) -> Result<DonorDeferral, ContractError> {
+        bank_id.require_auth();
+
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(ContractError::NotInitialized);
+        }
+
+        if !storage::is_authorized_bank(&env, &bank_id) {
+            return Err(ContractError::NotAuthorizedBloodBank);
+        }
+
+        let current_time = env.ledger().timestamp();
+        if until.is_some_and(|until| until <= current_time) {
+            return Err(ContractError::InvalidTimestamp);
+        }
+
+        if let Some(existing) = storage::get_donor_deferral(&env, &donor_id) {
+            let extends = match (existing.until, until) {
+                (None, _) => false,
+                (Some(_), None) => true,
+                (Some(current), Some(new)) => new > current,
+            };
+            if existing.is_active(current_time) && !extends {
+                return Err(ContractError::InvalidInput);
+            }
+        }
+
+        let deferral = DonorDeferral {
+            donor_id,
+            reason,
+            deferred_by: bank_id,
+            deferred_at: current_time,
+            until,
+        };
+        storage::set_donor_deferral(&env, &deferral);
+
+        events::emit_donor_deferral(&env, "donor_deferred", &deferral);
+
+        Ok(deferral)
+    }
+
+    /// Lift a donor's deferral, including a permanent one
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `NotFound`: Donor has no deferral on record
+    pub fn lift_deferral(env: Env, donor_id: Address) -> Result<(), ContractError> {
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(ContractError::NotInitialized);
+        }
+
+        // Only admin can lift deferrals
+        let admin = storage::get_admin(&env);
+        admin.require_auth();
+
+        let deferral =
+            storage::get_donor_deferral(&env, &donor_id).ok_or(ContractError::NotFound)?;
+        storage::remove_donor_deferral(&env, &donor_id);
+
+        events::emit_donor_deferral(&env, "deferral_lifted", &deferral);
+
+        Ok(())
+    }
+
+    /// Get the deferral on record for a donor, if any
+    ///
+    /// Temporary deferrals stay on record after they end.
+    pub fn get_donor_deferral(env: Env, donor_id: Address) -> Option<DonorDeferral> {
+        storage::get_donor_deferral(&env, &donor_id)
+    }
+
+    /// Get the earliest time a donor may donate again
+    ///
+    /// Combines the donor's deferral with the minimum interval after their
+    /// last collection. Returns None if the donor is permanently deferred.
+    pub fn get_donor_eligible_at(env: Env, donor_id: Address) -> Option<u64> {
+        let next_donation_at = storage::get_donor_next_donation_at(&env, &donor_id);
+
+        match storage::get_donor_deferral(&env, &donor_id) {
+            Some(DonorDeferral { until: None, .. }) => None,
+            Some(DonorDeferral {
+                until: Some(until), ..
+            }) => Some(next_donation_at.max(until)),
+            None => Some(next_donation_at),
+        }
+    }
+
+    /// Add a key to the metadata allowlist
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `key` - Metadata key banks may set (e.g. `irradiated`, `storage_slot`)
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    pub fn allow_metadata_key(env: Env, key: Symbol) -> Result<(), ContractError> {
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(ContractError::NotInitialized);
+        }
+
+        // Only admin manages the allowlist
+        let admin = storage::get_admin(&env);
+        admin.require_auth();
+
+        let mut keys = storage::get_metadata_key_allowlist(&env);
+        if !keys.contains(&key) {
+            keys.push_back(key);
+            storage::set_metadata_key_allowlist(&env, &keys);
+        }
+
+        Ok(())
+    }
+
+    /// Remove a key from the metadata allowlist
+    ///
+    /// Existing entries under the key are kept, but banks can no longer
+    /// set new values for it.
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    pub fn disallow_metadata_key(env: Env, key: Symbol) -> Result<(), ContractError> {
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(ContractError::NotInitialized);
+        }
+
+        let admin = storage::get_admin(&env);
+        admin.require_auth();
+
+        let keys = storage::get_metadata_key_allowlist(&env);
+        if let Some(index) = keys.first_index_of(&key) {
+            let mut keys = keys;
+            keys.remove(index);
+            storage::set_metadata_key_allowlist(&env, &keys);
+        }
+
+        Ok(())
+    }
+
+    /// Get the metadata keys banks are allowed to set
+    pub fn get_allowed_metadata_keys(env: Env) -> Vec<Symbol> {
+        storage::get_metadata_key_allowlist(&env)
+    }
+
+    /// Set a metadata entry on a blood unit
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `unit_id` - ID of the blood unit
+    /// * `authorized_by` - Admin or the bank holding the unit
+    /// * `key` - Allowlisted metadata key
+    /// * `value` - Value to store (max 64 bytes)
+    ///
+    /// # Errors
+    /// - `NotFound`: Blood unit doesn't exist
+    /// - `Unauthorized`: Caller is neither admin nor the holding bank
+    /// - `MetadataKeyNotAllowed`: Key is not on the allowlist
+    /// - `InvalidInput`: Value is empty
+    /// - `MetadataValueTooLong`: Value exceeds the length limit
+    /// - `MetadataLimitExceeded`: Unit already holds the maximum number of entries
+    ///
+    /// # Events
+    /// Emits `MetadataUpdated`
+    pub fn set_unit_metadata(
+        env: Env,
+        unit_id: u64,
+        authorized_by: Address,
+        key: Symbol,
+        value: String,
+    ) -> Result<BloodUnit, ContractError> {
+        authorized_by.require_auth();
+
+        let mut blood_unit =
+            storage::get_blood_unit(&env, unit_id).ok_or(ContractError::NotFound)?;
+
+        let admin = storage::get_admin(&env);
+        if authorized_by != admin && authorized_by != blood_unit.bank_id {
+            return Err(ContractError::Unauthorized);
+        }
+
+        validation::validate_metadata_entry(&env, &blood_unit, &key, &value)?;
+
+        let old_value = blood_unit.metadata.get(key.clone());
+        if old_value.as_ref() == Some(&value) {
+            return Ok(blood_unit);
+        }
+
+        blood_unit.metadata.set(key.clone(), value.clone());
+        Self::apply_metadata_change(
+            &env,
+            &blood_unit,
+            &key,
+            old_value,
+            Some(value),
+            &authorized_by,
+        );
+
+        Ok(blood_unit)
+    }
+
+    /// Remove a metadata entry from a blood unit
+    ///
+    /// # Errors
+    /// - `NotFound`: Blood unit doesn't exist or has no entry for the key
+    /// - `Unauthorized`: Caller is neither admin nor the holding bank
+    ///
+    /// # Events
+    /// Emits `MetadataUpdated` with no value
+    pub fn remove_unit_metadata(
+        env: Env,
+        unit_id: u64,
+        authorized_by: Address,
+        key: Symbol,
+    ) -> Result<BloodUnit, ContractError> {
+        authorized_by.require_auth();
+
+        let mut blood_unit =
+            storage::get_blood_unit(&env, unit_id).ok_or(ContractError::NotFound)?;
+
+        let admin = storage::get_admin(&env);
+        if authorized_by != admin && authorized_by != blood_unit.bank_id {
+            return Err(ContractError::Unauthorized);
+        }
+
+        let old_value = blood_unit
+            .metadata
+            .get(key.clone())
+            .ok_or(ContractError::NotFound)?;
+
+        blood_unit.metadata.remove(key.clone());
+        Self::apply_metadata_change(
+            &env,
+            &blood_unit,
+            &key,
+            Some(old_value),
+            None,
+            &authorized_by,
+        );
+
+        Ok(blood_unit)
+    }
+
+    /// Get metadata change history for a blood unit
+    pub fn get_metadata_history(env: Env, unit_id: u64) -> Vec<MetadataChange> {
+        storage::get_metadata_history(&env, unit_id)
+    }
+
+    /// Get IDs of blood units whose metadata `key` is set to `value`
+    pub fn get_units_by_metadata(env: Env, key: Symbol, value: String) -> Vec<u64> {
+        storage::get_units_by_metadata(&env, &key, &value)
+    }
+
+    /// Start moving an available unit to another bank
+    ///
+    /// The unit goes `InTransit` so it can't be reserved or allocated until
+    /// the receiving bank confirms custody with `receive_bank_transfer`.
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `unit_id` - ID of the blood unit to transfer
+    /// * `from_bank` - Bank currently holding the unit
+    /// * `to_bank` - Authorized bank taking custody
+    ///
+    /// # Errors
+    /// - `NotFound`: Blood unit doesn't exist
+    /// - `Unauthorized`: `from_bank` doesn't hold the unit
+    /// - `InvalidInput`: Source and destination are the same bank
+    /// - `NotAuthorizedBloodBank`: Destination bank is not authorized
+    /// - `BloodUnitExpired`: Unit has expired
+    /// - `BloodUnitNotAvailable`: Unit is not `Available`
+    ///
+    /// # Events
+    /// Emits `StatusChange` and `transfer_initiated`
+    pub fn initiate_bank_transfer(
+        env: Env,
+        unit_id: u64,
+        from_bank: Address,
+        to_bank: Address,
+    ) -> Result<BloodUnit, ContractError> {
+        from_bank.require_auth();
+
+        let mut blood_unit =
+            storage::get_blood_unit(&env, unit_id).ok_or(ContractError::NotFound)?;
+
+        if from_bank != blood_unit.bank_id {
+            return Err(ContractError::Unauthorized);
+        }
+
+        if from_bank == to_bank {
+            return Err(ContractError::InvalidInput);
+        }
+
+        if !storage::is_authorized_bank(&env, &to_bank) {
+            return Err(ContractError::NotAuthorizedBloodBank);
+        }
+
+        if blood_unit.is_expired(env.ledger().timestamp()) {
+            return Err(ContractError::BloodUnitExpired);
+        }
+
+        if blood_unit.status != BloodStatus::Available {
+            return Err(ContractError::BloodUnitNotAvailable);
+        }
+
+        let transfer = BankTransfer {
+            from_bank: from_bank.clone(),
+            to_bank,
+            initiated_at: env.ledger().timestamp(),
+        };
+        storage::set_pending_transfer(&env, unit_id, &transfer);
+
+        Self::apply_status_change(
+            &env,
+            &mut blood_unit,
+            BloodStatus::InTransit,
+            &from_bank,
+            None,
+            StatusDetail::BankTransfer(transfer.clone()),
+        );
+
+        events::emit_bank_transfer(&env, "transfer_initiated", unit_id, &transfer);
+
+        Ok(blood_unit)
+    }
+
+    /// Confirm receipt of a unit transferred from another bank
+    ///
+    /// Custody moves to the receiving bank and the unit becomes `Available`
+    /// in its stock.
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `unit_id` - ID of the blood unit
+    /// * `to_bank` - Bank named as destination when the transfer was initiated
+    ///
+    /// # Errors
+    /// - `NotFound`: Blood unit doesn't exist
+    /// - `NoPendingTransfer`: Unit has no transfer in flight
+    /// - `Unauthorized`: Caller is not the destination bank
+    /// - `NotAuthorizedBloodBank`: Destination bank was revoked in flight
+    /// - `BloodUnitExpired`: Unit expired in transit
+    /// - `InvalidStatusTransition`: Unit is no longer in transit
+    ///
+    /// # Events
+    /// Emits `StatusChange` and `transfer_received`
+    pub fn receive_bank_transfer(
+        env: Env,
+        unit_id: u64,
+        to_bank: Address,
+    ) -> Result<BloodUnit, ContractError> {
+        to_bank.require_auth();
+
+        let mut blood_unit =
+            storage::get_blood_unit(&env, unit_id).ok_or(ContractError::NotFound)?;
+
+        let transfer =
+            storage::get_pending_transfer(&env, unit_id).ok_or(ContractError::NoPendingTransfer)?;
+
+        if to_bank != transfer.to_bank {
+            return Err(ContractError::Unauthorized);
+        }
+
+        if !storage::is_authorized_bank(&env, &to_bank) {
+            return Err(ContractError::NotAuthorizedBloodBank);
+        }
+
+        if blood_unit.is_expired(env.ledger().timestamp()) {
+            return Err(ContractError::BloodUnitExpired);
+        }
+
+        validation::validate_status_transition(blood_unit.status, BloodStatus::Available)?;
+
+        storage::remove_from_bank_index(&env, unit_id, &transfer.from_bank);
+        blood_unit.bank_id = to_bank.clone();
+        storage::add_to_bank_index(&env, &blood_unit);
+        storage::set_custodian(&env, unit_id, &to_bank);
+
+        Self::apply_status_change(
+            &env,
+            &mut blood_unit,
+            BloodStatus::Available,
+            &to_bank,
+            None,
+            StatusDetail::BankTransfer(transfer.clone()),
+        );
+
+        events::emit_bank_transfer(&env, "transfer_received", unit_id, &transfer);
+
+        Ok(blood_unit)
+    }
+
+    /// Get the inter-bank transfer in flight for a unit, if any
+    pub fn get_pending_transfer(env: Env, unit_id: u64) -> Option<BankTransfer> {
+        storage::get_pending_transfer(&env, unit_id)
+    }
+
+    /// Get IDs of blood units held by a bank
+    pub fn get_units_by_bank(env: Env, bank: Address) -> Vec<u64> {
+        storage::get_units_by_bank(&env, &bank)
+    }
+
+    /// Reserve an available unit for a holder until the reservation lapses
+    ///
+    /// Units whose previous reservation has lapsed are treated as available:
+    /// the stale reservation is released first.
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `unit_id` - ID of the blood unit
+    /// * `authorized_by` - Admin, the bank holding the unit or the request contract
+    /// * `holder` - Hospital (or other party) the unit is held for
+    /// * `request_id` - Blood request the unit is held for, if any
+    /// * `duration_seconds` - Reservation length (max 7 days)
+    ///
+    /// # Errors
+    /// - `InvalidInput`: Duration is zero or too long
+    /// - `NotFound`: Blood unit doesn't exist
+    /// - `Unauthorized`: Caller is not admin, the holding bank or the request contract
+    /// - `BloodUnitExpired`: Unit has expired
+    /// - `BloodUnitNotAvailable`: Unit is not available or holds an active reservation
+    ///
+    /// # Events
+    /// Emits `StatusChange` and `unit_reserved`
+    pub fn reserve_unit(
+        env: Env,
+        unit_id: u64,
+        authorized_by: Address,
+        holder: Address,
+        request_id: Option<u64>,
+        duration_seconds: u64,
+    ) -> Result<Reservation, ContractError> {
+        authorized_by.require_auth();
+
+        validation::validate_reservation_duration(duration_seconds)?;
+
+        let mut blood_unit =
+            storage::get_blood_unit(&env, unit_id).ok_or(ContractError::NotFound)?;
+
+        if !Self::can_manage_reservation(&env, &blood_unit, &authorized_by) {
+            return Err(ContractError::Unauthorized);
+        }
+
+        let current_time = env.ledger().timestamp();
+        if blood_unit.is_expired(current_time) {
+            return Err(ContractError::BloodUnitExpired);
+        }
+
+        if Self::has_lapsed_reservation(&env, &blood_unit) {
+            Self::release_reservation(&env, &mut blood_unit, &env.current_contract_address());
+        }
+
+        if blood_unit.status != BloodStatus::Available {
+            return Err(ContractError::BloodUnitNotAvailable);
+        }
+
+        Self::apply_status_change(
+            &env,
+            &mut blood_unit,
+            BloodStatus::Reserved,
+            &authorized_by,
+            None,
+            StatusDetail::None,
+        );
+
+        Ok(Self::place_reservation(
+            &env,
+            unit_id,
+            &holder,
+            request_id,
+            duration_seconds,
+        ))
+    }
+
+    /// Release a reservation that has lapsed, returning the unit to stock
+    ///
+    /// Anyone can call this once the reservation has expired.
+    ///
+    /// # Errors
+    /// - `NotFound`: Blood unit doesn't exist
+    /// - `UnitNotReserved`: Unit has no reservation
+    /// - `ReservationStillActive`: Reservation has not lapsed yet
+    ///
+    /// # Events
+    /// Emits `StatusChange` and `reservation_released`
+    pub fn release_expired_reservation(env: Env, unit_id: u64) -> Result<BloodUnit, ContractError> {
+        let mut blood_unit =
+            storage::get_blood_unit(&env, unit_id).ok_or(ContractError::NotFound)?;
+
+        let reservation =
+            storage::get_reservation(&env, unit_id).ok_or(ContractError::UnitNotReserved)?;
+
+        if !reservation.is_lapsed(env.ledger().timestamp()) {
+            return Err(ContractError::ReservationStillActive);
+        }
+
+        Self::release_reservation(&env, &mut blood_unit, &env.current_contract_address());
+
+        Ok(blood_unit)
+    }
+
+    /// Release an active reservation, returning the unit to `Available`
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `unit_id` - ID of the blood unit
+    /// * `authorized_by` - Admin, the bank holding the unit or the request contract
+    ///
+    /// # Errors
+    /// - `NotFound`: Blood unit doesn't exist
+    /// - `Unauthorized`: Caller is not admin, the holding bank or the request contract
+    /// - `UnitNotReserved`: Unit has no reservation
+    ///
+    /// # Events
+    /// Emits `StatusChange` and `reservation_released`
+    pub fn cancel_reservation(
+        env: Env,
+        unit_id: u64,
+        authorized_by: Address,
+    ) -> Result<BloodUnit, ContractError> {
+        authorized_by.require_auth();
+
+        let mut blood_unit =
+            storage::get_blood_unit(&env, unit_id).ok_or(ContractError::NotFound)?;
+
+        if !Self::can_manage_reservation(&env, &blood_unit, &authorized_by) {
+            return Err(ContractError::Unauthorized);
+        }
+
+        if blood_unit.status != BloodStatus::Reserved
+            || storage::get_reservation(&env, unit_id).is_none()
+        {
+            return Err(ContractError::UnitNotReserved);
+        }
+
+        Self::release_reservation(&env, &mut blood_unit, &authorized_by);
+
+        Ok(blood_unit)
+    }
+
+    /// Dispatch a reserved unit, moving it to `InTransit`
+    ///
+    /// Lets the request contract ship units it reserved, so a shipped unit
+    /// no longer holds a reservation that could lapse and return it to
+    /// stock.
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `unit_id` - ID of the blood unit
+    /// * `authorized_by` - Admin, the bank holding the unit or the request contract
+    ///
+    /// # Errors
+    /// - `NotFound`: Blood unit doesn't exist
+    /// - `Unauthorized`: Caller is not admin, the holding bank or the request contract
+    /// - `UnitNotReserved`: Unit has no reservation
+    /// - `ReservationExpired`: Reservation has lapsed
+    /// - `BloodUnitExpired`: Unit has expired
+    ///
+    /// # Events
+    /// Emits `StatusChange`
+    pub fn dispatch_unit(
+        env: Env,
+        unit_id: u64,
+        authorized_by: Address,
+    ) -> Result<BloodUnit, ContractError> {
+        authorized_by.require_auth();
+
+        let mut blood_unit =
+            storage::get_blood_unit(&env, unit_id).ok_or(ContractError::NotFound)?;
+
+        if !Self::can_manage_reservation(&env, &blood_unit, &authorized_by) {
+            return Err(ContractError::Unauthorized);
+        }
+
+        if blood_unit.status != BloodStatus::Reserved
+            || storage::get_reservation(&env, unit_id).is_none()
+        {
+            return Err(ContractError::UnitNotReserved);
+        }
+
+        Self::transition_unit(
+            &env,
+            &mut blood_unit,
+            BloodStatus::InTransit,
+            &authorized_by,
+            Some(String::from_str(&env, "Dispatched")),
+        )?;
+
+        Ok(blood_unit)
+    }
+
+    /// Set the request contract allowed to reserve and release units
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    pub fn set_request_contract(env: Env, contract: Address) -> Result<(), ContractError> {
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(ContractError::NotInitialized);
+        }
+
+        // Only admin configures the request contract
+        let admin = storage::get_admin(&env);
+        admin.require_auth();
+
+        storage::set_request_contract(&env, &contract);
+
+        Ok(())
+    }
+
+    /// Get the request contract allowed to reserve units, if configured
+    pub fn get_request_contract(env: Env) -> Option<Address> {
+        storage::get_request_contract(&env)
+    }
+
+    /// Get the reservation on a unit, if any
+    pub fn get_reservation(env: Env, unit_id: u64) -> Option<Reservation> {
+        storage::get_reservation(&env, unit_id)
+    }
+
+    /// Set the minimum available volume for a blood type and component
+    ///
+    /// A `low_stock` event fires when available volume drops below the
+    /// threshold and `restocked` when it recovers. Pass 0 to clear it.
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `bank` - Authorized bank setting its own threshold
+    /// * `blood_type` - Blood type
+    /// * `component` - Blood product component
+    /// * `min_available_ml` - Minimum available volume in milliliters
+    ///
+    /// # Errors
+    /// - `NotAuthorizedBloodBank`: Bank is not authorized
+    pub fn set_stock_threshold(
+        env: Env,
+        bank: Address,
+        blood_type: BloodType,
+        component: BloodComponent,
+        min_available_ml: u64,
+    ) -> Result<(), ContractError> {
+        bank.require_auth();
+
+        if !storage::is_authorized_bank(&env, &bank) {
+            return Err(ContractError::NotAuthorizedBloodBank);
+        }
+
+        Self::update_stock(&env, &bank, blood_type, component, |level| {
+            level.min_available_ml = min_available_ml
+        });
+
+        Ok(())
+    }
+
+    /// Get current stock levels and thresholds for a bank
+    ///
+    /// Only units held in stock (quarantined, available, reserved) are
+    /// counted. Units past their shelf life count until marked expired.
+    pub fn get_stock_summary(env: Env, bank: Address) -> Vec<StockLevel> {
+        storage::get_bank_stock(&env, &bank)
+    }
+
+    /// Start a physical handoff of a unit to another custodian
+    ///
+    /// Covers bank to courier, courier to courier and courier to hospital.
+    /// Custody only moves once the receiver calls `accept_handoff`.
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `unit_id` - ID of the blood unit
+    /// * `from` - Current custodian releasing the unit
+    /// * `to` - Custodian receiving the unit (must hold a supply-chain role)
+    /// * `location` - Where the handoff takes place
+    ///
+    /// # Errors
+    /// - `NotFound`: Blood unit doesn't exist
+    /// - `NotCustodian`: `from` is not the current custodian
+    /// - `InvalidInput`: Location is empty or `to` is the current custodian
+    /// - `InsufficientPermissions`: `to` can't take custody
+    /// - `InvalidStatusTransition`: Unit is in a terminal state
+    /// - `HandoffPending`: Another handoff is awaiting acceptance
+    ///
+    /// # Events
+    /// Emits `handoff_initiated`
+    pub fn initiate_handoff(
+        env: Env,
+        unit_id: u64,
+        from: Address,
+        to: Address,
+        location: String,
+    ) -> Result<CustodyHandoff, ContractError> {
+        from.require_auth();
+
+        let blood_unit = storage::get_blood_unit(&env, unit_id).ok_or(ContractError::NotFound)?;
+
+        if storage::get_custodian(&env, &blood_unit) != from {
+            return Err(ContractError::NotCustodian);
+        }
+
+        if location.is_empty() || from == to {
+            return Err(ContractError::InvalidInput);
+        }
+
+        if !matches!(
+            storage::get_role(&env, &to),
+            Some(Role::Admin | Role::BankOperator | Role::Courier | Role::HospitalReceiver)
+        ) {
+            return Err(ContractError::InsufficientPermissions);
+        }
+
+        if blood_unit.status.is_terminal() {
+            return Err(ContractError::InvalidStatusTransition);
+        }
+
+        if storage::get_pending_handoff(&env, unit_id).is_some() {
+            return Err(ContractError::HandoffPending);
+        }
+
+        let handoff = CustodyHandoff {
+            from,
+            to,
+            location,
+            initiated_at: env.ledger().timestamp(),
+            accepted_at: None,
+        };
+        storage::set_pending_handoff(&env, unit_id, &handoff);
+
+        events::emit_custody_handoff(&env, "handoff_initiated", unit_id, &handoff);
+
+        Ok(handoff)
+    }
+
+    /// Accept a pending handoff, taking custody of the unit
+    ///
+    /// # Errors
+    /// - `NoPendingHandoff`: No handoff is awaiting acceptance
+    /// - `Unauthorized`: Caller is not the named receiver
+    ///
+    /// # Events
+    /// Emits `handoff_accepted`
+    pub fn accept_handoff(
+        env: Env,
+        unit_id: u64,
+        to: Address,
+    ) -> Result<CustodyHandoff, ContractError> {
+        to.require_auth();
+
+        let mut handoff =
+            storage::get_pending_handoff(&env, unit_id).ok_or(ContractError::NoPendingHandoff)?;
+
+        if handoff.to != to {
+            return Err(ContractError::Unauthorized);
+        }
+
+        handoff.accepted_at = Some(env.ledger().timestamp());
+
+        storage::remove_pending_handoff(&env, unit_id);
+        storage::append_custody_handoff(&env, unit_id, &handoff);
+        storage::set_custodian(&env, unit_id, &to);
+
+        events::emit_custody_handoff(&env, "handoff_accepted", unit_id, &handoff);
+
+        Ok(handoff)
+    }
+
+    /// Cancel a pending handoff before it is accepted
+    ///
+    /// # Errors
+    /// - `NoPendingHandoff`: No handoff is awaiting acceptance
+    /// - `NotCustodian`: Caller is not the custodian who initiated it
+    ///
+    /// # Events
+    /// Emits `handoff_cancelled`
+    pub fn cancel_handoff(env: Env, unit_id: u64, from: Address) -> Result<(), ContractError> {
+        from.require_auth();
+
+        let handoff =
+            storage::get_pending_handoff(&env, unit_id).ok_or(ContractError::NoPendingHandoff)?;
+
+        if handoff.from != from {
+            return Err(ContractError::NotCustodian);
+        }
+
+        storage::remove_pending_handoff(&env, unit_id);
+
+        events::emit_custody_handoff(&env, "handoff_cancelled", unit_id, &handoff);
+
+        Ok(())
+    }
+
+    /// Get the current custodian of a unit
+    ///
+    /// # Errors
+    /// - `NotFound`: Blood unit doesn't exist
+    pub fn get_custodian(env: Env, unit_id: u64) -> Result<Address, ContractError> {
+        let blood_unit = storage::get_blood_unit(&env, unit_id).ok_or(ContractError::NotFound)?;
+        Ok(storage::get_custodian(&env, &blood_unit))
+    }
+
+    /// Get the handoff awaiting acceptance for a unit, if any
+    pub fn get_pending_handoff(env: Env, unit_id: u64) -> Option<CustodyHandoff> {
+        storage::get_pending_handoff(&env, unit_id)
+    }
+
+    /// Get every completed custody handoff for a unit, oldest first
+    pub fn get_custody_chain(env: Env, unit_id: u64) -> Vec<CustodyHandoff> {
+        storage::get_custody_chain(&env, unit_id)
+    }
+
+    /// Extend the storage TTL of a blood unit and its audit trail
+    ///
+    /// Permissionless, so keepers can keep records alive for audits.
+    /// Active units get the active TTL; terminal units the longer archive TTL.
+    ///
+    /// # Errors
+    /// - `NotFound`: Blood unit doesn't exist
+    pub fn extend_unit_ttl(env: Env, unit_id: u64) -> Result<(), ContractError> {
+        let blood_unit = storage::get_blood_unit(&env, unit_id).ok_or(ContractError::NotFound)?;
+
+        storage::extend_unit_ttl(&env, &blood_unit);
+        storage::extend_instance_ttl(&env);
+
+        Ok(())
+    }
+
+    // ========== Helper Functions ==========
+
+    /// Apply a change to one stock line and fire threshold alerts
+    fn update_stock(
+        env: &Env,
+        bank: &Address,
+        blood_type: BloodType,
+        component: BloodComponent,
+        change: impl FnOnce(&mut StockLevel),
+    ) {
+        let mut levels = storage::get_bank_stock(env, bank);
+        Self::apply_stock_change(env, bank, &mut levels, blood_type, component, change);
+        storage::set_bank_stock(env, bank, &levels);
+    }
+
+    /// Apply a change to one line of an in-memory stock summary
+    ///
+    /// Fires threshold alerts; the caller persists `levels`.
+    fn apply_stock_change(
+        env: &Env,
+        bank: &Address,
+        levels: &mut Vec<StockLevel>,
+        blood_type: BloodType,
+        component: BloodComponent,
+        change: impl FnOnce(&mut StockLevel),
+    ) {
+        let index = levels
+            .iter()
+            .position(|level| level.blood_type == blood_type && level.component == component);
+
+        let mut level = match index {
+            Some(i) => levels.get_unchecked(i as u32),
+            None => StockLevel::new(blood_type, component),
+        };
+
+        let was_low = level.is_low();
+        change(&mut level);
+        let is_low = level.is_low();
+
+        match index {
+            Some(i) => levels.set(i as u32, level.clone()),
+            None => levels.push_back(level.clone()),
+        }
+
+        if !was_low && is_low {
+            events::emit_stock_alert(env, "low_stock", bank, &level);
+        } else if was_low && !is_low {
+            events::emit_stock_alert(env, "restocked", bank, &level);
+        }
+    }
+
+    /// Build a freshly registered (quarantined) blood unit
+    fn new_blood_unit(
+        env: &Env,
+        blood_unit_id: u64,
+        bank_id: &Address,
+        new_unit: NewUnit,
+    ) -> BloodUnit {
+        BloodUnit {
+            id: blood_unit_id,
+            blood_type: new_unit.blood_type,
+            component: new_unit.component,
+            din: new_unit.din,
+            product_code: new_unit.product_code,
+            quantity_ml: new_unit.quantity_ml,
+            bank_id: bank_id.clone(),
+            donor_id: new_unit.donor_id,
+            donation_timestamp: env.ledger().timestamp(),
+            expiration_timestamp: new_unit.expiration_timestamp,
+            status: BloodStatus::Quarantined,
+            metadata: Map::new(env),
+        }
+    }
+
+    /// Link a donor's unit to their open collection at its bank, opening a
+    /// whole blood collection if there is none
+    ///
+    /// The unit's donation time becomes the collection time. Eligibility is
+    /// checked by `validation::validate_donor_registration` beforehand.
+    fn attach_to_collection(env: &Env, blood_unit: &mut BloodUnit) {
+        let Some(donor) = blood_unit.donor_id.clone() else {
+            return;
+        };
+
+        let collection = storage::get_open_collection(env, &blood_unit.bank_id, &donor)
+            .unwrap_or_else(|| {
+                Self::open_collection(env, &blood_unit.bank_id, &donor, DonationType::WholeBlood)
+            });
+
+        blood_unit.donation_timestamp = collection.collected_at;
+        storage::set_unit_collection(env, blood_unit.id, collection.collection_id);
+    }
+
+    /// Store a new collection for a donor and emit `collection_recorded`
+    fn open_collection(
+        env: &Env,
+        bank_id: &Address,
+        donor_id: &Address,
+        donation_type: DonationType,
+    ) -> Collection {
+        let collection = Collection {
+            collection_id: storage::increment_collection_id(env),
+            donor_id: donor_id.clone(),
+            bank_id: bank_id.clone(),
+            donation_type,
+            collected_at: env.ledger().timestamp(),
+        };
+        storage::set_collection(env, &collection);
+
+        events::emit_collection_recorded(env, &collection);
+
+        collection
+    }
+
+    /// Persist a metadata change, keep the index in sync and record history
+    fn apply_metadata_change(
+        env: &Env,
+        blood_unit: &BloodUnit,
+        key: &Symbol,
+        old_value: Option<String>,
+        new_value: Option<String>,
+        changed_by: &Address,
+    ) {
+        storage::set_blood_unit(env, blood_unit);
+
+        if let Some(old) = &old_value {
+            storage::remove_from_metadata_index(env, blood_unit.id, key, old);
+        }
+        if let Some(new) = &new_value {
+            storage::add_to_metadata_index(env, blood_unit.id, key, new);
+        }
+
+        storage::record_metadata_change(
+            env,
+            &MetadataChange {
+                blood_unit_id: blood_unit.id,
+                key: key.clone(),
+                old_value,
+                new_value: new_value.clone(),
+                changed_by: changed_by.clone(),
+                changed_at: env.ledger().timestamp(),
+            },
+        );
+
+        events::emit_metadata_updated(env, blood_unit.id, key, new_value, changed_by);
+    }
+
+    /// Build a lookback entry from the unit's delivery record in history
+    fn lookback_entry(env: &Env, blood_unit: &BloodUnit) -> LookbackEntry {
+        let delivery = storage::get_delivery(env, blood_unit.id);
+
+        LookbackEntry {
+            blood_unit_id: blood_unit.id,
+            bank_id: blood_unit.bank_id.clone(),
+            status: blood_unit.status,
+            destination: delivery.as_ref().and_then(|d| d.destination.clone()),
+            delivered_at: delivery.map_or(0, |d| d.delivered_at),
+        }
+    }
+
+    /// Reject release from quarantine until screening has cleared
+    fn check_quarantine_release(
+        env: &Env,
+        blood_unit: &BloodUnit,
+        new_status: BloodStatus,
+    ) -> Result<(), ContractError> {
+        if blood_unit.status == BloodStatus::Quarantined && new_status == BloodStatus::Available {
+            let results = storage::get_screening_results(env, blood_unit.id);
+            validation::validate_screening_cleared(&results)?;
+        }
+        Ok(())
+    }
+
+    /// Validate and apply an admin status change to one unit
+    ///
+    /// Shared by `update_status` and the batch entrypoints. Nothing is
+    /// written if any check fails.
+    fn transition_unit(
+        env: &Env,
+        blood_unit: &mut BloodUnit,
+        new_status: BloodStatus,
+        authorized_by: &Address,
+        reason: Option<String>,
+    ) -> Result<(), ContractError> {
+        // Units past their shelf life can only be marked expired
+        if new_status != BloodStatus::Expired && blood_unit.is_expired(env.ledger().timestamp()) {
+            return Err(ContractError::BloodUnitExpired);
+        }
+
+        if blood_unit.status.is_terminal() {
+            return Err(ContractError::InvalidStatusTransition);
+        }
+
+        validation::validate_not_discard(new_status)?;
+        validation::validate_status_transition(blood_unit.status, new_status)?;
+        Self::check_quarantine_release(env, blood_unit, new_status)?;
+        Self::check_pending_transfer(env, blood_unit, new_status)?;
+        Self::check_reservation(env, blood_unit, new_status)?;
+
+        Self::apply_status_change(
+            env,
+            blood_unit,
+            new_status,
+            authorized_by,
+            reason,
+            StatusDetail::None,
+        );
+
+        if new_status == BloodStatus::Reserved {
+            Self::place_reservation(
+                env,
+                blood_unit.id,
+                authorized_by,
+                None,
+                storage::DEFAULT_RESERVATION_SECONDS,
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Give an address a role, refusing to silently replace a different one
+    fn assign_role(env: &Env, account: &Address, role: Role) -> Result<(), ContractError> {
+        match storage::get_role(env, account) {
+            Some(existing) if existing != role => Err(ContractError::RoleAlreadyAssigned),
+            _ => {
+                storage::set_role(env, account, role);
+                Ok(())
+            }
+        }
+    }
+
+    /// Whether an address is the unit's custodian or holds its reservation
+    fn holds_unit(env: &Env, blood_unit: &BloodUnit, account: &Address) -> bool {
+        if &storage::get_custodian(env, blood_unit) == account {
+            return true;
+        }
+        storage::get_reservation(env, blood_unit.id)
+            .is_some_and(|reservation| &reservation.holder == account)
+    }
+
+    /// Units moving between banks can't be delivered to a hospital, and
+    /// units in transit only return to stock through `receive_bank_transfer`
+    fn check_pending_transfer(
+        env: &Env,
+        blood_unit: &BloodUnit,
+        new_status: BloodStatus,
+    ) -> Result<(), ContractError> {
+        let pending = storage::get_pending_transfer(env, blood_unit.id).is_some();
+        if new_status == BloodStatus::Delivered && pending {
+            return Err(ContractError::TransferPending);
+        }
+        if blood_unit.status == BloodStatus::InTransit && new_status == BloodStatus::Available {
+            if pending {
+                return Err(ContractError::TransferPending);
+            }
+            return Err(ContractError::InvalidStatusTransition);
+        }
+        Ok(())
+    }
+
+    /// Lapsed reservations can't be dispatched
+    fn check_reservation(
+        env: &Env,
+        blood_unit: &BloodUnit,
+        new_status: BloodStatus,
+    ) -> Result<(), ContractError> {
+        if new_status == BloodStatus::InTransit && Self::has_lapsed_reservation(env, blood_unit) {
+            return Err(ContractError::ReservationExpired);
+        }
+        Ok(())
+    }
+
+    /// Whether the unit is reserved under a reservation that has lapsed
+    fn has_lapsed_reservation(env: &Env, blood_unit: &BloodUnit) -> bool {
+        blood_unit.status == BloodStatus::Reserved
+            && storage::get_reservation(env, blood_unit.id)
+                .is_some_and(|r| r.is_lapsed(env.ledger().timestamp()))
+    }
+
+    /// Store a reservation on a unit that has just moved to `Reserved`
+    fn place_reservation(
+        env: &Env,
+        blood_unit_id: u64,
+        holder: &Address,
+        request_id: Option<u64>,
+        duration_seconds: u64,
+    ) -> Reservation {
+        let reserved_at = env.ledger().timestamp();
+        let reservation = Reservation {
+            holder: holder.clone(),
+            request_id,
+            reserved_at,
+            expires_at: reserved_at + duration_seconds,
+        };
+
+        storage::set_reservation(env, blood_unit_id, &reservation);
+        events::emit_reservation(env, "unit_reserved", blood_unit_id, &reservation);
+
+        reservation
+    }
+
+    /// Whether `caller` may reserve a unit or release its reservation
+    fn can_manage_reservation(env: &Env, blood_unit: &BloodUnit, caller: &Address) -> bool {
+        *caller == storage::get_admin(env)
+            || *caller == blood_unit.bank_id
+            || storage::get_request_contract(env).as_ref() == Some(caller)
+    }
+
+    /// Return a reserved unit to `Available`
+    ///
+    /// Lapsed reservations are released by the contract itself; anyone else
+    /// releasing a reservation is cancelling it early.
+    fn release_reservation(env: &Env, blood_unit: &mut BloodUnit, authorized_by: &Address) {
+        let Some(reservation) = storage::get_reservation(env, blood_unit.id) else {
+            return;
+        };
+
+        let reason = if *authorized_by == env.current_contract_address() {
+            "Reservation expired"
+        } else {
+            "Reservation cancelled"
+        };
+        Self::apply_status_change(
+            env,
+            blood_unit,
+            BloodStatus::Available,
+            authorized_by,
+            Some(String::from_str(env, reason)),
+            StatusDetail::None,
+        );
+
+        events::emit_reservation(env, "reservation_released", blood_unit.id, &reservation);
+    }
+
+    /// Move a unit to `Discarded` and count it against its bank's wastage
+    ///
+    /// Callers are responsible for validating the transition first.
+    fn discard(
+        env: &Env,
+        blood_unit: &mut BloodUnit,
+        authorized_by: &Address,
+        reason: DiscardReason,
+        note: Option<String>,
+    ) {
+        Self::apply_status_change(
+            env,
+            blood_unit,
+            BloodStatus::Discarded,
+            authorized_by,
+            note,
+            StatusDetail::Discarded(reason),
+        );
+
+        storage::record_wastage(env, blood_unit, reason);
+        events::emit_unit_discarded(env, blood_unit, reason, authorized_by);
+    }
+
+    /// Persist a status change and record it in history and events
+    ///
+    /// Callers are responsible for validating the transition first.
+    fn apply_status_change(
+        env: &Env,
+        blood_unit: &mut BloodUnit,
+        new_status: BloodStatus,
+        authorized_by: &Address,
+        reason: Option<String>,
+        detail: StatusDetail,
+    ) {
+        let old_status = blood_unit.status;
+        blood_unit.status = new_status;
+        storage::set_blood_unit(env, blood_unit);
+
+        let role = storage::get_role(env, authorized_by).unwrap_or(Role::System);
+
+        let quantity_ml = blood_unit.quantity_ml;
+        Self::update_stock(
+            env,
+            &blood_unit.bank_id,
+            blood_unit.blood_type,
+            blood_unit.component,
+            |level| {
+                level.remove(old_status, quantity_ml);
+                level.add(new_status, quantity_ml);
+            },
+        );
+
+        // Leaving transit (quarantine, discard, expiry) cancels any bank transfer
+        if old_status == BloodStatus::InTransit {
+            storage::remove_pending_transfer(env, blood_unit.id);
+        }
+
+        // Leaving Reserved ends the reservation, whether dispatched or released
+        if old_status == BloodStatus::Reserved {
+            storage::remove_reservation(env, blood_unit.id);
+        }
+
+        let history_id = storage::record_status_change(
+            env,
+            blood_unit.id,
+            old_status,
+            new_status,
+            authorized_by,
+            role,
+            reason.clone(),
+            detail.clone(),
+        );
+
+        // Keep the delivery outside the history so archiving can't lose it
+        if new_status == BloodStatus::Delivered {
+            let delivery = DeliveryRecord {
+                destination: reason.clone(),
+                delivered_at: env.ledger().timestamp(),
+            };
+            storage::set_delivery(env, blood_unit.id, &delivery);
+        }
+
+        // A terminal unit's records move to the archive TTL together
+        if new_status.is_terminal() {
+            storage::extend_unit_ttl(env, blood_unit);
+        }
+
+        events::emit_status_change(
+            env,
+            history_id,
+            blood_unit.id,
+            old_status,
+            new_status,
+            authorized_by,
+            role,
+            reason,
+            detail,
+        );
+    }
 }
 
 #[cfg(test)]
@@ -1,16 +1,57 @@
-use crate::types::{BloodStatus, BloodUnit, DataKey, StatusChangeHistory};
-use soroban_sdk::{Address, Env, String, Vec};
+use crate::types::{
+    BankTransfer, BloodStatus, BloodUnit, Collection, CustodyHandoff, DataKey, DeliveryRecord,
+    DiscardReason, DonorDeferral, MetadataChange, Reservation, Role, ScreeningRecord,
+    StatusChangeHistory, StatusDetail, StatusHistoryPage, StatusHistorySummary, StockLevel,
+    TtiMarker, WastageStats,
+};
+pub use lifebank_common::ttl::{
+    ACTIVE_TTL_EXTEND_TO, ACTIVE_TTL_THRESHOLD, ARCHIVE_TTL_EXTEND_TO, ARCHIVE_TTL_THRESHOLD,
+    INSTANCE_TTL_EXTEND_TO, INSTANCE_TTL_THRESHOLD,
+};
+use soroban_sdk::{xdr::ToXdr, Address, Bytes, BytesN, Env, Map, String, Symbol, Vec};
 
 /// Maximum expiration time (42 days for whole blood)
 pub const MAX_EXPIRATION_DAYS: u64 = 42;
 pub const SECONDS_PER_DAY: u64 = 86400;
 
+/// Maximum number of units in one batch registration
+pub const MAX_REGISTRATION_BATCH_SIZE: u32 = 100;
+
+/// Reservation length when none is given (48 hours)
+pub const DEFAULT_RESERVATION_SECONDS: u64 = 2 * SECONDS_PER_DAY;
+/// Longest reservation a bank can place (7 days)
+pub const MAX_RESERVATION_SECONDS: u64 = 7 * SECONDS_PER_DAY;
+
+/// How long after a collection its components can still be registered
+/// against it (24 hours)
+pub const COLLECTION_WINDOW_SECONDS: u64 = SECONDS_PER_DAY;
+
+/// Maximum number of status history entries returned per page
+pub const MAX_HISTORY_PAGE_SIZE: u32 = 50;
+
+/// Maximum number of metadata entries on a blood unit
+pub const MAX_METADATA_ENTRIES: u32 = 10;
+/// Maximum length of a metadata value in bytes
+pub const MAX_METADATA_VALUE_LEN: u32 = 64;
+
+/// Markers that must all test negative before a unit leaves quarantine
+pub const REQUIRED_TTI_MARKERS: [TtiMarker; 5] = [
+    TtiMarker::Hiv,
+    TtiMarker::Hbv,
+    TtiMarker::Hcv,
+    TtiMarker::Syphilis,
+    TtiMarker::Malaria,
+];
+
 /// Get the admin address
 pub fn get_admin(env: &Env) -> Address {
-    env.storage()
+    let admin = env
+        .storage()
         .instance()
         .get(&DataKey::Admin)
-        .expect("Admin not initialized")
+        .expect("Admin not initialized");
+    extend_instance_ttl(env);
+    admin
 }
 
 /// Set the admin address
@@ -18,10 +59,55 @@ pub fn set_admin(env: &Env, admin: &Address) {
     env.storage().instance().set(&DataKey::Admin, admin);
 }
 
+/// Get the request contract allowed to reserve units, if configured
+pub fn get_request_contract(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&DataKey::RequestContract)
+}
+
+/// Set the request contract allowed to reserve units
+pub fn set_request_contract(env: &Env, contract: &Address) {
+    env.storage()
+        .instance()
+        .set(&DataKey::RequestContract, contract);
+}
+
+/// Get the role an address holds (admin always acts as `Admin`)
+pub fn get_role(env: &Env, account: &Address) -> Option<Role> {
+    if account == &get_admin(env) {
+        return Some(Role::Admin);
+    }
+
+    let key = DataKey::Role(account.clone());
+    extend_active_ttl(env, &key);
+    env.storage().persistent().get(&key)
+}
+
+/// Assign a role to an address, replacing any previous role
+///
+/// Entrypoints go through `assign_role`, which refuses to replace one.
+pub fn set_role(env: &Env, account: &Address, role: Role) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::Role(account.clone()), &role);
+    extend_active_ttl(env, &DataKey::Role(account.clone()));
+}
+
+/// Remove an address's role if it currently holds `role`
+pub fn remove_role(env: &Env, account: &Address, role: Role) {
+    let key = DataKey::Role(account.clone());
+    if env.storage().persistent().get::<DataKey, Role>(&key) == Some(role) {
+        env.storage().persistent().remove(&key);
+    }
+}
+
 /// Check if an address is authorized as a blood bank
 pub fn is_authorized_bank(env: &Env, bank: &Address) -> bool {
-    let admin = get_admin(env);
-    bank == &admin
+    matches!(get_role(env, bank), Some(Role::Admin | Role::BankOperator))
+}
+
+/// Check if an address is authorized to record screening results
+pub fn is_authorized_lab(env: &Env, lab: &Address) -> bool {
+    matches!(get_role(env, lab), Some(Role::Admin | Role::Lab))
 }
 
 /// Get the current blood unit counter
@@ -47,18 +133,57 @@ pub fn set_blood_unit(env: &Env, blood_unit: &BloodUnit) {
     env.storage()
         .persistent()
         .set(&DataKey::BloodUnit(blood_unit.id), blood_unit);
+    extend_unit_entry_ttl(env, &DataKey::BloodUnit(blood_unit.id), blood_unit.status);
 }
 
 /// Get a blood unit by ID
 pub fn get_blood_unit(env: &Env, id: u64) -> Option<BloodUnit> {
-    env.storage().persistent().get(&DataKey::BloodUnit(id))
+    let blood_unit: Option<BloodUnit> = env.storage().persistent().get(&DataKey::BloodUnit(id));
+    if let Some(unit) = &blood_unit {
+        extend_unit_entry_ttl(env, &DataKey::BloodUnit(id), unit.status);
+    }
+    blood_unit
 }
 
 /// Check if a blood unit exists
+#[allow(dead_code)]
 pub fn blood_unit_exists(env: &Env, id: u64) -> bool {
     env.storage().persistent().has(&DataKey::BloodUnit(id))
 }
 
+/// Get the blood unit ID registered under a DIN
+pub fn get_unit_id_by_din(env: &Env, din: &String) -> Option<u64> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::DinIndex(din.clone()))
+}
+
+/// Index a blood unit by its DIN
+pub fn set_din_index(env: &Env, blood_unit: &BloodUnit) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::DinIndex(blood_unit.din.clone()), &blood_unit.id);
+    extend_active_ttl(env, &DataKey::DinIndex(blood_unit.din.clone()));
+}
+
+/// Allocate `count` contiguous blood unit IDs, returning the first
+pub fn allocate_blood_unit_ids(env: &Env, count: u64) -> u64 {
+    let first_id = get_blood_unit_counter(env) + 1;
+    env.storage()
+        .instance()
+        .set(&DataKey::BloodUnitCounter, &(first_id + count - 1));
+    first_id
+}
+
+/// Append several blood unit IDs to an index in one write
+pub fn append_to_index(env: &Env, key: &DataKey, ids: &Vec<u64>) {
+    let mut units: Vec<u64> = env.storage().persistent().get(key).unwrap_or(Vec::new(env));
+
+    units.append(ids);
+    env.storage().persistent().set(key, &units);
+    extend_active_ttl(env, key);
+}
+
 /// Add blood unit to blood type index
 pub fn add_to_blood_type_index(env: &Env, blood_unit: &BloodUnit) {
     let key = DataKey::BloodTypeIndex(blood_unit.blood_type);
@@ -70,6 +195,7 @@ pub fn add_to_blood_type_index(env: &Env, blood_unit: &BloodUnit) {
 
     units.push_back(blood_unit.id);
     env.storage().persistent().set(&key, &units);
+    extend_active_ttl(env, &key);
 }
 
 /// Add blood unit to bank index
@@ -83,6 +209,161 @@ pub fn add_to_bank_index(env: &Env, blood_unit: &BloodUnit) {
 
     units.push_back(blood_unit.id);
     env.storage().persistent().set(&key, &units);
+    extend_active_ttl(env, &key);
+}
+
+/// Remove blood unit from bank index
+pub fn remove_from_bank_index(env: &Env, blood_unit_id: u64, bank: &Address) {
+    let key = DataKey::BankIndex(bank.clone());
+    if let Some(units) = env.storage().persistent().get::<DataKey, Vec<u64>>(&key) {
+        let mut new_units = Vec::new(env);
+        for id in units.iter() {
+            if id != blood_unit_id {
+                new_units.push_back(id);
+            }
+        }
+        env.storage().persistent().set(&key, &new_units);
+        extend_active_ttl(env, &key);
+    }
+}
+
+/// Get blood unit IDs held by a bank
+pub fn get_units_by_bank(env: &Env, bank: &Address) -> Vec<u64> {
+    extend_active_ttl(env, &DataKey::BankIndex(bank.clone()));
+    env.storage()
+        .persistent()
+        .get(&DataKey::BankIndex(bank.clone()))
+        .unwrap_or(Vec::new(env))
+}
+
+/// Get the stock summary for a bank
+pub fn get_bank_stock(env: &Env, bank: &Address) -> Vec<StockLevel> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::BankStock(bank.clone()))
+        .unwrap_or(Vec::new(env))
+}
+
+/// Store the stock summary for a bank
+pub fn set_bank_stock(env: &Env, bank: &Address, levels: &Vec<StockLevel>) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::BankStock(bank.clone()), levels);
+    extend_active_ttl(env, &DataKey::BankStock(bank.clone()));
+}
+
+/// Get the reservation on a blood unit
+pub fn get_reservation(env: &Env, blood_unit_id: u64) -> Option<Reservation> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Reservation(blood_unit_id))
+}
+
+/// Store the reservation on a blood unit
+pub fn set_reservation(env: &Env, blood_unit_id: u64, reservation: &Reservation) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::Reservation(blood_unit_id), reservation);
+    extend_active_ttl(env, &DataKey::Reservation(blood_unit_id));
+}
+
+/// Clear the reservation on a blood unit
+pub fn remove_reservation(env: &Env, blood_unit_id: u64) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::Reservation(blood_unit_id));
+}
+
+/// Get where and when a blood unit was delivered
+pub fn get_delivery(env: &Env, blood_unit_id: u64) -> Option<DeliveryRecord> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Delivery(blood_unit_id))
+}
+
+/// Record where and when a blood unit was delivered
+pub fn set_delivery(env: &Env, blood_unit_id: u64, delivery: &DeliveryRecord) {
+    let key = DataKey::Delivery(blood_unit_id);
+    env.storage().persistent().set(&key, delivery);
+    extend_active_ttl(env, &key);
+}
+
+/// Get the current custodian of a blood unit
+pub fn get_custodian(env: &Env, blood_unit: &BloodUnit) -> Address {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Custodian(blood_unit.id))
+        .unwrap_or(blood_unit.bank_id.clone())
+}
+
+/// Set the current custodian of a blood unit
+pub fn set_custodian(env: &Env, blood_unit_id: u64, custodian: &Address) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::Custodian(blood_unit_id), custodian);
+    extend_active_ttl(env, &DataKey::Custodian(blood_unit_id));
+}
+
+/// Get the handoff awaiting acceptance for a blood unit
+pub fn get_pending_handoff(env: &Env, blood_unit_id: u64) -> Option<CustodyHandoff> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::PendingHandoff(blood_unit_id))
+}
+
+/// Store a handoff awaiting acceptance
+pub fn set_pending_handoff(env: &Env, blood_unit_id: u64, handoff: &CustodyHandoff) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::PendingHandoff(blood_unit_id), handoff);
+    extend_active_ttl(env, &DataKey::PendingHandoff(blood_unit_id));
+}
+
+/// Clear the handoff awaiting acceptance
+pub fn remove_pending_handoff(env: &Env, blood_unit_id: u64) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::PendingHandoff(blood_unit_id));
+}
+
+/// Get completed custody handoffs for a blood unit, oldest first
+pub fn get_custody_chain(env: &Env, blood_unit_id: u64) -> Vec<CustodyHandoff> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::CustodyChain(blood_unit_id))
+        .unwrap_or(Vec::new(env))
+}
+
+/// Append a completed handoff to a unit's custody chain
+pub fn append_custody_handoff(env: &Env, blood_unit_id: u64, handoff: &CustodyHandoff) {
+    let mut chain = get_custody_chain(env, blood_unit_id);
+    chain.push_back(handoff.clone());
+    env.storage()
+        .persistent()
+        .set(&DataKey::CustodyChain(blood_unit_id), &chain);
+    extend_active_ttl(env, &DataKey::CustodyChain(blood_unit_id));
+}
+
+/// Get the pending inter-bank transfer for a blood unit
+pub fn get_pending_transfer(env: &Env, blood_unit_id: u64) -> Option<BankTransfer> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::PendingTransfer(blood_unit_id))
+}
+
+/// Store a pending inter-bank transfer
+pub fn set_pending_transfer(env: &Env, blood_unit_id: u64, transfer: &BankTransfer) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::PendingTransfer(blood_unit_id), transfer);
+    extend_active_ttl(env, &DataKey::PendingTransfer(blood_unit_id));
+}
+
+/// Clear a pending inter-bank transfer
+pub fn remove_pending_transfer(env: &Env, blood_unit_id: u64) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::PendingTransfer(blood_unit_id));
 }
 
 /// Add blood unit to status index
@@ -96,6 +377,7 @@ pub fn add_to_status_index(env: &Env, blood_unit: &BloodUnit) {
 
     units.push_back(blood_unit.id);
     env.storage().persistent().set(&key, &units);
+    extend_active_ttl(env, &key);
 }
 
 /// Add blood unit to donor index (if donor_id exists)
@@ -110,18 +392,233 @@ pub fn add_to_donor_index(env: &Env, blood_unit: &BloodUnit) {
 
         units.push_back(blood_unit.id);
         env.storage().persistent().set(&key, &units);
+        extend_active_ttl(env, &key);
     }
 }
 
-/// Record a status change in history
+/// Get screening results recorded for a blood unit, keyed by marker
+pub fn get_screening_results(env: &Env, blood_unit_id: u64) -> Map<TtiMarker, ScreeningRecord> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::ScreeningResults(blood_unit_id))
+        .unwrap_or(Map::new(env))
+}
+
+/// Store a screening result for a blood unit
+pub fn set_screening_result(env: &Env, blood_unit_id: u64, record: &ScreeningRecord) {
+    let key = DataKey::ScreeningResults(blood_unit_id);
+    let mut results = get_screening_results(env, blood_unit_id);
+    results.set(record.marker, record.clone());
+    env.storage().persistent().set(&key, &results);
+    extend_active_ttl(env, &key);
+}
+
+/// Remove every screening result recorded for a blood unit
+pub fn clear_screening_results(env: &Env, blood_unit_id: u64) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::ScreeningResults(blood_unit_id));
+}
+
+/// Get all blood unit IDs donated by a donor
+pub fn get_units_by_donor(env: &Env, donor: &Address) -> Vec<u64> {
+    extend_active_ttl(env, &DataKey::DonorIndex(donor.clone()));
+    env.storage()
+        .persistent()
+        .get(&DataKey::DonorIndex(donor.clone()))
+        .unwrap_or(Vec::new(env))
+}
+
+/// Get the deferral on record for a donor
+pub fn get_donor_deferral(env: &Env, donor: &Address) -> Option<DonorDeferral> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::DonorDeferral(donor.clone()))
+}
+
+/// Store a donor's deferral, replacing any previous one
+pub fn set_donor_deferral(env: &Env, deferral: &DonorDeferral) {
+    let key = DataKey::DonorDeferral(deferral.donor_id.clone());
+    env.storage().persistent().set(&key, deferral);
+    extend_active_ttl(env, &key);
+}
+
+/// Remove a donor's deferral
+pub fn remove_donor_deferral(env: &Env, donor: &Address) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::DonorDeferral(donor.clone()));
+}
+
+/// Get a collection by ID
+pub fn get_collection(env: &Env, collection_id: u64) -> Option<Collection> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Collection(collection_id))
+}
+
+/// Store a collection and make it the donor's most recent one
+pub fn set_collection(env: &Env, collection: &Collection) {
+    let key = DataKey::Collection(collection.collection_id);
+    env.storage().persistent().set(&key, collection);
+    extend_active_ttl(env, &key);
+
+    let donor_key = DataKey::DonorLastCollection(collection.donor_id.clone());
+    env.storage()
+        .persistent()
+        .set(&donor_key, &collection.collection_id);
+    extend_active_ttl(env, &donor_key);
+}
+
+/// Increment and return the next collection ID
+pub fn increment_collection_id(env: &Env) -> u64 {
+    let next_id = env
+        .storage()
+        .instance()
+        .get::<DataKey, u64>(&DataKey::CollectionCounter)
+        .unwrap_or(0)
+        + 1;
+    env.storage()
+        .instance()
+        .set(&DataKey::CollectionCounter, &next_id);
+    next_id
+}
+
+/// Get a donor's most recent collection
+pub fn get_donor_last_collection(env: &Env, donor: &Address) -> Option<Collection> {
+    let collection_id: u64 = env
+        .storage()
+        .persistent()
+        .get(&DataKey::DonorLastCollection(donor.clone()))?;
+    get_collection(env, collection_id)
+}
+
+/// Get the donor's collection at `bank_id` that units can still be
+/// registered against, if any
+pub fn get_open_collection(env: &Env, bank_id: &Address, donor: &Address) -> Option<Collection> {
+    let collection = get_donor_last_collection(env, donor)?;
+    let open = collection.bank_id == *bank_id
+        && env.ledger().timestamp() < collection.collected_at + COLLECTION_WINDOW_SECONDS;
+    open.then_some(collection)
+}
+
+/// Get the ID of the collection a blood unit came from
+pub fn get_unit_collection(env: &Env, blood_unit_id: u64) -> Option<u64> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::UnitCollection(blood_unit_id))
+}
+
+/// Record the collection a blood unit came from
+pub fn set_unit_collection(env: &Env, blood_unit_id: u64, collection_id: u64) {
+    let key = DataKey::UnitCollection(blood_unit_id);
+    env.storage().persistent().set(&key, &collection_id);
+    extend_active_ttl(env, &key);
+}
+
+/// Earliest time a donor's last collection allows another donation
+///
+/// Collections can't overlap their own interval, so the most recent one
+/// always sets the date.
+pub fn get_donor_next_donation_at(env: &Env, donor: &Address) -> u64 {
+    get_donor_last_collection(env, donor)
+        .map(|collection| {
+            collection.collected_at
+                + collection.donation_type.min_donation_interval_days() * SECONDS_PER_DAY
+        })
+        .unwrap_or(0)
+}
+
+/// Get the metadata keys banks are allowed to set
+pub fn get_metadata_key_allowlist(env: &Env) -> Vec<Symbol> {
+    env.storage()
+        .instance()
+        .get(&DataKey::MetadataKeyAllowlist)
+        .unwrap_or(Vec::new(env))
+}
+
+/// Replace the metadata key allowlist
+pub fn set_metadata_key_allowlist(env: &Env, keys: &Vec<Symbol>) {
+    env.storage()
+        .instance()
+        .set(&DataKey::MetadataKeyAllowlist, keys);
+}
+
+/// Check if a metadata key is on the allowlist
+pub fn is_metadata_key_allowed(env: &Env, key: &Symbol) -> bool {
+    get_metadata_key_allowlist(env).contains(key)
+}
+
+/// Add blood unit to metadata (key, value) index
+pub fn add_to_metadata_index(env: &Env, blood_unit_id: u64, key: &Symbol, value: &String) {
+    let index_key = DataKey::MetadataIndex(key.clone(), value.clone());
+    let mut units: Vec<u64> = env
+        .storage()
+        .persistent()
+        .get(&index_key)
+        .unwrap_or(Vec::new(env));
+
+    units.push_back(blood_unit_id);
+    env.storage().persistent().set(&index_key, &units);
+    extend_active_ttl(env, &index_key);
+}
+
+/// Remove blood unit from metadata (key, value) index
+pub fn remove_from_metadata_index(env: &Env, blood_unit_id: u64, key: &Symbol, value: &String) {
+    let index_key = DataKey::MetadataIndex(key.clone(), value.clone());
+    if let Some(units) = env
+        .storage()
+        .persistent()
+        .get::<DataKey, Vec<u64>>(&index_key)
+    {
+        let mut new_units = Vec::new(env);
+        for id in units.iter() {
+            if id != blood_unit_id {
+                new_units.push_back(id);
+            }
+        }
+        env.storage().persistent().set(&index_key, &new_units);
+        extend_active_ttl(env, &index_key);
+    }
+}
+
+/// Get blood unit IDs with a given metadata value
+pub fn get_units_by_metadata(env: &Env, key: &Symbol, value: &String) -> Vec<u64> {
+    extend_active_ttl(env, &DataKey::MetadataIndex(key.clone(), value.clone()));
+    env.storage()
+        .persistent()
+        .get(&DataKey::MetadataIndex(key.clone(), value.clone()))
+        .unwrap_or(Vec::new(env))
+}
+
+/// Record a metadata change in the unit's metadata history
+pub fn record_metadata_change(env: &Env, change: &MetadataChange) {
+    let key = DataKey::MetadataHistory(change.blood_unit_id);
+    let mut changes = get_metadata_history(env, change.blood_unit_id);
+    changes.push_back(change.clone());
+    env.storage().persistent().set(&key, &changes);
+    extend_active_ttl(env, &key);
+}
+
+/// Get metadata change history for a blood unit
+pub fn get_metadata_history(env: &Env, blood_unit_id: u64) -> Vec<MetadataChange> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::MetadataHistory(blood_unit_id))
+        .unwrap_or(Vec::new(env))
+}
+
+/// Record a status change in history, returning its history ID
 pub fn record_status_change(
     env: &Env,
     blood_unit_id: u64,
     from_status: BloodStatus,
     to_status: BloodStatus,
     authorized_by: &Address,
+    role: Role,
     reason: Option<String>,
-) {
+    detail: StatusDetail,
+) -> u64 {
     let changed_at = env.ledger().timestamp();
     let history_id = increment_status_history_counter(env);
 
@@ -131,34 +628,146 @@ pub fn record_status_change(
         from_status,
         to_status,
         authorized_by: authorized_by.clone(),
+        role,
         changed_at,
         reason,
+        detail,
     };
 
-    // Get existing history for this blood unit
-    let key = DataKey::StatusHistory(blood_unit_id);
-    let mut histories: Vec<StatusChangeHistory> = env
-        .storage()
-        .persistent()
-        .get(&key)
-        .unwrap_or(Vec::new(env));
+    // Store the entry by ID and append it to the unit's sequence
+    let key = DataKey::StatusChange(history_id);
+    env.storage().persistent().set(&key, &history);
+    extend_unit_entry_ttl(env, &key, to_status);
 
-    histories.push_back(history);
-    env.storage().persistent().set(&key, &histories);
+    let seq = get_blood_unit_status_change_count(env, blood_unit_id);
+    let seq_key = DataKey::UnitStatusChange(blood_unit_id, seq);
+    env.storage().persistent().set(&seq_key, &history_id);
+    extend_unit_entry_ttl(env, &seq_key, to_status);
 
     // Increment change count for this unit
     let count_key = DataKey::BloodUnitStatusChangeCount(blood_unit_id);
-    let count = get_blood_unit_status_change_count(env, blood_unit_id);
-    env.storage().persistent().set(&count_key, &(count + 1));
+    env.storage().persistent().set(&count_key, &(seq + 1));
+    extend_unit_entry_ttl(env, &count_key, to_status);
+
+    history_id
 }
 
-/// Get status change history for a blood unit
-pub fn get_status_history(env: &Env, blood_unit_id: u64) -> Vec<StatusChangeHistory> {
-    let key = DataKey::StatusHistory(blood_unit_id);
+/// Get a status change record by its history ID
+pub fn get_status_change(env: &Env, history_id: u64) -> Option<StatusChangeHistory> {
     env.storage()
         .persistent()
-        .get(&key)
-        .unwrap_or(Vec::new(env))
+        .get(&DataKey::StatusChange(history_id))
+}
+
+/// Get the history ID at position `seq` in a unit's history
+fn get_unit_status_change(env: &Env, blood_unit_id: u64, seq: u64) -> Option<StatusChangeHistory> {
+    let history_id: u64 = env
+        .storage()
+        .persistent()
+        .get(&DataKey::UnitStatusChange(blood_unit_id, seq))?;
+    get_status_change(env, history_id)
+}
+
+/// Get the digest of a unit's archived history, if any was archived
+pub fn get_status_history_summary(env: &Env, blood_unit_id: u64) -> Option<StatusHistorySummary> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::StatusHistorySummary(blood_unit_id))
+}
+
+/// Sequence number of the oldest history entry still in storage
+fn first_live_seq(env: &Env, blood_unit_id: u64) -> u64 {
+    get_status_history_summary(env, blood_unit_id)
+        .map(|summary| summary.archived_count)
+        .unwrap_or(0)
+}
+
+/// Get every status change still in storage for a blood unit, oldest first
+pub fn get_status_history(env: &Env, blood_unit_id: u64) -> Vec<StatusChangeHistory> {
+    let mut histories = Vec::new(env);
+    let count = get_blood_unit_status_change_count(env, blood_unit_id);
+    for seq in first_live_seq(env, blood_unit_id)..count {
+        if let Some(history) = get_unit_status_change(env, blood_unit_id, seq) {
+            histories.push_back(history);
+        }
+    }
+    histories
+}
+
+/// Get up to `limit` status changes starting at sequence `cursor`
+///
+/// Archived entries are skipped; a cursor below the archive boundary starts
+/// at the oldest live entry.
+pub fn get_status_history_page(
+    env: &Env,
+    blood_unit_id: u64,
+    cursor: u64,
+    limit: u32,
+) -> StatusHistoryPage {
+    let count = get_blood_unit_status_change_count(env, blood_unit_id);
+    let start = cursor.max(first_live_seq(env, blood_unit_id));
+    let end = count.min(start + limit.min(MAX_HISTORY_PAGE_SIZE) as u64);
+
+    let mut entries = Vec::new(env);
+    for seq in start..end {
+        if let Some(history) = get_unit_status_change(env, blood_unit_id, seq) {
+            entries.push_back(history);
+        }
+    }
+
+    StatusHistoryPage {
+        entries,
+        next_cursor: if end < count { Some(end) } else { None },
+    }
+}
+
+/// Roll a unit's oldest live history entries into its hash-chained summary
+///
+/// Archives every entry except the newest `keep_latest`, removing them from
+/// storage. Returns None if there is nothing to archive.
+pub fn archive_status_history(
+    env: &Env,
+    blood_unit_id: u64,
+    keep_latest: u64,
+) -> Option<StatusHistorySummary> {
+    let count = get_blood_unit_status_change_count(env, blood_unit_id);
+    let start = first_live_seq(env, blood_unit_id);
+    let end = count.saturating_sub(keep_latest);
+    if end <= start {
+        return None;
+    }
+
+    let mut summary =
+        get_status_history_summary(env, blood_unit_id).unwrap_or(StatusHistorySummary {
+            blood_unit_id,
+            archived_count: 0,
+            last_history_id: 0,
+            last_changed_at: 0,
+            chain_hash: BytesN::from_array(env, &[0; 32]),
+        });
+
+    for seq in start..end {
+        let seq_key = DataKey::UnitStatusChange(blood_unit_id, seq);
+        if let Some(history) = get_unit_status_change(env, blood_unit_id, seq) {
+            let mut data = Bytes::from_array(env, &summary.chain_hash.to_array());
+            data.append(&history.clone().to_xdr(env));
+            summary.chain_hash = env.crypto().sha256(&data).to_bytes();
+            summary.last_history_id = history.id;
+            summary.last_changed_at = history.changed_at;
+
+            env.storage()
+                .persistent()
+                .remove(&DataKey::StatusChange(history.id));
+        }
+        env.storage().persistent().remove(&seq_key);
+    }
+    summary.archived_count = end;
+
+    let key = DataKey::StatusHistorySummary(blood_unit_id);
+    env.storage().persistent().set(&key, &summary);
+    extend_active_ttl(env, &key);
+
+    Some(summary)
 }
 
 /// Get the number of status changes for a blood unit
@@ -167,6 +776,26 @@ pub fn get_blood_unit_status_change_count(env: &Env, blood_unit_id: u64) -> u64
     env.storage().persistent().get(&key).unwrap_or(0)
 }
 
+/// Get wastage counters for a bank and discard reason
+pub fn get_wastage(env: &Env, bank: &Address, reason: DiscardReason) -> WastageStats {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Wastage(bank.clone(), reason))
+        .unwrap_or_default()
+}
+
+/// Add a discarded unit to its bank's wastage counters
+pub fn record_wastage(env: &Env, blood_unit: &BloodUnit, reason: DiscardReason) {
+    let mut stats = get_wastage(env, &blood_unit.bank_id, reason);
+    stats.units += 1;
+    stats.volume_ml += blood_unit.quantity_ml as u64;
+    env.storage().persistent().set(
+        &DataKey::Wastage(blood_unit.bank_id.clone(), reason),
+        &stats,
+    );
+    extend_active_ttl(env, &DataKey::Wastage(blood_unit.bank_id.clone(), reason));
+}
+
 /// Get the next status history ID
 fn increment_status_history_counter(env: &Env) -> u64 {
     let key = DataKey::StatusHistoryCounter;
@@ -175,3 +804,67 @@ fn increment_status_history_counter(env: &Env) -> u64 {
     env.storage().instance().set(&key, &next_id);
     next_id
 }
+
+/// Extend the contract instance and its instance storage
+pub fn extend_instance_ttl(env: &Env) {
+    env.storage()
+        .instance()
+        .extend_ttl(INSTANCE_TTL_THRESHOLD, INSTANCE_TTL_EXTEND_TO);
+}
+
+/// Extend a blood unit and every per-unit record it owns
+///
+/// Terminal units are moved to the longer archive TTL so their audit
+/// trail outlives the unit's working life.
+pub fn extend_unit_ttl(env: &Env, blood_unit: &BloodUnit) {
+    let id = blood_unit.id;
+    for key in [
+        DataKey::BloodUnit(id),
+        DataKey::StatusHistorySummary(id),
+        DataKey::BloodUnitStatusChangeCount(id),
+        DataKey::ScreeningResults(id),
+        DataKey::MetadataHistory(id),
+        DataKey::CustodyChain(id),
+        DataKey::Custodian(id),
+        DataKey::Delivery(id),
+        DataKey::UnitCollection(id),
+        DataKey::PendingHandoff(id),
+        DataKey::PendingTransfer(id),
+        DataKey::Reservation(id),
+        DataKey::DinIndex(blood_unit.din.clone()),
+    ] {
+        extend_unit_entry_ttl(env, &key, blood_unit.status);
+    }
+
+    let count = get_blood_unit_status_change_count(env, id);
+    for seq in first_live_seq(env, id)..count {
+        let seq_key = DataKey::UnitStatusChange(id, seq);
+        if let Some(history_id) = env.storage().persistent().get::<DataKey, u64>(&seq_key) {
+            extend_unit_entry_ttl(env, &seq_key, blood_unit.status);
+            extend_unit_entry_ttl(env, &DataKey::StatusChange(history_id), blood_unit.status);
+        }
+    }
+}
+
+/// Extend an entry owned by a unit, using the TTL class of its status
+fn extend_unit_entry_ttl(env: &Env, key: &DataKey, status: BloodStatus) {
+    if status.is_terminal() {
+        extend_persistent_ttl(env, key, ARCHIVE_TTL_THRESHOLD, ARCHIVE_TTL_EXTEND_TO);
+    } else {
+        extend_persistent_ttl(env, key, ACTIVE_TTL_THRESHOLD, ACTIVE_TTL_EXTEND_TO);
+    }
+}
+
+/// Extend a shared index or registry entry
+fn extend_active_ttl(env: &Env, key: &DataKey) {
+    extend_persistent_ttl(env, key, ACTIVE_TTL_THRESHOLD, ACTIVE_TTL_EXTEND_TO);
+}
+
+/// Extend a persistent entry's TTL if it exists
+fn extend_persistent_ttl(env: &Env, key: &DataKey, threshold: u32, extend_to: u32) {
+    if env.storage().persistent().has(key) {
+        env.storage()
+            .persistent()
+            .extend_ttl(key, threshold, extend_to);
+    }
+}
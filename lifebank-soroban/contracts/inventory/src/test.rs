@@ -1,9 +1,18 @@
+use crate::error::ContractError;
 use crate::storage;
-use crate::types::{BloodStatus, BloodType};
+use crate::types::{
+    BankTransfer, BatchStatusUpdate, BatchUpdateOutcome, BloodComponent, BloodStatus, BloodType,
+    DataKey, DeferralReason, DiscardReason, DonationType, NewUnit, RecallReason, Role,
+    ScreeningResult, StatusDetail, TtiMarker,
+};
+use crate::validation;
 use crate::{InventoryContract, InventoryContractClient};
+use core::sync::atomic::{AtomicU32, Ordering};
 use soroban_sdk::{
-    testutils::{Address as _, Ledger},
-    vec, Address, Env, String,
+    testutils::{storage::Persistent as _, Address as _, Events, Ledger},
+    vec,
+    xdr::ToXdr,
+    Address, Bytes, BytesN, Env, IntoVal, String, Symbol,
 };
 
 fn create_test_contract<'a>() -> (Env, Address, InventoryContractClient<'a>, Address) {
@@ -20,6 +29,36 @@ fn create_test_contract<'a>() -> (Env, Address, InventoryContractClient<'a>, Add
     (env, admin, client, contract_id)
 }
 
+/// Generate a valid, unique ISBT 128 DIN for a test registration
+fn next_din(env: &Env) -> String {
+    static SEQUENCE: AtomicU32 = AtomicU32::new(1);
+    din_with_sequence(env, SEQUENCE.fetch_add(1, Ordering::Relaxed))
+}
+
+/// Build a valid ISBT 128 DIN for facility W0000, year 26
+fn din_with_sequence(env: &Env, sequence: u32) -> String {
+    let mut bytes = *b"W000026000000?";
+    let mut n = sequence;
+    for i in (7..13).rev() {
+        bytes[i] = b'0' + (n % 10) as u8;
+        n /= 10;
+    }
+    bytes[13] = validation::isbt_check_character(&bytes[..13]);
+    String::from_bytes(env, &bytes)
+}
+
+/// Standard whole blood product code
+fn product_code(env: &Env) -> String {
+    String::from_str(env, "E0001V00")
+}
+
+/// Record negative results for every required marker, releasing the unit
+fn clear_screening(client: &InventoryContractClient, lab: &Address, unit_id: u64) {
+    for marker in storage::REQUIRED_TTI_MARKERS {
+        client.record_screening_result(&unit_id, lab, &marker, &ScreeningResult::Negative);
+    }
+}
+
 #[test]
 fn test_initialize_success() {
     let (env, admin, _client, contract_id) = create_test_contract();
@@ -56,7 +95,10 @@ fn test_register_blood_success() {
 
     let blood_unit_id = client.register_blood(
         &bank,
+        &next_din(&env),
+        &product_code(&env),
         &blood_type,
+        &BloodComponent::WholeBlood,
         &quantity_ml,
         &expiration,
         &Some(donor.clone()),
@@ -73,7 +115,7 @@ fn test_register_blood_success() {
     assert_eq!(stored_unit.donor_id, Some(donor));
     assert_eq!(stored_unit.donation_timestamp, current_time);
     assert_eq!(stored_unit.expiration_timestamp, expiration);
-    assert_eq!(stored_unit.status, BloodStatus::Available);
+    assert_eq!(stored_unit.status, BloodStatus::Quarantined);
 }
 
 #[test]
@@ -86,7 +128,10 @@ fn test_register_blood_anonymous_donor() {
 
     let blood_unit_id = client.register_blood(
         &bank,
+        &next_din(&env),
+        &product_code(&env),
         &BloodType::ONegative,
+        &BloodComponent::WholeBlood,
         &450u32,
         &(current_time + 30 * 86400),
         &None, // Anonymous donor
@@ -106,15 +151,42 @@ fn test_register_blood_increments_id() {
     let expiration = current_time + (30 * 86400);
 
     // Register first unit
-    let id1 = client.register_blood(&bank, &BloodType::APositive, &450u32, &expiration, &None);
+    let id1 = client.register_blood(
+        &bank,
+        &next_din(&env),
+        &product_code(&env),
+        &BloodType::APositive,
+        &BloodComponent::WholeBlood,
+        &450u32,
+        &expiration,
+        &None,
+    );
     assert_eq!(id1, 1);
 
     // Register second unit
-    let id2 = client.register_blood(&bank, &BloodType::BPositive, &450u32, &expiration, &None);
+    let id2 = client.register_blood(
+        &bank,
+        &next_din(&env),
+        &product_code(&env),
+        &BloodType::BPositive,
+        &BloodComponent::WholeBlood,
+        &450u32,
+        &expiration,
+        &None,
+    );
     assert_eq!(id2, 2);
 
     // Register third unit
-    let id3 = client.register_blood(&bank, &BloodType::ONegative, &450u32, &expiration, &None);
+    let id3 = client.register_blood(
+        &bank,
+        &next_din(&env),
+        &product_code(&env),
+        &BloodType::ONegative,
+        &BloodComponent::WholeBlood,
+        &450u32,
+        &expiration,
+        &None,
+    );
     assert_eq!(id3, 3);
 }
 
@@ -129,7 +201,10 @@ fn test_register_blood_quantity_too_low() {
 
     client.register_blood(
         &bank,
+        &next_din(&env),
+        &product_code(&env),
         &BloodType::APositive,
+        &BloodComponent::WholeBlood,
         &50u32, // Too low
         &(current_time + 30 * 86400),
         &None,
@@ -147,7 +222,10 @@ fn test_register_blood_quantity_too_high() {
 
     client.register_blood(
         &bank,
+        &next_din(&env),
+        &product_code(&env),
         &BloodType::APositive,
+        &BloodComponent::WholeBlood,
         &700u32, // Too high
         &(current_time + 30 * 86400),
         &None,
@@ -165,7 +243,10 @@ fn test_register_blood_expiration_in_past() {
 
     client.register_blood(
         &bank,
+        &next_din(&env),
+        &product_code(&env),
         &BloodType::APositive,
+        &BloodComponent::WholeBlood,
         &450u32,
         &(current_time - 100), // In the past
         &None,
@@ -184,7 +265,10 @@ fn test_register_blood_expiration_too_far_future() {
     // 60 days is beyond the 42-day maximum for whole blood
     client.register_blood(
         &bank,
+        &next_din(&env),
+        &product_code(&env),
         &BloodType::APositive,
+        &BloodComponent::WholeBlood,
         &450u32,
         &(current_time + 60 * 86400),
         &None,
@@ -203,7 +287,10 @@ fn test_register_blood_insufficient_shelf_life() {
     // Only 12 hours shelf life (less than minimum 1 day)
     client.register_blood(
         &bank,
+        &next_din(&env),
+        &product_code(&env),
         &BloodType::APositive,
+        &BloodComponent::WholeBlood,
         &450u32,
         &(current_time + 43200),
         &None,
@@ -213,7 +300,7 @@ fn test_register_blood_insufficient_shelf_life() {
 #[test]
 #[should_panic(expected = "Error(Contract, #32)")]
 fn test_register_blood_unauthorized_bank() {
-    let (env, admin, client, _contract_id) = create_test_contract();
+    let (env, _admin, client, _contract_id) = create_test_contract();
 
     let unauthorized_bank = Address::generate(&env);
     let current_time = 1000u64;
@@ -221,7 +308,10 @@ fn test_register_blood_unauthorized_bank() {
 
     client.register_blood(
         &unauthorized_bank,
+        &next_din(&env),
+        &product_code(&env),
         &BloodType::APositive,
+        &BloodComponent::WholeBlood,
         &450u32,
         &(current_time + 30 * 86400),
         &None,
@@ -250,7 +340,16 @@ fn test_register_all_blood_types() {
     ];
 
     for (i, blood_type) in blood_types.iter().enumerate() {
-        let id = client.register_blood(&bank, &blood_type, &450u32, &expiration, &None);
+        let id = client.register_blood(
+            &bank,
+            &next_din(&env),
+            &product_code(&env),
+            &blood_type,
+            &BloodComponent::WholeBlood,
+            &450u32,
+            &expiration,
+            &None,
+        );
 
         assert_eq!(id, (i + 1) as u64);
 
@@ -277,12 +376,30 @@ fn test_register_blood_edge_case_quantities() {
     let expiration = current_time + (30 * 86400);
 
     // Minimum valid quantity
-    let id1 = client.register_blood(&bank, &BloodType::APositive, &100u32, &expiration, &None);
+    let id1 = client.register_blood(
+        &bank,
+        &next_din(&env),
+        &product_code(&env),
+        &BloodType::APositive,
+        &BloodComponent::WholeBlood,
+        &100u32,
+        &expiration,
+        &None,
+    );
     let unit1 = client.get_blood_unit(&id1);
     assert_eq!(unit1.quantity_ml, 100);
 
     // Maximum valid quantity
-    let id2 = client.register_blood(&bank, &BloodType::BPositive, &600u32, &expiration, &None);
+    let id2 = client.register_blood(
+        &bank,
+        &next_din(&env),
+        &product_code(&env),
+        &BloodType::BPositive,
+        &BloodComponent::WholeBlood,
+        &600u32,
+        &expiration,
+        &None,
+    );
     let unit2 = client.get_blood_unit(&id2);
     assert_eq!(unit2.quantity_ml, 600);
 }
@@ -299,7 +416,10 @@ fn test_register_blood_edge_case_expiration() {
     let min_expiration = current_time + 86400 + 1;
     let id1 = client.register_blood(
         &bank,
+        &next_din(&env),
+        &product_code(&env),
         &BloodType::APositive,
+        &BloodComponent::WholeBlood,
         &450u32,
         &min_expiration,
         &None,
@@ -311,7 +431,10 @@ fn test_register_blood_edge_case_expiration() {
     let max_expiration = current_time + (42 * 86400);
     let id2 = client.register_blood(
         &bank,
+        &next_din(&env),
+        &product_code(&env),
         &BloodType::BPositive,
+        &BloodComponent::WholeBlood,
         &450u32,
         &max_expiration,
         &None,
@@ -329,7 +452,17 @@ fn test_update_status_available_to_reserved() {
     env.ledger().set_timestamp(current_time);
     let expiration = current_time + (30 * 86400);
 
-    let unit_id = client.register_blood(&bank, &BloodType::APositive, &450u32, &expiration, &None);
+    let unit_id = client.register_blood(
+        &bank,
+        &next_din(&env),
+        &product_code(&env),
+        &BloodType::APositive,
+        &BloodComponent::WholeBlood,
+        &450u32,
+        &expiration,
+        &None,
+    );
+    clear_screening(&client, &admin, unit_id);
 
     // Update to Reserved
     let updated_unit = client.update_status(
@@ -355,7 +488,17 @@ fn test_update_status_complete_flow() {
     env.ledger().set_timestamp(current_time);
     let expiration = current_time + (30 * 86400);
 
-    let unit_id = client.register_blood(&bank, &BloodType::APositive, &450u32, &expiration, &None);
+    let unit_id = client.register_blood(
+        &bank,
+        &next_din(&env),
+        &product_code(&env),
+        &BloodType::APositive,
+        &BloodComponent::WholeBlood,
+        &450u32,
+        &expiration,
+        &None,
+    );
+    clear_screening(&client, &admin, unit_id);
 
     // Available -> Reserved
     let unit = client.update_status(
@@ -395,7 +538,17 @@ fn test_update_status_invalid_transition() {
     env.ledger().set_timestamp(current_time);
     let expiration = current_time + (30 * 86400);
 
-    let unit_id = client.register_blood(&bank, &BloodType::APositive, &450u32, &expiration, &None);
+    let unit_id = client.register_blood(
+        &bank,
+        &next_din(&env),
+        &product_code(&env),
+        &BloodType::APositive,
+        &BloodComponent::WholeBlood,
+        &450u32,
+        &expiration,
+        &None,
+    );
+    clear_screening(&client, &admin, unit_id);
 
     // Try to go directly from Available to Delivered (invalid)
     client.update_status(
@@ -416,7 +569,17 @@ fn test_update_status_unauthorized() {
     env.ledger().set_timestamp(current_time);
     let expiration = current_time + (30 * 86400);
 
-    let unit_id = client.register_blood(&bank, &BloodType::APositive, &450u32, &expiration, &None);
+    let unit_id = client.register_blood(
+        &bank,
+        &next_din(&env),
+        &product_code(&env),
+        &BloodType::APositive,
+        &BloodComponent::WholeBlood,
+        &450u32,
+        &expiration,
+        &None,
+    );
+    clear_screening(&client, &admin, unit_id);
 
     let unauthorized = Address::generate(&env);
 
@@ -427,7 +590,7 @@ fn test_update_status_unauthorized() {
 #[test]
 #[should_panic(expected = "Error(Contract, #21)")]
 fn test_update_status_nonexistent_unit() {
-    let (env, admin, client, _contract_id) = create_test_contract();
+    let (_env, admin, client, _contract_id) = create_test_contract();
 
     // Try to update unit that doesn't exist
     client.update_status(&999, &BloodStatus::Reserved, &admin, &None);
@@ -443,7 +606,17 @@ fn test_update_status_expired_unit() {
     env.ledger().set_timestamp(current_time);
     let expiration = current_time + (5 * 86400); // 5 days
 
-    let unit_id = client.register_blood(&bank, &BloodType::APositive, &450u32, &expiration, &None);
+    let unit_id = client.register_blood(
+        &bank,
+        &next_din(&env),
+        &product_code(&env),
+        &BloodType::APositive,
+        &BloodComponent::WholeBlood,
+        &450u32,
+        &expiration,
+        &None,
+    );
+    clear_screening(&client, &admin, unit_id);
 
     // Move time past expiration
     env.ledger().set_timestamp(expiration + 100);
@@ -462,7 +635,17 @@ fn test_update_status_from_terminal_delivered() {
     env.ledger().set_timestamp(current_time);
     let expiration = current_time + (30 * 86400);
 
-    let unit_id = client.register_blood(&bank, &BloodType::APositive, &450u32, &expiration, &None);
+    let unit_id = client.register_blood(
+        &bank,
+        &next_din(&env),
+        &product_code(&env),
+        &BloodType::APositive,
+        &BloodComponent::WholeBlood,
+        &450u32,
+        &expiration,
+        &None,
+    );
+    clear_screening(&client, &admin, unit_id);
 
     // Move to Delivered
     client.update_status(&unit_id, &BloodStatus::Reserved, &admin, &None);
@@ -484,7 +667,17 @@ fn test_mark_delivered_success() {
     env.ledger().set_timestamp(current_time);
     let expiration = current_time + (30 * 86400);
 
-    let unit_id = client.register_blood(&bank, &BloodType::APositive, &450u32, &expiration, &None);
+    let unit_id = client.register_blood(
+        &bank,
+        &next_din(&env),
+        &product_code(&env),
+        &BloodType::APositive,
+        &BloodComponent::WholeBlood,
+        &450u32,
+        &expiration,
+        &None,
+    );
+    clear_screening(&client, &admin, unit_id);
 
     // Set to Reserved first (should be InTransit in real scenario, but for test)
     client.update_status(&unit_id, &BloodStatus::Reserved, &admin, &None);
@@ -506,7 +699,17 @@ fn test_mark_delivered_from_available() {
     env.ledger().set_timestamp(current_time);
     let expiration = current_time + (30 * 86400);
 
-    let unit_id = client.register_blood(&bank, &BloodType::APositive, &450u32, &expiration, &None);
+    let unit_id = client.register_blood(
+        &bank,
+        &next_din(&env),
+        &product_code(&env),
+        &BloodType::APositive,
+        &BloodComponent::WholeBlood,
+        &450u32,
+        &expiration,
+        &None,
+    );
+    clear_screening(&client, &admin, unit_id);
 
     // Try to mark as delivered when still Available (invalid transition)
     client.mark_delivered(&unit_id, &admin, &String::from_str(&env, "Hospital A"));
@@ -523,7 +726,17 @@ fn test_mark_expired_success() {
     env.ledger().set_timestamp(current_time);
     let expiration = current_time + (30 * 86400);
 
-    let unit_id = client.register_blood(&bank, &BloodType::APositive, &450u32, &expiration, &None);
+    let unit_id = client.register_blood(
+        &bank,
+        &next_din(&env),
+        &product_code(&env),
+        &BloodType::APositive,
+        &BloodComponent::WholeBlood,
+        &450u32,
+        &expiration,
+        &None,
+    );
+    clear_screening(&client, &admin, unit_id);
 
     // Mark as expired from Available state (valid transition)
     let updated = client.mark_expired(&unit_id, &admin);
@@ -540,7 +753,17 @@ fn test_mark_expired_from_reserved() {
     env.ledger().set_timestamp(current_time);
     let expiration = current_time + (30 * 86400);
 
-    let unit_id = client.register_blood(&bank, &BloodType::APositive, &450u32, &expiration, &None);
+    let unit_id = client.register_blood(
+        &bank,
+        &next_din(&env),
+        &product_code(&env),
+        &BloodType::APositive,
+        &BloodComponent::WholeBlood,
+        &450u32,
+        &expiration,
+        &None,
+    );
+    clear_screening(&client, &admin, unit_id);
 
     // Move to Reserved
     client.update_status(&unit_id, &BloodStatus::Reserved, &admin, &None);
@@ -562,7 +785,17 @@ fn test_status_history_tracking() {
     env.ledger().set_timestamp(current_time);
     let expiration = current_time + (30 * 86400);
 
-    let unit_id = client.register_blood(&bank, &BloodType::APositive, &450u32, &expiration, &None);
+    let unit_id = client.register_blood(
+        &bank,
+        &next_din(&env),
+        &product_code(&env),
+        &BloodType::APositive,
+        &BloodComponent::WholeBlood,
+        &450u32,
+        &expiration,
+        &None,
+    );
+    clear_screening(&client, &admin, unit_id);
 
     // Perform status changes
     client.update_status(
@@ -589,22 +822,27 @@ fn test_status_history_tracking() {
     // Get history
     let history = client.get_status_history(&unit_id);
 
-    // Should have 3 history entries
-    assert_eq!(history.len(), 3);
+    // Should have 4 history entries (quarantine release + 3 updates)
+    assert_eq!(history.len(), 4);
+
+    // Check release: Quarantined -> Available
+    let release = history.get(0).unwrap();
+    assert_eq!(release.from_status, BloodStatus::Quarantined);
+    assert_eq!(release.to_status, BloodStatus::Available);
 
     // Check first transition: Available -> Reserved
-    let h0 = history.get(0).unwrap();
+    let h0 = history.get(1).unwrap();
     assert_eq!(h0.from_status, BloodStatus::Available);
     assert_eq!(h0.to_status, BloodStatus::Reserved);
     assert_eq!(h0.authorized_by, admin);
 
     // Check second transition: Reserved -> InTransit
-    let h1 = history.get(1).unwrap();
+    let h1 = history.get(2).unwrap();
     assert_eq!(h1.from_status, BloodStatus::Reserved);
     assert_eq!(h1.to_status, BloodStatus::InTransit);
 
     // Check third transition: InTransit -> Delivered
-    let h2 = history.get(2).unwrap();
+    let h2 = history.get(3).unwrap();
     assert_eq!(h2.from_status, BloodStatus::InTransit);
     assert_eq!(h2.to_status, BloodStatus::Delivered);
 }
@@ -618,20 +856,244 @@ fn test_status_change_count() {
     env.ledger().set_timestamp(current_time);
     let expiration = current_time + (30 * 86400);
 
-    let unit_id = client.register_blood(&bank, &BloodType::APositive, &450u32, &expiration, &None);
+    let unit_id = client.register_blood(
+        &bank,
+        &next_din(&env),
+        &product_code(&env),
+        &BloodType::APositive,
+        &BloodComponent::WholeBlood,
+        &450u32,
+        &expiration,
+        &None,
+    );
 
     // Initial count should be 0 (no changes yet)
     assert_eq!(client.get_status_change_count(&unit_id), 0);
 
+    // Release from quarantine counts as the first change
+    clear_screening(&client, &admin, unit_id);
+    assert_eq!(client.get_status_change_count(&unit_id), 1);
+
     // Make changes
     client.update_status(&unit_id, &BloodStatus::Reserved, &admin, &None);
-    assert_eq!(client.get_status_change_count(&unit_id), 1);
+    assert_eq!(client.get_status_change_count(&unit_id), 2);
 
     client.update_status(&unit_id, &BloodStatus::InTransit, &admin, &None);
-    assert_eq!(client.get_status_change_count(&unit_id), 2);
+    assert_eq!(client.get_status_change_count(&unit_id), 3);
 
     client.update_status(&unit_id, &BloodStatus::Delivered, &admin, &None);
-    assert_eq!(client.get_status_change_count(&unit_id), 3);
+    assert_eq!(client.get_status_change_count(&unit_id), 4);
+}
+
+/// Register an available unit and toggle it Reserved/Available `cycles` times
+fn unit_with_history(
+    env: &Env,
+    client: &InventoryContractClient,
+    admin: &Address,
+    cycles: u32,
+) -> u64 {
+    let unit_id = register_available_unit(env, client, admin);
+    for _ in 0..cycles {
+        client.update_status(&unit_id, &BloodStatus::Reserved, admin, &None);
+        client.update_status(&unit_id, &BloodStatus::Available, admin, &None);
+    }
+    unit_id
+}
+
+#[test]
+fn test_status_history_pagination() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+
+    // 1 release + 6 updates
+    let unit_id = unit_with_history(&env, &client, &admin, 3);
+    assert_eq!(client.get_status_change_count(&unit_id), 7);
+
+    let first = client.get_status_history_page(&unit_id, &0, &3);
+    assert_eq!(first.entries.len(), 3);
+    assert_eq!(first.next_cursor, Some(3));
+    assert_eq!(
+        first.entries.get(0).unwrap().to_status,
+        BloodStatus::Available
+    );
+    assert_eq!(
+        first.entries.get(1).unwrap().to_status,
+        BloodStatus::Reserved
+    );
+
+    let second = client.get_status_history_page(&unit_id, &3, &3);
+    assert_eq!(second.entries.len(), 3);
+    assert_eq!(second.next_cursor, Some(6));
+
+    let last = client.get_status_history_page(&unit_id, &6, &3);
+    assert_eq!(last.entries.len(), 1);
+    assert_eq!(last.next_cursor, None);
+
+    // Pages line up with the full history
+    let history = client.get_status_history(&unit_id);
+    assert_eq!(history.len(), 7);
+    assert_eq!(second.entries.get(0).unwrap(), history.get(3).unwrap());
+
+    // Past the end is empty
+    let empty = client.get_status_history_page(&unit_id, &10, &3);
+    assert_eq!(empty.entries.len(), 0);
+    assert_eq!(empty.next_cursor, None);
+}
+
+#[test]
+fn test_status_history_page_limit_is_capped() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+
+    let unit_id = unit_with_history(&env, &client, &admin, 30);
+
+    let page = client.get_status_history_page(&unit_id, &0, &u32::MAX);
+    assert_eq!(page.entries.len(), storage::MAX_HISTORY_PAGE_SIZE);
+    assert_eq!(
+        page.next_cursor,
+        Some(storage::MAX_HISTORY_PAGE_SIZE as u64)
+    );
+}
+
+#[test]
+fn test_get_status_change_by_id() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+
+    let unit_id = unit_with_history(&env, &client, &admin, 1);
+    let history = client.get_status_history(&unit_id);
+
+    for entry in history.iter() {
+        assert_eq!(client.get_status_change(&entry.id), entry);
+    }
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #21)")]
+fn test_get_status_change_not_found() {
+    let (_env, _admin, client, _contract_id) = create_test_contract();
+
+    client.get_status_change(&999);
+}
+
+#[test]
+fn test_archive_status_history() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+
+    let unit_id = unit_with_history(&env, &client, &admin, 2);
+    client.discard_unit(&unit_id, &admin, &DiscardReason::Other, &None);
+    let history = client.get_status_history(&unit_id);
+    assert_eq!(history.len(), 6);
+    assert_eq!(client.get_status_history_summary(&unit_id), None);
+
+    let summary = client.archive_status_history(&unit_id, &2);
+    let events = env.events().all();
+    assert_eq!(
+        events.last().unwrap().1,
+        (Symbol::new(&env, "history_archived"), unit_id).into_val(&env)
+    );
+    assert_eq!(summary.blood_unit_id, unit_id);
+    assert_eq!(summary.archived_count, 4);
+    assert_eq!(summary.last_history_id, history.get(3).unwrap().id);
+    assert_eq!(
+        client.get_status_history_summary(&unit_id),
+        Some(summary.clone())
+    );
+
+    // Only the newest entries remain; the count still covers everything
+    let remaining = client.get_status_history(&unit_id);
+    assert_eq!(remaining.len(), 2);
+    assert_eq!(remaining.get(0).unwrap(), history.get(4).unwrap());
+    assert_eq!(client.get_status_change_count(&unit_id), 6);
+    assert!(client
+        .try_get_status_change(&history.get(0).unwrap().id)
+        .is_err());
+
+    // Paging from the start skips archived entries
+    let page = client.get_status_history_page(&unit_id, &0, &10);
+    assert_eq!(page.entries, remaining);
+}
+
+#[test]
+fn test_archive_status_history_chains_hashes() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+
+    let unit_id = unit_with_history(&env, &client, &admin, 2);
+    client.discard_unit(&unit_id, &admin, &DiscardReason::Other, &None);
+    let history = client.get_status_history(&unit_id);
+
+    // Archiving in two steps folds the same entries as one step would
+    client.archive_status_history(&unit_id, &3);
+    let summary = client.archive_status_history(&unit_id, &2);
+    assert_eq!(summary.archived_count, 4);
+
+    let mut expected = BytesN::from_array(&env, &[0; 32]);
+    for entry in history.iter().take(4) {
+        let mut data = Bytes::from_array(&env, &expected.to_array());
+        data.append(&entry.to_xdr(&env));
+        expected = env.crypto().sha256(&data).to_bytes();
+    }
+    assert_eq!(summary.chain_hash, expected);
+    assert_eq!(summary.last_changed_at, history.get(3).unwrap().changed_at);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #12)")]
+fn test_archive_status_history_nothing_to_archive() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+
+    let unit_id = unit_with_history(&env, &client, &admin, 1);
+    client.discard_unit(&unit_id, &admin, &DiscardReason::Other, &None);
+    client.archive_status_history(&unit_id, &4);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #14)")]
+fn test_archive_status_history_active_unit_rejected() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+
+    let unit_id = unit_with_history(&env, &client, &admin, 2);
+    client.archive_status_history(&unit_id, &1);
+}
+
+#[test]
+fn test_recall_lookback_survives_history_archive() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+
+    let donor = Address::generate(&env);
+    env.ledger().set_timestamp(1000);
+    let unit_id = client.register_blood(
+        &admin,
+        &next_din(&env),
+        &product_code(&env),
+        &BloodType::APositive,
+        &BloodComponent::WholeBlood,
+        &450u32,
+        &(1000 + 30 * 86400),
+        &Some(donor.clone()),
+    );
+    clear_screening(&client, &admin, unit_id);
+    client.update_status(&unit_id, &BloodStatus::Reserved, &admin, &None);
+    client.update_status(&unit_id, &BloodStatus::InTransit, &admin, &None);
+    env.ledger().set_timestamp(1500);
+    client.mark_delivered(&unit_id, &admin, &String::from_str(&env, "Hospital A"));
+    client.mark_transfused(&unit_id, &admin);
+
+    client.archive_status_history(&unit_id, &0);
+    assert_eq!(client.get_status_history(&unit_id).len(), 0);
+
+    let report = client.recall_donor_units(&donor, &RecallReason::DonorTestedPositive);
+    let entry = report.lookback.get(0).unwrap();
+    assert_eq!(
+        entry.destination,
+        Some(String::from_str(&env, "Hospital A"))
+    );
+    assert_eq!(entry.delivered_at, 1500);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #21)")]
+fn test_archive_status_history_unit_not_found() {
+    let (_env, _admin, client, _contract_id) = create_test_contract();
+
+    client.archive_status_history(&999, &0);
 }
 
 // ==================== Batch Update Tests ====================
@@ -646,9 +1108,39 @@ fn test_batch_update_status_success() {
     let expiration = current_time + (30 * 86400);
 
     // Create multiple blood units
-    let id1 = client.register_blood(&bank, &BloodType::APositive, &450u32, &expiration, &None);
-    let id2 = client.register_blood(&bank, &BloodType::BPositive, &450u32, &expiration, &None);
-    let id3 = client.register_blood(&bank, &BloodType::ONegative, &450u32, &expiration, &None);
+    let id1 = client.register_blood(
+        &bank,
+        &next_din(&env),
+        &product_code(&env),
+        &BloodType::APositive,
+        &BloodComponent::WholeBlood,
+        &450u32,
+        &expiration,
+        &None,
+    );
+    clear_screening(&client, &admin, id1);
+    let id2 = client.register_blood(
+        &bank,
+        &next_din(&env),
+        &product_code(&env),
+        &BloodType::BPositive,
+        &BloodComponent::WholeBlood,
+        &450u32,
+        &expiration,
+        &None,
+    );
+    clear_screening(&client, &admin, id2);
+    let id3 = client.register_blood(
+        &bank,
+        &next_din(&env),
+        &product_code(&env),
+        &BloodType::ONegative,
+        &BloodComponent::WholeBlood,
+        &450u32,
+        &expiration,
+        &None,
+    );
+    clear_screening(&client, &admin, id3);
 
     // Batch update to Reserved
     let unit_ids = vec![&env, id1, id2, id3];
@@ -676,7 +1168,17 @@ fn test_batch_update_status_single_unit() {
     env.ledger().set_timestamp(current_time);
     let expiration = current_time + (30 * 86400);
 
-    let unit_id = client.register_blood(&bank, &BloodType::APositive, &450u32, &expiration, &None);
+    let unit_id = client.register_blood(
+        &bank,
+        &next_din(&env),
+        &product_code(&env),
+        &BloodType::APositive,
+        &BloodComponent::WholeBlood,
+        &450u32,
+        &expiration,
+        &None,
+    );
+    clear_screening(&client, &admin, unit_id);
 
     let unit_ids = vec![&env, unit_id];
     let count = client.batch_update_status(&unit_ids, &BloodStatus::Reserved, &admin, &None);
@@ -708,7 +1210,17 @@ fn test_batch_update_status_nonexistent_unit() {
     env.ledger().set_timestamp(current_time);
     let expiration = current_time + (30 * 86400);
 
-    let unit_id = client.register_blood(&bank, &BloodType::APositive, &450u32, &expiration, &None);
+    let unit_id = client.register_blood(
+        &bank,
+        &next_din(&env),
+        &product_code(&env),
+        &BloodType::APositive,
+        &BloodComponent::WholeBlood,
+        &450u32,
+        &expiration,
+        &None,
+    );
+    clear_screening(&client, &admin, unit_id);
 
     // Try batch update with one nonexistent unit
     let unit_ids = vec![&env, unit_id, 999];
@@ -725,7 +1237,17 @@ fn test_batch_update_status_unauthorized() {
     env.ledger().set_timestamp(current_time);
     let expiration = current_time + (30 * 86400);
 
-    let unit_id = client.register_blood(&bank, &BloodType::APositive, &450u32, &expiration, &None);
+    let unit_id = client.register_blood(
+        &bank,
+        &next_din(&env),
+        &product_code(&env),
+        &BloodType::APositive,
+        &BloodComponent::WholeBlood,
+        &450u32,
+        &expiration,
+        &None,
+    );
+    clear_screening(&client, &admin, unit_id);
 
     let unauthorized = Address::generate(&env);
 
@@ -743,8 +1265,28 @@ fn test_batch_update_status_invalid_transition() {
     env.ledger().set_timestamp(current_time);
     let expiration = current_time + (30 * 86400);
 
-    let id1 = client.register_blood(&bank, &BloodType::APositive, &450u32, &expiration, &None);
-    let id2 = client.register_blood(&bank, &BloodType::BPositive, &450u32, &expiration, &None);
+    let id1 = client.register_blood(
+        &bank,
+        &next_din(&env),
+        &product_code(&env),
+        &BloodType::APositive,
+        &BloodComponent::WholeBlood,
+        &450u32,
+        &expiration,
+        &None,
+    );
+    clear_screening(&client, &admin, id1);
+    let id2 = client.register_blood(
+        &bank,
+        &next_din(&env),
+        &product_code(&env),
+        &BloodType::BPositive,
+        &BloodComponent::WholeBlood,
+        &450u32,
+        &expiration,
+        &None,
+    );
+    clear_screening(&client, &admin, id2);
 
     // Move id1 to Reserved
     client.update_status(&id1, &BloodStatus::Reserved, &admin, &None);
@@ -758,3 +1300,2597 @@ fn test_batch_update_status_invalid_transition() {
         &None,
     );
 }
+
+// ==================== Screening Tests ====================
+
+#[test]
+fn test_screening_all_negative_releases_unit() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+
+    let lab = Address::generate(&env);
+    client.authorize_lab(&lab);
+    assert!(client.is_lab_authorized(&lab));
+
+    let current_time = 1000u64;
+    env.ledger().set_timestamp(current_time);
+    let expiration = current_time + (30 * 86400);
+
+    let unit_id = client.register_blood(
+        &admin,
+        &next_din(&env),
+        &product_code(&env),
+        &BloodType::APositive,
+        &BloodComponent::WholeBlood,
+        &450u32,
+        &expiration,
+        &None,
+    );
+
+    // Partial screening keeps the unit in quarantine
+    client.record_screening_result(&unit_id, &lab, &TtiMarker::Hiv, &ScreeningResult::Negative);
+    client.record_screening_result(&unit_id, &lab, &TtiMarker::Hbv, &ScreeningResult::Negative);
+    assert_eq!(
+        client.get_blood_unit(&unit_id).status,
+        BloodStatus::Quarantined
+    );
+
+    client.record_screening_result(&unit_id, &lab, &TtiMarker::Hcv, &ScreeningResult::Negative);
+    client.record_screening_result(
+        &unit_id,
+        &lab,
+        &TtiMarker::Syphilis,
+        &ScreeningResult::Negative,
+    );
+    let unit = client.record_screening_result(
+        &unit_id,
+        &lab,
+        &TtiMarker::Malaria,
+        &ScreeningResult::Negative,
+    );
+
+    assert_eq!(unit.status, BloodStatus::Available);
+    assert_eq!(client.get_screening_results(&unit_id).len(), 5);
+
+    // Release is recorded in history under the lab
+    let history = client.get_status_history(&unit_id);
+    assert_eq!(history.len(), 1);
+    let h0 = history.get(0).unwrap();
+    assert_eq!(h0.from_status, BloodStatus::Quarantined);
+    assert_eq!(h0.to_status, BloodStatus::Available);
+    assert_eq!(h0.authorized_by, lab);
+}
+
+#[test]
+fn test_screening_positive_discards_unit_and_flags_donor() {
+    let (env, admin, client, contract_id) = create_test_contract();
+
+    let current_time = 1000u64;
+    env.ledger().set_timestamp(current_time);
+    let expiration = current_time + (30 * 86400);
+    let donor = Address::generate(&env);
+
+    let unit_id = client.register_blood(
+        &admin,
+        &next_din(&env),
+        &product_code(&env),
+        &BloodType::ONegative,
+        &BloodComponent::WholeBlood,
+        &450u32,
+        &expiration,
+        &Some(donor.clone()),
+    );
+
+    client.record_screening_result(
+        &unit_id,
+        &admin,
+        &TtiMarker::Hiv,
+        &ScreeningResult::Negative,
+    );
+    let unit = client.record_screening_result(
+        &unit_id,
+        &admin,
+        &TtiMarker::Hbv,
+        &ScreeningResult::Positive,
+    );
+
+    assert_eq!(unit.status, BloodStatus::Discarded);
+    assert!(unit.status.is_terminal());
+
+    let (event_contract, topics, _data) = env.events().all().last().unwrap();
+    assert_eq!(event_contract, contract_id);
+    assert_eq!(
+        topics,
+        (Symbol::new(&env, "donor_follow_up"),).into_val(&env)
+    );
+}
+
+#[test]
+fn test_positive_screening_recorded_after_expiry() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+
+    let current_time = 1000u64;
+    env.ledger().set_timestamp(current_time);
+    let expiration = current_time + (5 * 86400);
+    let donor = Address::generate(&env);
+
+    let unit_id = client.register_blood(
+        &admin,
+        &next_din(&env),
+        &product_code(&env),
+        &BloodType::ONegative,
+        &BloodComponent::Platelets,
+        &300u32,
+        &expiration,
+        &Some(donor.clone()),
+    );
+
+    // The lab reports a reactive marker after the unit's shelf life ended
+    env.ledger().set_timestamp(expiration + 100);
+    let unit = client.record_screening_result(
+        &unit_id,
+        &admin,
+        &TtiMarker::Hcv,
+        &ScreeningResult::Positive,
+    );
+
+    assert_eq!(unit.status, BloodStatus::Discarded);
+    let (_, topics, _) = env.events().all().last().unwrap();
+    assert_eq!(
+        topics,
+        (Symbol::new(&env, "donor_follow_up"),).into_val(&env)
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #23)")]
+fn test_negative_screening_refused_after_expiry() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+
+    let current_time = 1000u64;
+    env.ledger().set_timestamp(current_time);
+    let expiration = current_time + (5 * 86400);
+
+    let unit_id = client.register_blood(
+        &admin,
+        &next_din(&env),
+        &product_code(&env),
+        &BloodType::ONegative,
+        &BloodComponent::Platelets,
+        &300u32,
+        &expiration,
+        &None,
+    );
+
+    env.ledger().set_timestamp(expiration + 100);
+    client.record_screening_result(
+        &unit_id,
+        &admin,
+        &TtiMarker::Hcv,
+        &ScreeningResult::Negative,
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #33)")]
+fn test_screening_unauthorized_lab() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+
+    let current_time = 1000u64;
+    env.ledger().set_timestamp(current_time);
+    let unit_id = client.register_blood(
+        &admin,
+        &next_din(&env),
+        &product_code(&env),
+        &BloodType::APositive,
+        &BloodComponent::WholeBlood,
+        &450u32,
+        &(current_time + 30 * 86400),
+        &None,
+    );
+
+    let lab = Address::generate(&env);
+    client.record_screening_result(&unit_id, &lab, &TtiMarker::Hiv, &ScreeningResult::Negative);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #33)")]
+fn test_screening_revoked_lab() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+
+    let lab = Address::generate(&env);
+    client.authorize_lab(&lab);
+    client.revoke_lab(&lab);
+
+    let current_time = 1000u64;
+    env.ledger().set_timestamp(current_time);
+    let unit_id = client.register_blood(
+        &admin,
+        &next_din(&env),
+        &product_code(&env),
+        &BloodType::APositive,
+        &BloodComponent::WholeBlood,
+        &450u32,
+        &(current_time + 30 * 86400),
+        &None,
+    );
+
+    client.record_screening_result(&unit_id, &lab, &TtiMarker::Hiv, &ScreeningResult::Negative);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #20)")]
+fn test_screening_duplicate_marker() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+
+    let current_time = 1000u64;
+    env.ledger().set_timestamp(current_time);
+    let unit_id = client.register_blood(
+        &admin,
+        &next_din(&env),
+        &product_code(&env),
+        &BloodType::APositive,
+        &BloodComponent::WholeBlood,
+        &450u32,
+        &(current_time + 30 * 86400),
+        &None,
+    );
+
+    client.record_screening_result(
+        &unit_id,
+        &admin,
+        &TtiMarker::Hiv,
+        &ScreeningResult::Negative,
+    );
+    client.record_screening_result(
+        &unit_id,
+        &admin,
+        &TtiMarker::Hiv,
+        &ScreeningResult::Negative,
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #42)")]
+fn test_screening_unit_not_quarantined() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+
+    let current_time = 1000u64;
+    env.ledger().set_timestamp(current_time);
+    let unit_id = client.register_blood(
+        &admin,
+        &next_din(&env),
+        &product_code(&env),
+        &BloodType::APositive,
+        &BloodComponent::WholeBlood,
+        &450u32,
+        &(current_time + 30 * 86400),
+        &None,
+    );
+    clear_screening(&client, &admin, unit_id);
+
+    client.record_screening_result(
+        &unit_id,
+        &admin,
+        &TtiMarker::Hiv,
+        &ScreeningResult::Positive,
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #43)")]
+fn test_update_status_release_before_screening() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+
+    let current_time = 1000u64;
+    env.ledger().set_timestamp(current_time);
+    let unit_id = client.register_blood(
+        &admin,
+        &next_din(&env),
+        &product_code(&env),
+        &BloodType::APositive,
+        &BloodComponent::WholeBlood,
+        &450u32,
+        &(current_time + 30 * 86400),
+        &None,
+    );
+
+    client.record_screening_result(
+        &unit_id,
+        &admin,
+        &TtiMarker::Hiv,
+        &ScreeningResult::Negative,
+    );
+
+    // Admin cannot bypass outstanding markers
+    client.update_status(&unit_id, &BloodStatus::Available, &admin, &None);
+}
+
+// ==================== Disposal Tests ====================
+
+#[test]
+fn test_discard_unit_records_reason_and_wastage() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+
+    let current_time = 1000u64;
+    env.ledger().set_timestamp(current_time);
+    let expiration = current_time + (30 * 86400);
+
+    let id1 = client.register_blood(
+        &admin,
+        &next_din(&env),
+        &product_code(&env),
+        &BloodType::APositive,
+        &BloodComponent::WholeBlood,
+        &450u32,
+        &expiration,
+        &None,
+    );
+    let id2 = client.register_blood(
+        &admin,
+        &next_din(&env),
+        &product_code(&env),
+        &BloodType::BPositive,
+        &BloodComponent::WholeBlood,
+        &300u32,
+        &expiration,
+        &None,
+    );
+    clear_screening(&client, &admin, id1);
+    clear_screening(&client, &admin, id2);
+    client.update_status(&id2, &BloodStatus::Reserved, &admin, &None);
+
+    let unit = client.discard_unit(
+        &id1,
+        &admin,
+        &DiscardReason::Damaged,
+        &Some(String::from_str(&env, "Bag punctured")),
+    );
+    assert_eq!(unit.status, BloodStatus::Discarded);
+    client.discard_unit(&id2, &admin, &DiscardReason::Damaged, &None);
+
+    let wastage = client.get_wastage(&admin, &DiscardReason::Damaged);
+    assert_eq!(wastage.units, 2);
+    assert_eq!(wastage.volume_ml, 750);
+    assert_eq!(
+        client
+            .get_wastage(&admin, &DiscardReason::Contaminated)
+            .units,
+        0
+    );
+
+    let history = client.get_status_history(&id1);
+    let last = history.get(history.len() - 1).unwrap();
+    assert_eq!(last.from_status, BloodStatus::Available);
+    assert_eq!(last.to_status, BloodStatus::Discarded);
+    assert_eq!(last.detail, StatusDetail::Discarded(DiscardReason::Damaged));
+}
+
+#[test]
+fn test_failed_screening_counts_as_wastage() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+
+    let current_time = 1000u64;
+    env.ledger().set_timestamp(current_time);
+    let unit_id = client.register_blood(
+        &admin,
+        &next_din(&env),
+        &product_code(&env),
+        &BloodType::APositive,
+        &BloodComponent::WholeBlood,
+        &450u32,
+        &(current_time + 30 * 86400),
+        &None,
+    );
+
+    client.record_screening_result(
+        &unit_id,
+        &admin,
+        &TtiMarker::Syphilis,
+        &ScreeningResult::Positive,
+    );
+
+    let wastage = client.get_wastage(&admin, &DiscardReason::FailedScreening);
+    assert_eq!(wastage.units, 1);
+    assert_eq!(wastage.volume_ml, 450);
+
+    let history = client.get_status_history(&unit_id);
+    assert_eq!(
+        history.get(0).unwrap().detail,
+        StatusDetail::Discarded(DiscardReason::FailedScreening)
+    );
+}
+
+#[test]
+fn test_discard_unit_after_shelf_life_expiry() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+
+    let current_time = 1000u64;
+    env.ledger().set_timestamp(current_time);
+    let expiration = current_time + (5 * 86400);
+    let unit_id = client.register_blood(
+        &admin,
+        &next_din(&env),
+        &product_code(&env),
+        &BloodType::APositive,
+        &BloodComponent::WholeBlood,
+        &450u32,
+        &expiration,
+        &None,
+    );
+    clear_screening(&client, &admin, unit_id);
+
+    env.ledger().set_timestamp(expiration + 100);
+
+    let unit = client.discard_unit(&unit_id, &admin, &DiscardReason::Other, &None);
+    assert_eq!(unit.status, BloodStatus::Discarded);
+}
+
+#[test]
+fn test_mark_transfused_after_delivery() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+
+    let current_time = 1000u64;
+    env.ledger().set_timestamp(current_time);
+    let expiration = current_time + (30 * 86400);
+    let unit_id = client.register_blood(
+        &admin,
+        &next_din(&env),
+        &product_code(&env),
+        &BloodType::APositive,
+        &BloodComponent::WholeBlood,
+        &450u32,
+        &expiration,
+        &None,
+    );
+    clear_screening(&client, &admin, unit_id);
+
+    client.update_status(&unit_id, &BloodStatus::Reserved, &admin, &None);
+    client.update_status(&unit_id, &BloodStatus::InTransit, &admin, &None);
+    client.mark_delivered(&unit_id, &admin, &String::from_str(&env, "Hospital A"));
+
+    let unit = client.mark_transfused(&unit_id, &admin);
+    assert_eq!(unit.status, BloodStatus::Transfused);
+    assert!(unit.status.is_terminal());
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #41)")]
+fn test_mark_transfused_before_delivery() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+
+    let current_time = 1000u64;
+    env.ledger().set_timestamp(current_time);
+    let expiration = current_time + (30 * 86400);
+    let unit_id = client.register_blood(
+        &admin,
+        &next_din(&env),
+        &product_code(&env),
+        &BloodType::APositive,
+        &BloodComponent::WholeBlood,
+        &450u32,
+        &expiration,
+        &None,
+    );
+    clear_screening(&client, &admin, unit_id);
+
+    client.mark_transfused(&unit_id, &admin);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #44)")]
+fn test_update_status_discard_requires_reason() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+
+    let current_time = 1000u64;
+    env.ledger().set_timestamp(current_time);
+    let expiration = current_time + (30 * 86400);
+    let unit_id = client.register_blood(
+        &admin,
+        &next_din(&env),
+        &product_code(&env),
+        &BloodType::APositive,
+        &BloodComponent::WholeBlood,
+        &450u32,
+        &expiration,
+        &None,
+    );
+    clear_screening(&client, &admin, unit_id);
+
+    client.update_status(&unit_id, &BloodStatus::Discarded, &admin, &None);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #41)")]
+fn test_discard_unit_already_terminal() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+
+    let current_time = 1000u64;
+    env.ledger().set_timestamp(current_time);
+    let expiration = current_time + (30 * 86400);
+    let unit_id = client.register_blood(
+        &admin,
+        &next_din(&env),
+        &product_code(&env),
+        &BloodType::APositive,
+        &BloodComponent::WholeBlood,
+        &450u32,
+        &expiration,
+        &None,
+    );
+    clear_screening(&client, &admin, unit_id);
+
+    client.mark_expired(&unit_id, &admin);
+    client.discard_unit(&unit_id, &admin, &DiscardReason::Other, &None);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2)")]
+fn test_discard_unit_unauthorized() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+
+    let current_time = 1000u64;
+    env.ledger().set_timestamp(current_time);
+    let expiration = current_time + (30 * 86400);
+    let unit_id = client.register_blood(
+        &admin,
+        &next_din(&env),
+        &product_code(&env),
+        &BloodType::APositive,
+        &BloodComponent::WholeBlood,
+        &450u32,
+        &expiration,
+        &None,
+    );
+
+    let stranger = Address::generate(&env);
+    client.discard_unit(&unit_id, &stranger, &DiscardReason::Damaged, &None);
+}
+
+// ==================== Donor Recall Tests ====================
+
+#[test]
+fn test_recall_donor_units_quarantines_and_reports_lookback() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+
+    let current_time = 1000u64;
+    env.ledger().set_timestamp(current_time);
+    let expiration = current_time + (30 * 86400);
+    let donor = Address::generate(&env);
+    let other_donor = Address::generate(&env);
+    let donor_id = Some(donor.clone());
+
+    // Unscreened, available, reserved, delivered, transfused and discarded units
+    let pending = client.register_blood(
+        &admin,
+        &next_din(&env),
+        &product_code(&env),
+        &BloodType::APositive,
+        &BloodComponent::WholeBlood,
+        &450u32,
+        &expiration,
+        &donor_id,
+    );
+    let available = client.register_blood(
+        &admin,
+        &next_din(&env),
+        &product_code(&env),
+        &BloodType::APositive,
+        &BloodComponent::WholeBlood,
+        &450u32,
+        &expiration,
+        &donor_id,
+    );
+    let reserved = client.register_blood(
+        &admin,
+        &next_din(&env),
+        &product_code(&env),
+        &BloodType::APositive,
+        &BloodComponent::WholeBlood,
+        &450u32,
+        &expiration,
+        &donor_id,
+    );
+    let delivered = client.register_blood(
+        &admin,
+        &next_din(&env),
+        &product_code(&env),
+        &BloodType::APositive,
+        &BloodComponent::WholeBlood,
+        &450u32,
+        &expiration,
+        &donor_id,
+    );
+    let transfused = client.register_blood(
+        &admin,
+        &next_din(&env),
+        &product_code(&env),
+        &BloodType::APositive,
+        &BloodComponent::WholeBlood,
+        &450u32,
+        &expiration,
+        &donor_id,
+    );
+    let discarded = client.register_blood(
+        &admin,
+        &next_din(&env),
+        &product_code(&env),
+        &BloodType::APositive,
+        &BloodComponent::WholeBlood,
+        &450u32,
+        &expiration,
+        &donor_id,
+    );
+    let unrelated = client.register_blood(
+        &admin,
+        &next_din(&env),
+        &product_code(&env),
+        &BloodType::APositive,
+        &BloodComponent::WholeBlood,
+        &450u32,
+        &expiration,
+        &Some(other_donor),
+    );
+
+    for id in [
+        available, reserved, delivered, transfused, discarded, unrelated,
+    ] {
+        clear_screening(&client, &admin, id);
+    }
+    client.update_status(&reserved, &BloodStatus::Reserved, &admin, &None);
+    for id in [delivered, transfused] {
+        client.update_status(&id, &BloodStatus::Reserved, &admin, &None);
+        client.update_status(&id, &BloodStatus::InTransit, &admin, &None);
+    }
+    env.ledger().set_timestamp(current_time + 500);
+    client.mark_delivered(&delivered, &admin, &String::from_str(&env, "Hospital A"));
+    client.mark_delivered(&transfused, &admin, &String::from_str(&env, "Hospital B"));
+    client.mark_transfused(&transfused, &admin);
+    client.discard_unit(&discarded, &admin, &DiscardReason::Damaged, &None);
+
+    env.ledger().set_timestamp(current_time + 1000);
+    let report = client.recall_donor_units(&donor, &RecallReason::DonorTestedPositive);
+
+    assert_eq!(report.donor_id, donor);
+    assert_eq!(report.recalled_at, current_time + 1000);
+    assert_eq!(
+        report.quarantined_units,
+        vec![&env, pending, available, reserved]
+    );
+    for id in [pending, available, reserved] {
+        assert_eq!(client.get_blood_unit(&id).status, BloodStatus::Quarantined);
+    }
+
+    assert_eq!(report.lookback.len(), 2);
+    let first = report.lookback.get(0).unwrap();
+    assert_eq!(first.blood_unit_id, delivered);
+    assert_eq!(first.status, BloodStatus::Delivered);
+    assert_eq!(
+        first.destination,
+        Some(String::from_str(&env, "Hospital A"))
+    );
+    assert_eq!(first.delivered_at, current_time + 500);
+    let second = report.lookback.get(1).unwrap();
+    assert_eq!(second.blood_unit_id, transfused);
+    assert_eq!(second.status, BloodStatus::Transfused);
+    assert_eq!(
+        second.destination,
+        Some(String::from_str(&env, "Hospital B"))
+    );
+
+    // Terminal and unrelated units are untouched
+    assert_eq!(
+        client.get_blood_unit(&discarded).status,
+        BloodStatus::Discarded
+    );
+    assert_eq!(
+        client.get_blood_unit(&unrelated).status,
+        BloodStatus::Available
+    );
+}
+
+#[test]
+fn test_recall_donor_units_emits_hospital_events() {
+    let (env, admin, client, contract_id) = create_test_contract();
+
+    let current_time = 1000u64;
+    env.ledger().set_timestamp(current_time);
+    let donor = Address::generate(&env);
+
+    let unit_id = client.register_blood(
+        &admin,
+        &next_din(&env),
+        &product_code(&env),
+        &BloodType::ONegative,
+        &BloodComponent::WholeBlood,
+        &450u32,
+        &(current_time + 30 * 86400),
+        &Some(donor.clone()),
+    );
+    clear_screening(&client, &admin, unit_id);
+    client.update_status(&unit_id, &BloodStatus::Reserved, &admin, &None);
+    client.update_status(&unit_id, &BloodStatus::InTransit, &admin, &None);
+    client.mark_delivered(&unit_id, &admin, &String::from_str(&env, "Hospital A"));
+
+    client.recall_donor_units(&donor, &RecallReason::PostDonationIllness);
+
+    let events = env.events().all();
+    assert_eq!(events.len(), 2);
+    let (_, unit_topics, _) = events.get(0).unwrap();
+    assert_eq!(
+        unit_topics,
+        (Symbol::new(&env, "unit_recalled"), unit_id).into_val(&env)
+    );
+    let (event_contract, summary_topics, _) = events.get(1).unwrap();
+    assert_eq!(event_contract, contract_id);
+    assert_eq!(
+        summary_topics,
+        (Symbol::new(&env, "donor_recall"),).into_val(&env)
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #43)")]
+fn test_recalled_unit_cannot_be_released_without_rescreening() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+
+    env.ledger().set_timestamp(1000);
+    let donor = Address::generate(&env);
+    let unit_id = client.register_blood(
+        &admin,
+        &next_din(&env),
+        &product_code(&env),
+        &BloodType::ONegative,
+        &BloodComponent::WholeBlood,
+        &450u32,
+        &(1000 + 30 * 86400),
+        &Some(donor.clone()),
+    );
+    clear_screening(&client, &admin, unit_id);
+
+    client.recall_donor_units(&donor, &RecallReason::DonorTestedPositive);
+    assert_eq!(client.get_screening_results(&unit_id).len(), 0);
+
+    client.update_status(&unit_id, &BloodStatus::Available, &admin, &None);
+}
+
+#[test]
+fn test_recalled_unit_released_after_rescreening() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+
+    env.ledger().set_timestamp(1000);
+    let donor = Address::generate(&env);
+    let unit_id = client.register_blood(
+        &admin,
+        &next_din(&env),
+        &product_code(&env),
+        &BloodType::ONegative,
+        &BloodComponent::WholeBlood,
+        &450u32,
+        &(1000 + 30 * 86400),
+        &Some(donor.clone()),
+    );
+    clear_screening(&client, &admin, unit_id);
+    client.recall_donor_units(&donor, &RecallReason::PostDonationIllness);
+
+    env.ledger().set_timestamp(2000);
+    clear_screening(&client, &admin, unit_id);
+
+    assert_eq!(
+        client.get_blood_unit(&unit_id).status,
+        BloodStatus::Available
+    );
+    for record in client.get_screening_results(&unit_id).iter() {
+        assert_eq!(record.tested_at, 2000);
+    }
+}
+
+#[test]
+fn test_recall_donor_without_units() {
+    let (env, _admin, client, _contract_id) = create_test_contract();
+
+    let donor = Address::generate(&env);
+    let report = client.recall_donor_units(&donor, &RecallReason::Other);
+
+    assert_eq!(report.quarantined_units.len(), 0);
+    assert_eq!(report.lookback.len(), 0);
+}
+
+// ==================== Metadata Tests ====================
+
+fn register_test_unit(env: &Env, client: &InventoryContractClient, bank: &Address) -> u64 {
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    client.register_blood(
+        bank,
+        &next_din(env),
+        &product_code(env),
+        &BloodType::APositive,
+        &BloodComponent::WholeBlood,
+        &450u32,
+        &(1000 + 30 * 86400),
+        &None,
+    )
+}
+
+#[test]
+fn test_set_unit_metadata_records_history_and_index() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+    let unit_id = register_test_unit(&env, &client, &admin);
+
+    let slot = Symbol::new(&env, "storage_slot");
+    client.allow_metadata_key(&slot);
+
+    let fridge_a = String::from_str(&env, "fridge-a/3");
+    let fridge_b = String::from_str(&env, "fridge-b/1");
+
+    client.set_unit_metadata(&unit_id, &admin, &slot, &fridge_a);
+    assert_eq!(
+        client.get_units_by_metadata(&slot, &fridge_a),
+        vec![&env, unit_id]
+    );
+
+    let unit = client.set_unit_metadata(&unit_id, &admin, &slot, &fridge_b);
+    assert_eq!(unit.metadata.get(slot.clone()), Some(fridge_b.clone()));
+    assert_eq!(client.get_units_by_metadata(&slot, &fridge_a).len(), 0);
+    assert_eq!(
+        client.get_units_by_metadata(&slot, &fridge_b),
+        vec![&env, unit_id]
+    );
+
+    let unit = client.remove_unit_metadata(&unit_id, &admin, &slot);
+    assert!(!unit.metadata.contains_key(slot.clone()));
+    assert_eq!(client.get_units_by_metadata(&slot, &fridge_b).len(), 0);
+
+    let history = client.get_metadata_history(&unit_id);
+    assert_eq!(history.len(), 3);
+    let first = history.get(0).unwrap();
+    assert_eq!(first.old_value, None);
+    assert_eq!(first.new_value, Some(fridge_a.clone()));
+    let second = history.get(1).unwrap();
+    assert_eq!(second.old_value, Some(fridge_a));
+    assert_eq!(second.new_value, Some(fridge_b.clone()));
+    let third = history.get(2).unwrap();
+    assert_eq!(third.old_value, Some(fridge_b));
+    assert_eq!(third.new_value, None);
+    assert_eq!(third.changed_by, admin);
+}
+
+#[test]
+fn test_set_unit_metadata_emits_event() {
+    let (env, admin, client, contract_id) = create_test_contract();
+    let unit_id = register_test_unit(&env, &client, &admin);
+
+    let irradiated = Symbol::new(&env, "irradiated");
+    client.allow_metadata_key(&irradiated);
+    client.set_unit_metadata(
+        &unit_id,
+        &admin,
+        &irradiated,
+        &String::from_str(&env, "true"),
+    );
+
+    let events = env.events().all();
+    assert_eq!(events.len(), 1);
+    let (event_contract, topics, _) = events.get(0).unwrap();
+    assert_eq!(event_contract, contract_id);
+    assert_eq!(
+        topics,
+        (Symbol::new(&env, "metadata_updated"),).into_val(&env)
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #50)")]
+fn test_set_unit_metadata_key_not_allowed() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+    let unit_id = register_test_unit(&env, &client, &admin);
+
+    client.set_unit_metadata(
+        &unit_id,
+        &admin,
+        &Symbol::new(&env, "cmv_neg"),
+        &String::from_str(&env, "true"),
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #50)")]
+fn test_disallow_metadata_key() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+    let unit_id = register_test_unit(&env, &client, &admin);
+
+    let cmv_neg = Symbol::new(&env, "cmv_neg");
+    client.allow_metadata_key(&cmv_neg);
+    client.allow_metadata_key(&cmv_neg);
+    assert_eq!(
+        client.get_allowed_metadata_keys(),
+        vec![&env, cmv_neg.clone()]
+    );
+
+    client.disallow_metadata_key(&cmv_neg);
+    assert_eq!(client.get_allowed_metadata_keys().len(), 0);
+
+    client.set_unit_metadata(&unit_id, &admin, &cmv_neg, &String::from_str(&env, "true"));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #51)")]
+fn test_set_unit_metadata_value_too_long() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+    let unit_id = register_test_unit(&env, &client, &admin);
+
+    let slot = Symbol::new(&env, "storage_slot");
+    client.allow_metadata_key(&slot);
+
+    let long_value = String::from_bytes(&env, &[b'x'; 65]);
+    client.set_unit_metadata(&unit_id, &admin, &slot, &long_value);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #52)")]
+fn test_set_unit_metadata_entry_limit() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+    let unit_id = register_test_unit(&env, &client, &admin);
+
+    let keys = [
+        "k0", "k1", "k2", "k3", "k4", "k5", "k6", "k7", "k8", "k9", "k10",
+    ];
+    let value = String::from_str(&env, "v");
+    for key in keys.iter() {
+        client.allow_metadata_key(&Symbol::new(&env, key));
+    }
+    for key in keys.iter().take(storage::MAX_METADATA_ENTRIES as usize) {
+        client.set_unit_metadata(&unit_id, &admin, &Symbol::new(&env, key), &value);
+    }
+
+    // Overwriting an existing key is still allowed at the limit
+    client.set_unit_metadata(
+        &unit_id,
+        &admin,
+        &Symbol::new(&env, "k0"),
+        &String::from_str(&env, "w"),
+    );
+
+    client.set_unit_metadata(&unit_id, &admin, &Symbol::new(&env, "k10"), &value);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2)")]
+fn test_set_unit_metadata_unauthorized() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+    let unit_id = register_test_unit(&env, &client, &admin);
+
+    let slot = Symbol::new(&env, "storage_slot");
+    client.allow_metadata_key(&slot);
+
+    let other_bank = Address::generate(&env);
+    client.set_unit_metadata(&unit_id, &other_bank, &slot, &String::from_str(&env, "x"));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #21)")]
+fn test_remove_unit_metadata_missing_key() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+    let unit_id = register_test_unit(&env, &client, &admin);
+
+    client.remove_unit_metadata(&unit_id, &admin, &Symbol::new(&env, "cmv_neg"));
+}
+
+// ==================== Bank Transfer Tests ====================
+
+/// Register a unit at `admin` and clear screening so it is Available
+fn register_available_unit(env: &Env, client: &InventoryContractClient, admin: &Address) -> u64 {
+    let unit_id = register_test_unit(env, client, admin);
+    clear_screening(client, admin, unit_id);
+    unit_id
+}
+
+#[test]
+fn test_authorize_bank() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+
+    let bank = Address::generate(&env);
+    assert!(client.is_bank_authorized(&admin));
+    assert!(!client.is_bank_authorized(&bank));
+
+    client.authorize_bank(&bank);
+    assert!(client.is_bank_authorized(&bank));
+    register_test_unit(&env, &client, &bank);
+
+    client.revoke_bank(&bank);
+    assert!(!client.is_bank_authorized(&bank));
+}
+
+#[test]
+fn test_bank_transfer_moves_custody() {
+    let (env, admin, client, contract_id) = create_test_contract();
+
+    let bank_b = Address::generate(&env);
+    client.authorize_bank(&bank_b);
+    let unit_id = register_available_unit(&env, &client, &admin);
+
+    env.ledger().with_mut(|li| li.timestamp = 2000);
+    let unit = client.initiate_bank_transfer(&unit_id, &admin, &bank_b);
+    assert_eq!(unit.status, BloodStatus::InTransit);
+    assert_eq!(unit.bank_id, admin);
+
+    let events = env.events().all();
+    let (event_contract, topics, _) = events.get(events.len() - 1).unwrap();
+    assert_eq!(event_contract, contract_id);
+    assert_eq!(
+        topics,
+        (Symbol::new(&env, "transfer_initiated"), unit_id).into_val(&env)
+    );
+
+    let transfer = BankTransfer {
+        from_bank: admin.clone(),
+        to_bank: bank_b.clone(),
+        initiated_at: 2000,
+    };
+    assert_eq!(
+        client.get_pending_transfer(&unit_id),
+        Some(transfer.clone())
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = 3000);
+    let unit = client.receive_bank_transfer(&unit_id, &bank_b);
+    assert_eq!(unit.status, BloodStatus::Available);
+    assert_eq!(unit.bank_id, bank_b);
+    assert_eq!(client.get_pending_transfer(&unit_id), None);
+
+    assert_eq!(client.get_units_by_bank(&admin).len(), 0);
+    assert_eq!(client.get_units_by_bank(&bank_b), vec![&env, unit_id]);
+
+    // Both legs record the two custodians
+    let history = client.get_status_history(&unit_id);
+    let sent = history.get(history.len() - 2).unwrap();
+    assert_eq!(sent.to_status, BloodStatus::InTransit);
+    assert_eq!(sent.authorized_by, admin);
+    assert_eq!(sent.detail, StatusDetail::BankTransfer(transfer.clone()));
+    let received = history.get(history.len() - 1).unwrap();
+    assert_eq!(received.to_status, BloodStatus::Available);
+    assert_eq!(received.authorized_by, bank_b);
+    assert_eq!(received.detail, StatusDetail::BankTransfer(transfer));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2)")]
+fn test_bank_transfer_not_holding_bank() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+
+    let bank_b = Address::generate(&env);
+    client.authorize_bank(&bank_b);
+    let unit_id = register_available_unit(&env, &client, &admin);
+
+    client.initiate_bank_transfer(&unit_id, &bank_b, &admin);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #32)")]
+fn test_bank_transfer_to_unauthorized_bank() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+
+    let unit_id = register_available_unit(&env, &client, &admin);
+    client.initiate_bank_transfer(&unit_id, &admin, &Address::generate(&env));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #40)")]
+fn test_bank_transfer_unit_not_available() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+
+    let bank_b = Address::generate(&env);
+    client.authorize_bank(&bank_b);
+    let unit_id = register_test_unit(&env, &client, &admin); // still quarantined
+
+    client.initiate_bank_transfer(&unit_id, &admin, &bank_b);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2)")]
+fn test_receive_bank_transfer_wrong_bank() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+
+    let bank_b = Address::generate(&env);
+    let bank_c = Address::generate(&env);
+    client.authorize_bank(&bank_b);
+    client.authorize_bank(&bank_c);
+    let unit_id = register_available_unit(&env, &client, &admin);
+
+    client.initiate_bank_transfer(&unit_id, &admin, &bank_b);
+    client.receive_bank_transfer(&unit_id, &bank_c);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #23)")]
+fn test_receive_bank_transfer_expired_in_transit() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+
+    let bank_b = Address::generate(&env);
+    client.authorize_bank(&bank_b);
+    let unit_id = register_available_unit(&env, &client, &admin);
+
+    client.initiate_bank_transfer(&unit_id, &admin, &bank_b);
+    env.ledger().with_mut(|li| li.timestamp = 1000 + 30 * 86400);
+    client.receive_bank_transfer(&unit_id, &bank_b);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #45)")]
+fn test_transfer_unit_not_returned_to_stock_by_update_status() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+
+    let bank_b = Address::generate(&env);
+    client.authorize_bank(&bank_b);
+    let unit_id = register_available_unit(&env, &client, &admin);
+
+    client.initiate_bank_transfer(&unit_id, &admin, &bank_b);
+    client.update_status(&unit_id, &BloodStatus::Available, &admin, &None);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #41)")]
+fn test_dispatched_unit_not_returned_to_stock() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+
+    let unit_id = register_available_unit(&env, &client, &admin);
+    client.update_status(&unit_id, &BloodStatus::Reserved, &admin, &None);
+    client.update_status(&unit_id, &BloodStatus::InTransit, &admin, &None);
+    client.update_status(&unit_id, &BloodStatus::Available, &admin, &None);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #45)")]
+fn test_bank_transfer_blocks_delivery() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+
+    let bank_b = Address::generate(&env);
+    client.authorize_bank(&bank_b);
+    let unit_id = register_available_unit(&env, &client, &admin);
+
+    client.initiate_bank_transfer(&unit_id, &admin, &bank_b);
+    client.mark_delivered(&unit_id, &admin, &String::from_str(&env, "Hospital A"));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #46)")]
+fn test_quarantine_cancels_bank_transfer() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+
+    let bank_b = Address::generate(&env);
+    client.authorize_bank(&bank_b);
+    let unit_id = register_available_unit(&env, &client, &admin);
+
+    client.initiate_bank_transfer(&unit_id, &admin, &bank_b);
+    client.update_status(&unit_id, &BloodStatus::Quarantined, &admin, &None);
+    assert_eq!(client.get_pending_transfer(&unit_id), None);
+
+    client.receive_bank_transfer(&unit_id, &bank_b);
+}
+
+// ==================== Stock Threshold Tests ====================
+
+/// Check whether the last invocation published an event with `topics`
+fn has_event(env: &Env, topics: &soroban_sdk::Vec<soroban_sdk::Val>) -> bool {
+    env.events().all().iter().any(|(_, t, _)| &t == topics)
+}
+
+#[test]
+fn test_stock_summary_tracks_status_changes() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+
+    let unit_id = register_test_unit(&env, &client, &admin);
+    client.register_blood(
+        &admin,
+        &next_din(&env),
+        &product_code(&env),
+        &BloodType::APositive,
+        &BloodComponent::Plasma,
+        &250u32,
+        &(1000 + 30 * 86400),
+        &None,
+    );
+
+    let summary = client.get_stock_summary(&admin);
+    assert_eq!(summary.len(), 2);
+    let whole = summary.get(0).unwrap();
+    assert_eq!(whole.component, BloodComponent::WholeBlood);
+    assert_eq!(whole.quarantined_units, 1);
+    assert_eq!(whole.quarantined_ml, 450);
+    assert_eq!(whole.available_ml, 0);
+    let plasma = summary.get(1).unwrap();
+    assert_eq!(plasma.component, BloodComponent::Plasma);
+    assert_eq!(plasma.quarantined_ml, 250);
+
+    clear_screening(&client, &admin, unit_id);
+    client.update_status(&unit_id, &BloodStatus::Reserved, &admin, &None);
+
+    let whole = client.get_stock_summary(&admin).get(0).unwrap();
+    assert_eq!(whole.quarantined_units, 0);
+    assert_eq!(whole.available_units, 0);
+    assert_eq!(whole.reserved_units, 1);
+    assert_eq!(whole.reserved_ml, 450);
+
+    client.update_status(&unit_id, &BloodStatus::InTransit, &admin, &None);
+    let whole = client.get_stock_summary(&admin).get(0).unwrap();
+    assert_eq!(whole.reserved_units, 0);
+    assert_eq!(whole.reserved_ml, 0);
+}
+
+#[test]
+fn test_stock_threshold_alerts() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+
+    let unit_id = register_available_unit(&env, &client, &admin);
+
+    let low_stock = (Symbol::new(&env, "low_stock"), admin.clone()).into_val(&env);
+    let restocked = (Symbol::new(&env, "restocked"), admin.clone()).into_val(&env);
+
+    // 450ml available meets a 400ml threshold
+    client.set_stock_threshold(
+        &admin,
+        &BloodType::APositive,
+        &BloodComponent::WholeBlood,
+        &400u64,
+    );
+    assert!(!has_event(&env, &low_stock));
+
+    client.update_status(&unit_id, &BloodStatus::Reserved, &admin, &None);
+    assert!(has_event(&env, &low_stock));
+
+    let level = client.get_stock_summary(&admin).get(0).unwrap();
+    assert!(level.is_low());
+    assert_eq!(level.min_available_ml, 400);
+
+    client.update_status(&unit_id, &BloodStatus::Available, &admin, &None);
+    assert!(has_event(&env, &restocked));
+    assert!(!client.get_stock_summary(&admin).get(0).unwrap().is_low());
+}
+
+#[test]
+fn test_marking_expired_unit_lowers_stock() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+
+    let unit_id = register_available_unit(&env, &client, &admin);
+    client.set_stock_threshold(
+        &admin,
+        &BloodType::APositive,
+        &BloodComponent::WholeBlood,
+        &400u64,
+    );
+
+    // Past its shelf life the unit still counts until it is marked
+    env.ledger().with_mut(|li| li.timestamp = 1000 + 30 * 86400);
+    let level = client.get_stock_summary(&admin).get(0).unwrap();
+    assert_eq!(level.available_ml, 450);
+
+    let updated = client.mark_expired(&unit_id, &admin);
+    assert_eq!(updated.status, BloodStatus::Expired);
+
+    let low_stock = (Symbol::new(&env, "low_stock"), admin.clone()).into_val(&env);
+    assert!(has_event(&env, &low_stock));
+    let level = client.get_stock_summary(&admin).get(0).unwrap();
+    assert_eq!(level.available_units, 0);
+    assert_eq!(level.available_ml, 0);
+    assert!(level.is_low());
+}
+
+#[test]
+fn test_stock_threshold_set_below_current_level() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+
+    register_test_unit(&env, &client, &admin); // quarantined, nothing available
+
+    client.set_stock_threshold(
+        &admin,
+        &BloodType::ONegative,
+        &BloodComponent::RedCells,
+        &900u64,
+    );
+
+    let low_stock = (Symbol::new(&env, "low_stock"), admin.clone()).into_val(&env);
+    assert!(has_event(&env, &low_stock));
+
+    let summary = client.get_stock_summary(&admin);
+    assert_eq!(summary.len(), 2);
+    let red_cells = summary.get(1).unwrap();
+    assert_eq!(red_cells.blood_type, BloodType::ONegative);
+    assert_eq!(red_cells.available_units, 0);
+}
+
+#[test]
+fn test_stock_follows_bank_transfer() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+
+    let bank_b = Address::generate(&env);
+    client.authorize_bank(&bank_b);
+    let unit_id = register_available_unit(&env, &client, &admin);
+
+    client.initiate_bank_transfer(&unit_id, &admin, &bank_b);
+    assert_eq!(
+        client
+            .get_stock_summary(&admin)
+            .get(0)
+            .unwrap()
+            .available_ml,
+        0
+    );
+    assert_eq!(client.get_stock_summary(&bank_b).len(), 0);
+
+    client.receive_bank_transfer(&unit_id, &bank_b);
+    let level = client.get_stock_summary(&bank_b).get(0).unwrap();
+    assert_eq!(level.available_units, 1);
+    assert_eq!(level.available_ml, 450);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #32)")]
+fn test_stock_threshold_unauthorized_bank() {
+    let (env, _admin, client, _contract_id) = create_test_contract();
+
+    client.set_stock_threshold(
+        &Address::generate(&env),
+        &BloodType::APositive,
+        &BloodComponent::WholeBlood,
+        &400u64,
+    );
+}
+
+// ==================== Reservation Tests ====================
+
+#[test]
+fn test_reserve_unit_records_holder_and_expiry() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+
+    let hospital = Address::generate(&env);
+    let unit_id = register_available_unit(&env, &client, &admin);
+
+    let reservation = client.reserve_unit(&unit_id, &admin, &hospital, &Some(7u64), &3600u64);
+    assert_eq!(reservation.holder, hospital);
+    assert_eq!(reservation.request_id, Some(7));
+    assert_eq!(reservation.reserved_at, 1000);
+    assert_eq!(reservation.expires_at, 4600);
+
+    assert_eq!(
+        client.get_blood_unit(&unit_id).status,
+        BloodStatus::Reserved
+    );
+    assert_eq!(client.get_reservation(&unit_id), Some(reservation));
+
+    // Dispatch ends the reservation
+    client.update_status(&unit_id, &BloodStatus::InTransit, &admin, &None);
+    assert_eq!(client.get_reservation(&unit_id), None);
+}
+
+#[test]
+fn test_update_status_reserved_uses_default_expiry() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+
+    let unit_id = register_available_unit(&env, &client, &admin);
+    client.update_status(&unit_id, &BloodStatus::Reserved, &admin, &None);
+
+    let reservation = client.get_reservation(&unit_id).unwrap();
+    assert_eq!(reservation.holder, admin);
+    assert_eq!(
+        reservation.expires_at,
+        1000 + storage::DEFAULT_RESERVATION_SECONDS
+    );
+}
+
+#[test]
+fn test_release_expired_reservation() {
+    let (env, admin, client, contract_id) = create_test_contract();
+
+    let hospital = Address::generate(&env);
+    let unit_id = register_available_unit(&env, &client, &admin);
+    client.reserve_unit(&unit_id, &admin, &hospital, &None, &3600u64);
+
+    env.ledger().with_mut(|li| li.timestamp = 4600);
+    let unit = client.release_expired_reservation(&unit_id);
+    assert_eq!(unit.status, BloodStatus::Available);
+
+    let events = env.events().all();
+    let (_, topics, _) = events.get(events.len() - 1).unwrap();
+    assert_eq!(
+        topics,
+        (Symbol::new(&env, "reservation_released"), unit_id).into_val(&env)
+    );
+    assert_eq!(client.get_reservation(&unit_id), None);
+
+    let history = client.get_status_history(&unit_id);
+    let release = history.get(history.len() - 1).unwrap();
+    assert_eq!(release.from_status, BloodStatus::Reserved);
+    assert_eq!(release.authorized_by, contract_id);
+    assert_eq!(
+        release.reason,
+        Some(String::from_str(&env, "Reservation expired"))
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #48)")]
+fn test_release_active_reservation() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+
+    let unit_id = register_available_unit(&env, &client, &admin);
+    client.reserve_unit(&unit_id, &admin, &Address::generate(&env), &None, &3600u64);
+
+    env.ledger().with_mut(|li| li.timestamp = 4599);
+    client.release_expired_reservation(&unit_id);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #49)")]
+fn test_release_unreserved_unit() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+
+    let unit_id = register_available_unit(&env, &client, &admin);
+    client.release_expired_reservation(&unit_id);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #47)")]
+fn test_dispatch_lapsed_reservation() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+
+    let unit_id = register_available_unit(&env, &client, &admin);
+    client.reserve_unit(&unit_id, &admin, &Address::generate(&env), &None, &3600u64);
+
+    env.ledger().with_mut(|li| li.timestamp = 5000);
+    client.update_status(&unit_id, &BloodStatus::InTransit, &admin, &None);
+}
+
+#[test]
+fn test_reserve_unit_over_lapsed_reservation() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+
+    let first = Address::generate(&env);
+    let second = Address::generate(&env);
+    let unit_id = register_available_unit(&env, &client, &admin);
+    client.reserve_unit(&unit_id, &admin, &first, &None, &3600u64);
+
+    env.ledger().with_mut(|li| li.timestamp = 5000);
+    let reservation = client.reserve_unit(&unit_id, &admin, &second, &None, &3600u64);
+    assert_eq!(reservation.holder, second);
+    assert_eq!(reservation.expires_at, 8600);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #40)")]
+fn test_reserve_unit_active_reservation() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+
+    let unit_id = register_available_unit(&env, &client, &admin);
+    client.reserve_unit(&unit_id, &admin, &Address::generate(&env), &None, &3600u64);
+    client.reserve_unit(&unit_id, &admin, &Address::generate(&env), &None, &3600u64);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #12)")]
+fn test_reserve_unit_duration_too_long() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+
+    let unit_id = register_available_unit(&env, &client, &admin);
+    client.reserve_unit(
+        &unit_id,
+        &admin,
+        &Address::generate(&env),
+        &None,
+        &(storage::MAX_RESERVATION_SECONDS + 1),
+    );
+}
+
+#[test]
+fn test_cancel_reservation_returns_unit_to_stock() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+
+    let unit_id = register_available_unit(&env, &client, &admin);
+    client.reserve_unit(&unit_id, &admin, &Address::generate(&env), &None, &3600u64);
+
+    let unit = client.cancel_reservation(&unit_id, &admin);
+    assert_eq!(unit.status, BloodStatus::Available);
+    assert_eq!(client.get_reservation(&unit_id), None);
+
+    let history = client.get_status_history(&unit_id);
+    let release = history.get(history.len() - 1).unwrap();
+    assert_eq!(release.authorized_by, admin);
+    assert_eq!(
+        release.reason,
+        Some(String::from_str(&env, "Reservation cancelled"))
+    );
+}
+
+#[test]
+fn test_request_contract_manages_reservations() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+
+    let requests = Address::generate(&env);
+    client.set_request_contract(&requests);
+    assert_eq!(client.get_request_contract(), Some(requests.clone()));
+
+    let unit_id = register_available_unit(&env, &client, &admin);
+    let hospital = Address::generate(&env);
+    client.reserve_unit(&unit_id, &requests, &hospital, &Some(3u64), &3600u64);
+    assert_eq!(
+        client.get_reservation(&unit_id).unwrap().request_id,
+        Some(3)
+    );
+
+    client.cancel_reservation(&unit_id, &requests);
+    assert_eq!(
+        client.get_blood_unit(&unit_id).status,
+        BloodStatus::Available
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2)")]
+fn test_cancel_reservation_unauthorized() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+
+    let unit_id = register_available_unit(&env, &client, &admin);
+    client.reserve_unit(&unit_id, &admin, &Address::generate(&env), &None, &3600u64);
+
+    client.cancel_reservation(&unit_id, &Address::generate(&env));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #49)")]
+fn test_cancel_reservation_unreserved_unit() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+
+    let unit_id = register_available_unit(&env, &client, &admin);
+    client.cancel_reservation(&unit_id, &admin);
+}
+
+#[test]
+fn test_request_contract_dispatches_reserved_unit() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+
+    let requests = Address::generate(&env);
+    client.set_request_contract(&requests);
+
+    let unit_id = register_available_unit(&env, &client, &admin);
+    client.reserve_unit(
+        &unit_id,
+        &requests,
+        &Address::generate(&env),
+        &Some(3u64),
+        &3600u64,
+    );
+
+    let unit = client.dispatch_unit(&unit_id, &requests);
+    assert_eq!(unit.status, BloodStatus::InTransit);
+    assert_eq!(client.get_reservation(&unit_id), None);
+
+    // Nothing left to lapse
+    env.ledger().with_mut(|li| li.timestamp = 5000);
+    let result = client.try_release_expired_reservation(&unit_id);
+    assert_eq!(result.err(), Some(Ok(ContractError::UnitNotReserved)));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2)")]
+fn test_dispatch_unit_unauthorized() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+
+    let unit_id = register_available_unit(&env, &client, &admin);
+    client.reserve_unit(&unit_id, &admin, &Address::generate(&env), &None, &3600u64);
+
+    client.dispatch_unit(&unit_id, &Address::generate(&env));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #49)")]
+fn test_dispatch_unit_unreserved() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+
+    let unit_id = register_available_unit(&env, &client, &admin);
+    client.dispatch_unit(&unit_id, &admin);
+}
+
+// ==================== ISBT 128 Tests ====================
+
+fn register_with_label(
+    env: &Env,
+    client: &InventoryContractClient,
+    bank: &Address,
+    din: &String,
+    product_code: &String,
+) -> u64 {
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    client.register_blood(
+        bank,
+        din,
+        product_code,
+        &BloodType::APositive,
+        &BloodComponent::WholeBlood,
+        &450u32,
+        &(1000 + 30 * 86400),
+        &None,
+    )
+}
+
+#[test]
+fn test_isbt_check_character() {
+    assert_eq!(validation::isbt_check_character(b"W000026000001"), b'F');
+    assert_eq!(validation::isbt_check_character(b"G123417654321"), b'A');
+}
+
+#[test]
+fn test_get_unit_by_din() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+
+    let din = String::from_str(&env, "W000026000001F");
+    let unit_id = register_with_label(&env, &client, &admin, &din, &product_code(&env));
+
+    let unit = client.get_unit_by_din(&din);
+    assert_eq!(unit.id, unit_id);
+    assert_eq!(unit.din, din);
+    assert_eq!(unit.product_code, product_code(&env));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #21)")]
+fn test_get_unit_by_unknown_din() {
+    let (env, _admin, client, _contract_id) = create_test_contract();
+
+    client.get_unit_by_din(&String::from_str(&env, "W000026000001F"));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #24)")]
+fn test_register_blood_duplicate_din() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+
+    let din = String::from_str(&env, "W000026000001F");
+    register_with_label(&env, &client, &admin, &din, &product_code(&env));
+    register_with_label(&env, &client, &admin, &din, &product_code(&env));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #18)")]
+fn test_register_blood_bad_din_check_character() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+
+    let din = String::from_str(&env, "W000026000001G");
+    register_with_label(&env, &client, &admin, &din, &product_code(&env));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #18)")]
+fn test_register_blood_bad_din_facility() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+
+    // 'O' is not a valid first facility character
+    let mut bytes = *b"O000026000001?";
+    bytes[13] = validation::isbt_check_character(&bytes[..13]);
+    let din = String::from_bytes(&env, &bytes);
+    register_with_label(&env, &client, &admin, &din, &product_code(&env));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #18)")]
+fn test_register_blood_din_without_check_character() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+
+    let din = String::from_str(&env, "W000026000001");
+    register_with_label(&env, &client, &admin, &din, &product_code(&env));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #19)")]
+fn test_register_blood_bad_product_code() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+
+    register_with_label(
+        &env,
+        &client,
+        &admin,
+        &next_din(&env),
+        &String::from_str(&env, "00001V00"),
+    );
+}
+
+// ==================== Batch Registration Tests ====================
+
+fn new_unit(env: &Env, blood_type: BloodType, donor_id: Option<Address>) -> NewUnit {
+    NewUnit {
+        din: next_din(env),
+        product_code: product_code(env),
+        blood_type,
+        component: BloodComponent::WholeBlood,
+        quantity_ml: 450,
+        expiration_timestamp: 1000 + 30 * 86400,
+        donor_id,
+    }
+}
+
+#[test]
+fn test_register_blood_batch_success() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+
+    let first = register_test_unit(&env, &client, &admin);
+    let donor = Address::generate(&env);
+    let units = vec![
+        &env,
+        new_unit(&env, BloodType::APositive, Some(donor.clone())),
+        new_unit(&env, BloodType::ONegative, None),
+        new_unit(&env, BloodType::APositive, Some(donor.clone())),
+    ];
+
+    let ids = client.register_blood_batch(&admin, &units);
+    assert_eq!(ids, vec![&env, first + 1, first + 2, first + 3]);
+
+    // One event per unit, one for the donor's collection and the batch summary
+    let events = env.events().all();
+    assert_eq!(events.len(), 5);
+    let (_, topics, _) = events.get(4).unwrap();
+    assert_eq!(
+        topics,
+        (Symbol::new(&env, "blood_batch_registered"),).into_val(&env)
+    );
+
+    let unit = client.get_blood_unit(&(first + 2));
+    assert_eq!(unit.blood_type, BloodType::ONegative);
+    assert_eq!(unit.status, BloodStatus::Quarantined);
+    assert_eq!(client.get_unit_by_din(&unit.din).id, first + 2);
+
+    assert_eq!(
+        client.get_units_by_bank(&admin),
+        vec![&env, first, first + 1, first + 2, first + 3]
+    );
+
+    let stock = client.get_stock_summary(&admin).get(0).unwrap();
+    assert_eq!(stock.quarantined_units, 3);
+    assert_eq!(stock.quarantined_ml, 1350);
+
+    // Donor index covers the batch
+    let report = client.recall_donor_units(&donor, &RecallReason::Other);
+    assert_eq!(report.quarantined_units, vec![&env, first + 1, first + 3]);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #12)")]
+fn test_register_blood_batch_empty() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+
+    client.register_blood_batch(&admin, &soroban_sdk::Vec::new(&env));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #24)")]
+fn test_register_blood_batch_repeated_din() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+
+    let unit = new_unit(&env, BloodType::APositive, None);
+    client.register_blood_batch(&admin, &vec![&env, unit.clone(), unit]);
+}
+
+#[test]
+fn test_register_blood_batch_invalid_entry_writes_nothing() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+
+    let mut bad = new_unit(&env, BloodType::BPositive, None);
+    bad.quantity_ml = 50;
+    let units = vec![&env, new_unit(&env, BloodType::APositive, None), bad];
+
+    let result = client.try_register_blood_batch(&admin, &units);
+    assert!(result.is_err());
+    assert_eq!(client.get_units_by_bank(&admin).len(), 0);
+    assert_eq!(client.get_stock_summary(&admin).len(), 0);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #32)")]
+fn test_register_blood_batch_unauthorized_bank() {
+    let (env, _admin, client, _contract_id) = create_test_contract();
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+
+    let units = vec![&env, new_unit(&env, BloodType::APositive, None)];
+    client.register_blood_batch(&Address::generate(&env), &units);
+}
+
+// ==================== Partial Batch Update Tests ====================
+
+#[test]
+fn test_batch_update_status_partial_mixed_results() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+
+    let ready = register_available_unit(&env, &client, &admin);
+    let quarantined = register_test_unit(&env, &client, &admin);
+    let short_lived = client.register_blood(
+        &admin,
+        &next_din(&env),
+        &product_code(&env),
+        &BloodType::APositive,
+        &BloodComponent::Platelets,
+        &300u32,
+        &(1000 + 2 * 86400),
+        &None,
+    );
+    clear_screening(&client, &admin, short_lived);
+
+    env.ledger().with_mut(|li| li.timestamp = 1000 + 3 * 86400);
+
+    let update = BatchStatusUpdate {
+        blood_unit_ids: vec![&env, ready, quarantined, short_lived, 999],
+        new_status: BloodStatus::Reserved,
+        reason: Some(String::from_str(&env, "Nightly reconciliation")),
+    };
+    let results = client.batch_update_status_partial(&update, &admin);
+
+    assert_eq!(results.len(), 4);
+    assert_eq!(results.get(0).unwrap().blood_unit_id, ready);
+    assert_eq!(results.get(0).unwrap().outcome, BatchUpdateOutcome::Updated);
+    assert_eq!(
+        results.get(1).unwrap().outcome,
+        BatchUpdateOutcome::Skipped(ContractError::InvalidStatusTransition as u32)
+    );
+    assert_eq!(
+        results.get(2).unwrap().outcome,
+        BatchUpdateOutcome::Skipped(ContractError::BloodUnitExpired as u32)
+    );
+    assert_eq!(
+        results.get(3).unwrap().outcome,
+        BatchUpdateOutcome::NotFound
+    );
+
+    assert_eq!(client.get_blood_unit(&ready).status, BloodStatus::Reserved);
+    assert_eq!(
+        client.get_blood_unit(&quarantined).status,
+        BloodStatus::Quarantined
+    );
+    assert_eq!(
+        client.get_blood_unit(&short_lived).status,
+        BloodStatus::Available
+    );
+}
+
+#[test]
+fn test_batch_update_status_partial_discard_skipped() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+
+    let unit_id = register_available_unit(&env, &client, &admin);
+    let update = BatchStatusUpdate {
+        blood_unit_ids: vec![&env, unit_id],
+        new_status: BloodStatus::Discarded,
+        reason: None,
+    };
+
+    let results = client.batch_update_status_partial(&update, &admin);
+    assert_eq!(
+        results.get(0).unwrap().outcome,
+        BatchUpdateOutcome::Skipped(ContractError::DiscardReasonRequired as u32)
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2)")]
+fn test_batch_update_status_partial_unauthorized() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+
+    let unit_id = register_available_unit(&env, &client, &admin);
+    let update = BatchStatusUpdate {
+        blood_unit_ids: vec![&env, unit_id],
+        new_status: BloodStatus::Reserved,
+        reason: None,
+    };
+
+    client.batch_update_status_partial(&update, &Address::generate(&env));
+}
+
+// ==================== Role Permission Tests ====================
+
+#[test]
+fn test_roles_drive_delivery_chain() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+
+    let bank = Address::generate(&env);
+    let courier = Address::generate(&env);
+    let hospital = Address::generate(&env);
+    client.authorize_bank(&bank);
+    client.grant_role(&courier, &Role::Courier);
+    client.grant_role(&hospital, &Role::HospitalReceiver);
+    assert_eq!(client.get_role(&courier), Some(Role::Courier));
+    assert_eq!(client.get_role(&admin), Some(Role::Admin));
+
+    let unit_id = register_test_unit(&env, &client, &bank);
+    clear_screening(&client, &admin, unit_id);
+    client.update_status(&unit_id, &BloodStatus::Reserved, &bank, &None);
+    let dock = String::from_str(&env, "Loading dock");
+    client.initiate_handoff(&unit_id, &bank, &courier, &dock);
+    client.accept_handoff(&unit_id, &courier);
+    client.update_status(&unit_id, &BloodStatus::InTransit, &courier, &None);
+    let ward = String::from_str(&env, "Ward 3");
+    client.initiate_handoff(&unit_id, &courier, &hospital, &ward);
+    client.accept_handoff(&unit_id, &hospital);
+    client.mark_delivered(&unit_id, &hospital, &ward);
+    client.mark_transfused(&unit_id, &hospital);
+
+    let history = client.get_status_history(&unit_id);
+    let len = history.len();
+    assert_eq!(history.get(len - 4).unwrap().role, Role::BankOperator);
+    assert_eq!(history.get(len - 3).unwrap().role, Role::Courier);
+    assert_eq!(history.get(len - 2).unwrap().role, Role::HospitalReceiver);
+    let transfused = history.get(len - 1).unwrap();
+    assert_eq!(transfused.to_status, BloodStatus::Transfused);
+    assert_eq!(transfused.role, Role::HospitalReceiver);
+    assert_eq!(transfused.authorized_by, hospital);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #31)")]
+fn test_courier_cannot_reserve() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+
+    let courier = Address::generate(&env);
+    client.grant_role(&courier, &Role::Courier);
+    let unit_id = register_available_unit(&env, &client, &admin);
+
+    client.update_status(&unit_id, &BloodStatus::Reserved, &courier, &None);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #31)")]
+fn test_hospital_cannot_mark_expired() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+
+    let hospital = Address::generate(&env);
+    client.grant_role(&hospital, &Role::HospitalReceiver);
+    let unit_id = register_available_unit(&env, &client, &admin);
+
+    client.mark_expired(&unit_id, &hospital);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2)")]
+fn test_bank_operator_cannot_move_other_bank_units() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+
+    let other_bank = Address::generate(&env);
+    client.authorize_bank(&other_bank);
+    let unit_id = register_available_unit(&env, &client, &admin);
+
+    client.update_status(&unit_id, &BloodStatus::Reserved, &other_bank, &None);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #62)")]
+fn test_courier_cannot_dispatch_unit_it_does_not_hold() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+
+    let courier = Address::generate(&env);
+    client.grant_role(&courier, &Role::Courier);
+    let unit_id = register_available_unit(&env, &client, &admin);
+    client.update_status(&unit_id, &BloodStatus::Reserved, &admin, &None);
+
+    client.update_status(&unit_id, &BloodStatus::InTransit, &courier, &None);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #62)")]
+fn test_hospital_cannot_receive_unit_it_does_not_hold() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+
+    let hospital = Address::generate(&env);
+    client.grant_role(&hospital, &Role::HospitalReceiver);
+    let unit_id = register_available_unit(&env, &client, &admin);
+    client.update_status(&unit_id, &BloodStatus::Reserved, &admin, &None);
+    client.update_status(&unit_id, &BloodStatus::InTransit, &admin, &None);
+
+    client.mark_delivered(&unit_id, &hospital, &String::from_str(&env, "Ward 3"));
+}
+
+#[test]
+fn test_reservation_holder_courier_can_dispatch() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+
+    let courier = Address::generate(&env);
+    client.grant_role(&courier, &Role::Courier);
+    let unit_id = register_available_unit(&env, &client, &admin);
+    client.reserve_unit(&unit_id, &admin, &courier, &None, &3600u64);
+
+    let unit = client.update_status(&unit_id, &BloodStatus::InTransit, &courier, &None);
+    assert_eq!(unit.status, BloodStatus::InTransit);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #34)")]
+fn test_grant_role_does_not_replace_existing_role() {
+    let (env, _admin, client, _contract_id) = create_test_contract();
+
+    let account = Address::generate(&env);
+    client.authorize_bank(&account);
+
+    client.authorize_lab(&account);
+}
+
+#[test]
+fn test_regrant_same_role_and_replace_after_revoke() {
+    let (env, _admin, client, _contract_id) = create_test_contract();
+
+    let account = Address::generate(&env);
+    client.grant_role(&account, &Role::Courier);
+    client.grant_role(&account, &Role::Courier);
+    assert_eq!(client.get_role(&account), Some(Role::Courier));
+
+    client.revoke_role(&account);
+    client.grant_role(&account, &Role::HospitalReceiver);
+    assert_eq!(client.get_role(&account), Some(Role::HospitalReceiver));
+}
+
+#[test]
+fn test_lapsed_reservation_release_recorded_as_system() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+
+    let unit_id = register_available_unit(&env, &client, &admin);
+    client.reserve_unit(&unit_id, &admin, &Address::generate(&env), &None, &60u64);
+
+    env.ledger().with_mut(|li| li.timestamp = 2000);
+    client.release_expired_reservation(&unit_id);
+
+    let history = client.get_status_history(&unit_id);
+    assert_eq!(history.get(history.len() - 1).unwrap().role, Role::System);
+}
+
+#[test]
+fn test_revoke_role() {
+    let (env, _admin, client, _contract_id) = create_test_contract();
+
+    let lab = Address::generate(&env);
+    client.grant_role(&lab, &Role::Lab);
+    assert!(client.is_lab_authorized(&lab));
+
+    client.revoke_role(&lab);
+    assert_eq!(client.get_role(&lab), None);
+    assert!(!client.is_lab_authorized(&lab));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #12)")]
+fn test_grant_admin_role_rejected() {
+    let (env, _admin, client, _contract_id) = create_test_contract();
+
+    client.grant_role(&Address::generate(&env), &Role::Admin);
+}
+
+// ==================== Donor Deferral Tests ====================
+
+fn register_donation(
+    env: &Env,
+    client: &InventoryContractClient,
+    bank: &Address,
+    donor: &Address,
+    component: BloodComponent,
+) -> Result<u64, ContractError> {
+    let expiration = env.ledger().timestamp() + 30 * 86400;
+    client
+        .try_register_blood(
+            bank,
+            &next_din(env),
+            &product_code(env),
+            &BloodType::OPositive,
+            &component,
+            &450u32,
+            &expiration,
+            &Some(donor.clone()),
+        )
+        .map(|id| id.unwrap())
+        .map_err(|error| error.unwrap())
+}
+
+#[test]
+fn test_whole_blood_donation_interval() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+    env.ledger().set_timestamp(1000);
+    let donor = Address::generate(&env);
+
+    register_donation(&env, &client, &admin, &donor, BloodComponent::WholeBlood).unwrap();
+    assert_eq!(
+        client.get_donor_eligible_at(&donor),
+        Some(1000 + 56 * 86400)
+    );
+
+    env.ledger().set_timestamp(1000 + 30 * 86400);
+    assert_eq!(
+        register_donation(&env, &client, &admin, &donor, BloodComponent::WholeBlood),
+        Err(ContractError::DonationIntervalNotMet)
+    );
+    // Platelets after whole blood still wait out the whole blood interval
+    assert_eq!(
+        register_donation(&env, &client, &admin, &donor, BloodComponent::Platelets),
+        Err(ContractError::DonationIntervalNotMet)
+    );
+
+    env.ledger().set_timestamp(1000 + 56 * 86400);
+    assert!(register_donation(&env, &client, &admin, &donor, BloodComponent::WholeBlood).is_ok());
+}
+
+#[test]
+fn test_platelet_donation_interval_is_shorter() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+    env.ledger().set_timestamp(1000);
+    let donor = Address::generate(&env);
+
+    client.record_collection(&admin, &donor, &DonationType::Plateletpheresis);
+    register_donation(&env, &client, &admin, &donor, BloodComponent::Platelets).unwrap();
+
+    env.ledger().set_timestamp(1000 + 6 * 86400);
+    let result = client.try_record_collection(&admin, &donor, &DonationType::Plateletpheresis);
+    assert_eq!(result, Err(Ok(ContractError::DonationIntervalNotMet)));
+
+    env.ledger().set_timestamp(1000 + 7 * 86400);
+    client.record_collection(&admin, &donor, &DonationType::Plateletpheresis);
+    assert!(register_donation(&env, &client, &admin, &donor, BloodComponent::Platelets).is_ok());
+}
+
+#[test]
+fn test_double_red_cell_collection_interval() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+    env.ledger().set_timestamp(1000);
+    let donor = Address::generate(&env);
+
+    let collection = client.record_collection(&admin, &donor, &DonationType::DoubleRedCells);
+    assert_eq!(collection.collected_at, 1000);
+
+    let events = env.events().all();
+    assert_eq!(
+        events.last().unwrap().1,
+        (Symbol::new(&env, "collection_recorded"), donor.clone()).into_val(&env)
+    );
+
+    let unit_id =
+        register_donation(&env, &client, &admin, &donor, BloodComponent::RedCells).unwrap();
+    assert_eq!(client.get_unit_collection(&unit_id), Some(collection));
+    assert_eq!(
+        client.get_donor_eligible_at(&donor),
+        Some(1000 + 112 * 86400)
+    );
+}
+
+#[test]
+fn test_components_from_one_collection_register_together() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+    env.ledger().set_timestamp(1000);
+    let donor = Address::generate(&env);
+
+    for component in [
+        BloodComponent::RedCells,
+        BloodComponent::Plasma,
+        BloodComponent::Platelets,
+    ] {
+        assert!(register_donation(&env, &client, &admin, &donor, component).is_ok());
+    }
+
+    // A late component still joins the same whole blood collection
+    env.ledger().set_timestamp(1000 + 3600);
+    let cryo_id = register_donation(
+        &env,
+        &client,
+        &admin,
+        &donor,
+        BloodComponent::Cryoprecipitate,
+    )
+    .unwrap();
+    assert_eq!(client.get_blood_unit(&cryo_id).donation_timestamp, 1000);
+
+    let collection = client.get_unit_collection(&cryo_id).unwrap();
+    assert_eq!(collection.donation_type, DonationType::WholeBlood);
+    assert_eq!(collection.collected_at, 1000);
+
+    // The interval is the whole blood one, applied once for the collection
+    assert_eq!(
+        client.get_donor_eligible_at(&donor),
+        Some(1000 + 56 * 86400)
+    );
+}
+
+#[test]
+fn test_collection_closes_after_window() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+    env.ledger().set_timestamp(1000);
+    let donor = Address::generate(&env);
+
+    register_donation(&env, &client, &admin, &donor, BloodComponent::RedCells).unwrap();
+
+    // Past the window a unit opens a new collection and needs the interval
+    env.ledger().set_timestamp(1000 + 86400);
+    assert_eq!(
+        register_donation(&env, &client, &admin, &donor, BloodComponent::Plasma),
+        Err(ContractError::DonationIntervalNotMet)
+    );
+}
+
+#[test]
+fn test_anonymous_unit_has_no_collection() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+    let unit_id = register_test_unit(&env, &client, &admin);
+
+    assert_eq!(client.get_unit_collection(&unit_id), None);
+}
+
+#[test]
+fn test_batch_registration_checks_donation_interval() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+    env.ledger().set_timestamp(1000);
+    let donor = Address::generate(&env);
+
+    client.register_blood_batch(
+        &admin,
+        &vec![
+            &env,
+            new_unit(&env, BloodType::APositive, Some(donor.clone())),
+        ],
+    );
+
+    // Past the collection window the donor's unit opens a new collection
+    env.ledger().set_timestamp(1000 + 86400);
+    let result = client.try_register_blood_batch(
+        &admin,
+        &vec![
+            &env,
+            new_unit(&env, BloodType::APositive, None),
+            new_unit(&env, BloodType::APositive, Some(donor.clone())),
+        ],
+    );
+    assert_eq!(result, Err(Ok(ContractError::DonationIntervalNotMet)));
+}
+
+#[test]
+fn test_temporary_deferral_blocks_until_it_ends() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+    env.ledger().set_timestamp(1000);
+    let donor = Address::generate(&env);
+    let until = 1000 + 14 * 86400;
+
+    let deferral = client.defer_donor(&admin, &donor, &DeferralReason::LowHemoglobin, &Some(until));
+    assert_eq!(deferral.deferred_at, 1000);
+
+    let events = env.events().all();
+    assert_eq!(
+        events.last().unwrap().1,
+        (Symbol::new(&env, "donor_deferred"), donor.clone()).into_val(&env)
+    );
+    assert_eq!(client.get_donor_deferral(&donor), Some(deferral));
+    assert_eq!(client.get_donor_eligible_at(&donor), Some(until));
+
+    assert_eq!(
+        register_donation(&env, &client, &admin, &donor, BloodComponent::WholeBlood),
+        Err(ContractError::DonorDeferred)
+    );
+
+    env.ledger().set_timestamp(until);
+    assert!(register_donation(&env, &client, &admin, &donor, BloodComponent::WholeBlood).is_ok());
+}
+
+#[test]
+fn test_permanent_deferral_until_lifted() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+    env.ledger().set_timestamp(1000);
+    let donor = Address::generate(&env);
+
+    client.defer_donor(&admin, &donor, &DeferralReason::ReactiveScreening, &None);
+    assert_eq!(client.get_donor_eligible_at(&donor), None);
+
+    env.ledger().set_timestamp(1000 + 365 * 86400);
+    assert_eq!(
+        register_donation(&env, &client, &admin, &donor, BloodComponent::Plasma),
+        Err(ContractError::DonorPermanentlyDeferred)
+    );
+
+    // A permanent deferral can't be shortened
+    let result = client.try_defer_donor(
+        &admin,
+        &donor,
+        &DeferralReason::Other,
+        &Some(1000 + 400 * 86400),
+    );
+    assert_eq!(result, Err(Ok(ContractError::InvalidInput)));
+
+    client.lift_deferral(&donor);
+    assert_eq!(client.get_donor_deferral(&donor), None);
+    assert!(register_donation(&env, &client, &admin, &donor, BloodComponent::Plasma).is_ok());
+}
+
+#[test]
+fn test_deferral_can_be_extended() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+    env.ledger().set_timestamp(1000);
+    let donor = Address::generate(&env);
+
+    client.defer_donor(&admin, &donor, &DeferralReason::Travel, &Some(5000));
+    let extended = client.defer_donor(&admin, &donor, &DeferralReason::Medication, &Some(9000));
+    assert_eq!(extended.reason, DeferralReason::Medication);
+    assert_eq!(client.get_donor_eligible_at(&donor), Some(9000));
+
+    let result = client.try_defer_donor(&admin, &donor, &DeferralReason::Travel, &Some(7000));
+    assert_eq!(result, Err(Ok(ContractError::InvalidInput)));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #15)")]
+fn test_defer_donor_rejects_past_end() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+    env.ledger().set_timestamp(1000);
+
+    let donor = Address::generate(&env);
+    client.defer_donor(&admin, &donor, &DeferralReason::Other, &Some(1000));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #32)")]
+fn test_defer_donor_requires_authorized_bank() {
+    let (env, _admin, client, _contract_id) = create_test_contract();
+
+    let bank = Address::generate(&env);
+    let donor = Address::generate(&env);
+    client.defer_donor(&bank, &donor, &DeferralReason::Other, &None);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #21)")]
+fn test_lift_deferral_not_found() {
+    let (env, _admin, client, _contract_id) = create_test_contract();
+
+    client.lift_deferral(&Address::generate(&env));
+}
+
+// ==================== Custody Chain Tests ====================
+
+#[test]
+fn test_custody_chain_records_handoffs() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+
+    let courier_a = Address::generate(&env);
+    let courier_b = Address::generate(&env);
+    let hospital = Address::generate(&env);
+    client.grant_role(&courier_a, &Role::Courier);
+    client.grant_role(&courier_b, &Role::Courier);
+    client.grant_role(&hospital, &Role::HospitalReceiver);
+
+    let unit_id = register_available_unit(&env, &client, &admin);
+    assert_eq!(client.get_custodian(&unit_id), admin);
+
+    let depot = String::from_str(&env, "Central depot");
+    client.initiate_handoff(&unit_id, &admin, &courier_a, &depot);
+    assert!(has_event(
+        &env,
+        &(Symbol::new(&env, "handoff_initiated"), unit_id).into_val(&env)
+    ));
+    assert_eq!(client.get_custodian(&unit_id), admin);
+
+    env.ledger().with_mut(|li| li.timestamp = 1100);
+    client.accept_handoff(&unit_id, &courier_a);
+    assert!(has_event(
+        &env,
+        &(Symbol::new(&env, "handoff_accepted"), unit_id).into_val(&env)
+    ));
+
+    env.ledger().with_mut(|li| li.timestamp = 1200);
+    client.initiate_handoff(
+        &unit_id,
+        &courier_a,
+        &courier_b,
+        &String::from_str(&env, "Relay"),
+    );
+    client.accept_handoff(&unit_id, &courier_b);
+
+    env.ledger().with_mut(|li| li.timestamp = 1300);
+    let ward = String::from_str(&env, "Ward 4 receiving");
+    client.initiate_handoff(&unit_id, &courier_b, &hospital, &ward);
+    env.ledger().with_mut(|li| li.timestamp = 1350);
+    client.accept_handoff(&unit_id, &hospital);
+
+    let chain = client.get_custody_chain(&unit_id);
+    assert_eq!(chain.len(), 3);
+
+    let first = chain.get(0).unwrap();
+    assert_eq!(first.from, admin);
+    assert_eq!(first.to, courier_a);
+    assert_eq!(first.location, depot);
+    assert_eq!(first.initiated_at, 1000);
+    assert_eq!(first.accepted_at, Some(1100));
+
+    let last = chain.get(2).unwrap();
+    assert_eq!(last.from, courier_b);
+    assert_eq!(last.to, hospital);
+    assert_eq!(last.location, ward);
+    assert_eq!(last.initiated_at, 1300);
+    assert_eq!(last.accepted_at, Some(1350));
+
+    assert_eq!(client.get_custodian(&unit_id), hospital);
+    assert_eq!(client.get_pending_handoff(&unit_id), None);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #62)")]
+fn test_handoff_from_non_custodian_rejected() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+
+    let courier = Address::generate(&env);
+    client.grant_role(&courier, &Role::Courier);
+    let unit_id = register_available_unit(&env, &client, &admin);
+
+    client.initiate_handoff(
+        &unit_id,
+        &courier,
+        &admin,
+        &String::from_str(&env, "Central depot"),
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #31)")]
+fn test_handoff_to_address_without_role_rejected() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+
+    let unit_id = register_available_unit(&env, &client, &admin);
+
+    client.initiate_handoff(
+        &unit_id,
+        &admin,
+        &Address::generate(&env),
+        &String::from_str(&env, "Central depot"),
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #60)")]
+fn test_second_handoff_while_pending_rejected() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+
+    let courier = Address::generate(&env);
+    client.grant_role(&courier, &Role::Courier);
+    let unit_id = register_available_unit(&env, &client, &admin);
+    let depot = String::from_str(&env, "Central depot");
+
+    client.initiate_handoff(&unit_id, &admin, &courier, &depot);
+    client.initiate_handoff(&unit_id, &admin, &courier, &depot);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2)")]
+fn test_accept_handoff_by_wrong_receiver_rejected() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+
+    let courier = Address::generate(&env);
+    let other = Address::generate(&env);
+    client.grant_role(&courier, &Role::Courier);
+    client.grant_role(&other, &Role::Courier);
+    let unit_id = register_available_unit(&env, &client, &admin);
+
+    client.initiate_handoff(
+        &unit_id,
+        &admin,
+        &courier,
+        &String::from_str(&env, "Central depot"),
+    );
+    client.accept_handoff(&unit_id, &other);
+}
+
+#[test]
+fn test_cancel_handoff_keeps_custody() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+
+    let courier = Address::generate(&env);
+    client.grant_role(&courier, &Role::Courier);
+    let unit_id = register_available_unit(&env, &client, &admin);
+
+    client.initiate_handoff(
+        &unit_id,
+        &admin,
+        &courier,
+        &String::from_str(&env, "Central depot"),
+    );
+    client.cancel_handoff(&unit_id, &admin);
+
+    assert_eq!(client.get_pending_handoff(&unit_id), None);
+    assert_eq!(client.get_custodian(&unit_id), admin);
+    assert_eq!(client.get_custody_chain(&unit_id).len(), 0);
+    assert_eq!(
+        client.try_accept_handoff(&unit_id, &courier),
+        Err(Ok(ContractError::NoPendingHandoff))
+    );
+}
+
+// ==================== Storage TTL Tests ====================
+
+fn persistent_ttl(env: &Env, contract_id: &Address, key: &DataKey) -> u32 {
+    env.as_contract(contract_id, || env.storage().persistent().get_ttl(key))
+}
+
+fn advance_ledgers(env: &Env, ledgers: u32) {
+    env.ledger().with_mut(|li| li.sequence_number += ledgers);
+}
+
+#[test]
+fn test_registration_extends_unit_ttl() {
+    let (env, admin, client, contract_id) = create_test_contract();
+
+    let unit_id = register_test_unit(&env, &client, &admin);
+
+    assert_eq!(
+        persistent_ttl(&env, &contract_id, &DataKey::BloodUnit(unit_id)),
+        storage::ACTIVE_TTL_EXTEND_TO
+    );
+    assert_eq!(
+        persistent_ttl(
+            &env,
+            &contract_id,
+            &DataKey::StatusIndex(BloodStatus::Quarantined)
+        ),
+        storage::ACTIVE_TTL_EXTEND_TO
+    );
+}
+
+#[test]
+fn test_reading_unit_bumps_ttl() {
+    let (env, admin, client, contract_id) = create_test_contract();
+
+    let unit_id = register_test_unit(&env, &client, &admin);
+    let key = DataKey::BloodUnit(unit_id);
+
+    advance_ledgers(
+        &env,
+        storage::ACTIVE_TTL_EXTEND_TO - storage::ACTIVE_TTL_THRESHOLD + 1,
+    );
+    assert!(persistent_ttl(&env, &contract_id, &key) < storage::ACTIVE_TTL_THRESHOLD);
+
+    client.get_blood_unit(&unit_id);
+    assert_eq!(
+        persistent_ttl(&env, &contract_id, &key),
+        storage::ACTIVE_TTL_EXTEND_TO
+    );
+}
+
+#[test]
+fn test_terminal_unit_moves_to_archive_ttl() {
+    let (env, admin, client, contract_id) = create_test_contract();
+
+    let unit_id = register_available_unit(&env, &client, &admin);
+    client.discard_unit(&unit_id, &admin, &DiscardReason::Damaged, &None);
+
+    for key in [
+        DataKey::BloodUnit(unit_id),
+        DataKey::UnitStatusChange(unit_id, 0),
+        DataKey::BloodUnitStatusChangeCount(unit_id),
+        DataKey::ScreeningResults(unit_id),
+    ] {
+        assert_eq!(
+            persistent_ttl(&env, &contract_id, &key),
+            storage::ARCHIVE_TTL_EXTEND_TO
+        );
+    }
+}
+
+#[test]
+fn test_extend_unit_ttl_is_permissionless() {
+    let (env, admin, client, contract_id) = create_test_contract();
+
+    let unit_id = register_available_unit(&env, &client, &admin);
+    let key = DataKey::BloodUnitStatusChangeCount(unit_id);
+
+    advance_ledgers(
+        &env,
+        storage::ACTIVE_TTL_EXTEND_TO - storage::ACTIVE_TTL_THRESHOLD + 1,
+    );
+    assert!(persistent_ttl(&env, &contract_id, &key) < storage::ACTIVE_TTL_THRESHOLD);
+
+    client.extend_unit_ttl(&unit_id);
+    assert!(env.auths().is_empty());
+    assert_eq!(
+        persistent_ttl(&env, &contract_id, &key),
+        storage::ACTIVE_TTL_EXTEND_TO
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #21)")]
+fn test_extend_unit_ttl_not_found() {
+    let (_env, _admin, client, _contract_id) = create_test_contract();
+
+    client.extend_unit_ttl(&999);
+}
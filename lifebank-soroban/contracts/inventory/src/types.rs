@@ -1,5 +1,5 @@
 use crate::error::ContractError;
-use soroban_sdk::{contracttype, Address, Map, String, Symbol, Vec};
+use soroban_sdk::{contracttype, Address, BytesN, Map, String, Symbol, Vec};
 
 /// Blood type enumeration supporting all major blood groups
 ///
@@ -29,24 +29,363 @@ pub enum BloodType {
     ONegative,
 }
 
+/// Blood product component
+///
+/// Components are separated from whole blood donations and tracked as
+/// distinct stock lines because hospitals order them independently.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq, Copy)]
+pub enum BloodComponent {
+    /// Unseparated whole blood
+    WholeBlood,
+    /// Packed red blood cells
+    RedCells,
+    /// Platelet concentrate
+    Platelets,
+    /// Fresh frozen plasma
+    Plasma,
+    /// Cryoprecipitate
+    Cryoprecipitate,
+}
+
+/// How a donation was collected
+///
+/// One collection can be split into several components; the donation type,
+/// not the components made from it, sets the donor's next eligible date.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq, Copy)]
+pub enum DonationType {
+    /// Whole blood donation, possibly split into components
+    WholeBlood,
+    /// Double red cell apheresis
+    DoubleRedCells,
+    /// Plasma apheresis
+    Plasmapheresis,
+    /// Platelet apheresis
+    Plateletpheresis,
+}
+
 /// Blood unit status representing its current state in the supply chain
 ///
 /// Status transitions follow this flow:
-/// Available -> Reserved -> InTransit -> Delivered
-///           \-> Expired (can happen at any stage)
+/// Quarantined -> Available -> Reserved -> InTransit -> Delivered -> Transfused
+///             \-> Discarded (can happen at any stage before transfusion)
+///             \-> Expired (can happen at any stage before delivery)
+///             <- Quarantined (donor recall, any stage before delivery)
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq, Copy)]
 pub enum BloodStatus {
-    /// Available for reservation - initial state after donation processing
+    /// Held pending infectious-disease screening - initial state after donation
+    Quarantined,
+    /// Available for reservation - screening cleared
     Available,
     /// Reserved for a specific request but not yet shipped
     Reserved,
     /// Currently being transported to destination
     InTransit,
-    /// Successfully delivered to recipient/hospital
+    /// Successfully delivered to recipient/hospital, awaiting transfusion
     Delivered,
     /// Expired and no longer usable (typically after 42 days for whole blood)
     Expired,
+    /// Removed from the supply chain - see `DiscardReason`
+    Discarded,
+    /// Transfused into a patient - end of the unit's life
+    Transfused,
+}
+
+/// Structured reason for discarding a blood unit
+///
+/// Used for wastage reporting per blood bank.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq, Copy)]
+pub enum DiscardReason {
+    /// Bag broken, leaking or otherwise physically damaged
+    Damaged,
+    /// Bacterial contamination or failed visual inspection
+    Contaminated,
+    /// Reactive infectious-disease screening result
+    FailedScreening,
+    /// Any other reason (see status change note)
+    Other,
+}
+
+/// Why a donor's units are being recalled
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq, Copy)]
+pub enum RecallReason {
+    /// Donor tested positive for a TTI marker at a later donation or visit
+    DonorTestedPositive,
+    /// Donor reported an illness after donating
+    PostDonationIllness,
+    /// Any other reason (e.g. new deferral criteria)
+    Other,
+}
+
+/// Why a donor is deferred from donating
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq, Copy)]
+pub enum DeferralReason {
+    /// Hemoglobin below the donation threshold
+    LowHemoglobin,
+    /// Recent travel to a malaria or other risk area
+    Travel,
+    /// Medication that makes donation unsafe
+    Medication,
+    /// Recent tattoo, piercing or other exposure risk
+    ExposureRisk,
+    /// Reactive infectious-disease screening result
+    ReactiveScreening,
+    /// Any other reason
+    Other,
+}
+
+/// Deferral on record for a donor
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DonorDeferral {
+    /// Deferred donor
+    pub donor_id: Address,
+
+    /// Why the donor was deferred
+    pub reason: DeferralReason,
+
+    /// Bank or admin that recorded the deferral
+    pub deferred_by: Address,
+
+    /// When the deferral was recorded
+    pub deferred_at: u64,
+
+    /// When the deferral ends (None for a permanent deferral)
+    pub until: Option<u64>,
+}
+
+impl DonorDeferral {
+    /// Whether the donor is still deferred at `current_time`
+    pub fn is_active(&self, current_time: u64) -> bool {
+        match self.until {
+            Some(until) => current_time < until,
+            None => true,
+        }
+    }
+}
+
+/// One collection from a donor
+///
+/// Units registered for the donor at the same bank within
+/// `COLLECTION_WINDOW_SECONDS` belong to this collection.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Collection {
+    /// Unique ID of the collection
+    pub collection_id: u64,
+
+    /// Donor the collection was taken from
+    pub donor_id: Address,
+
+    /// Bank that took the collection
+    pub bank_id: Address,
+
+    /// How the donation was collected
+    pub donation_type: DonationType,
+
+    /// When the donation was collected
+    pub collected_at: u64,
+}
+
+/// Lookback entry for a recalled unit that already left the bank
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct LookbackEntry {
+    /// Unique ID of the blood unit
+    pub blood_unit_id: u64,
+
+    /// Bank that issued the unit
+    pub bank_id: Address,
+
+    /// Current status (Delivered or Transfused)
+    pub status: BloodStatus,
+
+    /// Delivery location recorded when the unit was marked delivered
+    pub destination: Option<String>,
+
+    /// When the unit was delivered
+    pub delivered_at: u64,
+}
+
+/// Where and when a unit was delivered
+///
+/// Kept apart from status history so recall lookback still works after the
+/// history has been archived.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DeliveryRecord {
+    /// Delivery location recorded when the unit was marked delivered
+    pub destination: Option<String>,
+
+    /// When the unit was delivered
+    pub delivered_at: u64,
+}
+
+/// Result of a donor recall
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct RecallReport {
+    /// Donor whose units were recalled
+    pub donor_id: Address,
+
+    /// Why the recall was raised
+    pub reason: RecallReason,
+
+    /// When the recall was raised
+    pub recalled_at: u64,
+
+    /// Units still in bank custody, now held in quarantine
+    pub quarantined_units: Vec<u64>,
+
+    /// Units already delivered to hospitals
+    pub lookback: Vec<LookbackEntry>,
+}
+
+/// Role an address holds in the inventory supply chain
+///
+/// Each address holds at most one role. The admin address always acts as
+/// `Admin` and is not stored in the registry.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq, Copy)]
+pub enum Role {
+    /// Contract administrator
+    Admin,
+    /// Blood bank holding inventory
+    BankOperator,
+    /// Courier moving units between sites
+    Courier,
+    /// Hospital staff receiving and transfusing units
+    HospitalReceiver,
+    /// Screening laboratory
+    Lab,
+    /// Changes made by the contract itself (e.g. lapsed reservation release)
+    /// or by an address without a registered role
+    System,
+}
+
+/// Structured detail attached to a status change
+///
+/// "No detail" is its own variant rather than an `Option<StatusDetail>`
+/// so history entries and events always carry exactly one detail value
+/// and callers match on a single enum.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum StatusDetail {
+    /// No structured detail - see the free-text reason
+    None,
+    /// Unit was discarded for the given reason
+    Discarded(DiscardReason),
+    /// Unit custody moving (or moved) between banks
+    BankTransfer(BankTransfer),
+}
+
+/// Time-limited hold on a reserved blood unit
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Reservation {
+    /// Hospital or other party holding the unit
+    pub holder: Address,
+
+    /// Blood request the unit is held for, if any
+    pub request_id: Option<u64>,
+
+    /// When the reservation was placed
+    pub reserved_at: u64,
+
+    /// When the reservation lapses
+    pub expires_at: u64,
+}
+
+impl Reservation {
+    /// Whether the reservation has lapsed at `current_time`
+    pub fn is_lapsed(&self, current_time: u64) -> bool {
+        current_time >= self.expires_at
+    }
+}
+
+/// Physical handoff of a blood unit between two custodians
+///
+/// The releasing custodian initiates the handoff and the receiving one
+/// accepts it, so each link in the chain carries both parties' signatures.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CustodyHandoff {
+    /// Custodian releasing the unit
+    pub from: Address,
+
+    /// Custodian receiving the unit
+    pub to: Address,
+
+    /// Where the handoff takes place
+    pub location: String,
+
+    /// When the releasing custodian initiated the handoff
+    pub initiated_at: u64,
+
+    /// When the receiving custodian accepted (None while pending)
+    pub accepted_at: Option<u64>,
+}
+
+/// Custody transfer of a blood unit between two banks
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BankTransfer {
+    /// Bank releasing custody
+    pub from_bank: Address,
+
+    /// Bank taking custody
+    pub to_bank: Address,
+
+    /// When the transfer was initiated
+    pub initiated_at: u64,
+}
+
+/// Transfusion-transmissible infection (TTI) markers screened on every donation
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq, Copy)]
+pub enum TtiMarker {
+    /// Human immunodeficiency virus
+    Hiv,
+    /// Hepatitis B virus
+    Hbv,
+    /// Hepatitis C virus
+    Hcv,
+    /// Syphilis (Treponema pallidum)
+    Syphilis,
+    /// Malaria (Plasmodium)
+    Malaria,
+}
+
+/// Outcome of a single marker test
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq, Copy)]
+pub enum ScreeningResult {
+    /// Non-reactive - marker not detected
+    Negative,
+    /// Reactive - unit must not be released
+    Positive,
+}
+
+/// Lab result recorded for one marker on a blood unit
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct ScreeningRecord {
+    /// Marker that was tested
+    pub marker: TtiMarker,
+
+    /// Test outcome
+    pub result: ScreeningResult,
+
+    /// Lab that performed the test
+    pub lab: Address,
+
+    /// When the result was recorded
+    pub tested_at: u64,
 }
 
 /// Complete blood unit record stored in the inventory contract
@@ -66,6 +405,15 @@ pub struct BloodUnit {
     /// Blood type (A+, A-, B+, B-, AB+, AB-, O+, O-)
     pub blood_type: BloodType,
 
+    /// Blood product component
+    pub component: BloodComponent,
+
+    /// ISBT 128 donation identification number with check character
+    pub din: String,
+
+    /// ISBT 128 product code
+    pub product_code: String,
+
     /// Volume in milliliters (ml)
     /// Standard unit: 450ml ± 10% for whole blood
     /// Typical range: 400-500ml
@@ -136,35 +484,69 @@ impl BloodType {
     }
 }
 
+impl DonationType {
+    /// Minimum days before a donor may donate again after this donation type
+    pub fn min_donation_interval_days(&self) -> u64 {
+        match self {
+            DonationType::WholeBlood => 56,
+            DonationType::DoubleRedCells => 112,
+            DonationType::Plasmapheresis => 28,
+            DonationType::Plateletpheresis => 7,
+        }
+    }
+}
+
 impl BloodStatus {
     /// Check if transition from current status to new status is valid
     ///
     /// Valid transitions:
-    /// - Available -> Reserved, Expired
-    /// - Reserved -> InTransit, Available (if cancelled), Expired
-    /// - InTransit -> Delivered, Expired
-    /// - Delivered -> (terminal state)
+    /// - Quarantined -> Available (screening cleared), Discarded, Expired
+    /// - Available -> Reserved, Quarantined (recall), Discarded, Expired
+    /// - Reserved -> InTransit, Available (if cancelled), Quarantined (recall), Discarded, Expired
+    /// - InTransit -> Delivered, Available (bank transfer received), Quarantined (recall),
+    ///   Discarded, Expired
+    /// - Delivered -> Transfused, Discarded
     /// - Expired -> (terminal state)
+    /// - Discarded -> (terminal state)
+    /// - Transfused -> (terminal state)
     pub fn can_transition_to(&self, new_status: &BloodStatus) -> bool {
         use BloodStatus::*;
 
         match (self, new_status) {
-            // Available can go to Reserved or Expired
+            // Quarantined is released to Available, or discarded on a reactive result
+            (Quarantined, Available) => true,
+            (Quarantined, Discarded) => true,
+            (Quarantined, Expired) => true,
+
+            // Available can go to Reserved, Discarded or Expired, or back to quarantine on recall
             (Available, Reserved) => true,
+            (Available, Quarantined) => true,
+            (Available, Discarded) => true,
             (Available, Expired) => true,
 
-            // Reserved can go to InTransit, back to Available, or Expired
+            // Reserved can go to InTransit, back to Available, Discarded or Expired, or be recalled
             (Reserved, InTransit) => true,
             (Reserved, Available) => true,
+            (Reserved, Quarantined) => true,
+            (Reserved, Discarded) => true,
             (Reserved, Expired) => true,
 
-            // InTransit can go to Delivered or Expired
+            // InTransit can go to Delivered, Discarded or Expired, or be recalled.
+            // It returns to Available when another bank receives a transfer.
             (InTransit, Delivered) => true,
+            (InTransit, Available) => true,
+            (InTransit, Quarantined) => true,
+            (InTransit, Discarded) => true,
             (InTransit, Expired) => true,
 
-            // Delivered and Expired are terminal states
-            (Delivered, _) => false,
+            // Delivered is either transfused or discarded by the hospital
+            (Delivered, Transfused) => true,
+            (Delivered, Discarded) => true,
+
+            // Expired, Discarded and Transfused are terminal states
             (Expired, _) => false,
+            (Discarded, _) => false,
+            (Transfused, _) => false,
 
             // No other transitions allowed
             _ => false,
@@ -173,7 +555,10 @@ impl BloodStatus {
 
     /// Check if this status is a terminal state
     pub fn is_terminal(&self) -> bool {
-        matches!(self, BloodStatus::Delivered | BloodStatus::Expired)
+        matches!(
+            self,
+            BloodStatus::Expired | BloodStatus::Discarded | BloodStatus::Transfused
+        )
     }
 }
 
@@ -236,17 +621,94 @@ pub enum DataKey {
     /// Index: Donor ID -> Vec<u64> (blood unit IDs)
     DonorIndex(Address),
 
+    /// Deferral on record for a donor -> DonorDeferral
+    DonorDeferral(Address),
+
+    /// Collection record by collection ID -> Collection
+    Collection(u64),
+
+    /// Counter for generating new collection IDs
+    CollectionCounter,
+
+    /// Donor's most recent collection -> u64 (collection ID)
+    DonorLastCollection(Address),
+
+    /// Collection a blood unit came from -> u64 (collection ID)
+    UnitCollection(u64),
+
     /// Admin address
     Admin,
 
-    /// Status change history for a blood unit
-    StatusHistory(u64), // u64 is blood_unit_id -> Vec<StatusChangeHistory>
+    /// Request contract allowed to reserve units for blood requests
+    RequestContract,
+
+    /// Status change record by history ID -> StatusChangeHistory
+    StatusChange(u64),
+
+    /// Per-unit history sequence: (blood_unit_id, seq) -> u64 (history ID)
+    UnitStatusChange(u64, u64),
+
+    /// Digest of a unit's archived history entries -> StatusHistorySummary
+    StatusHistorySummary(u64),
 
     /// Counter for status change history records
     StatusHistoryCounter,
 
     /// Counter for status changes on specific blood unit
     BloodUnitStatusChangeCount(u64), // u64 is blood_unit_id
+
+    /// Screening results for a blood unit
+    ScreeningResults(u64), // u64 is blood_unit_id -> Map<TtiMarker, ScreeningRecord>
+
+    /// Wastage counters per bank and discard reason
+    Wastage(Address, DiscardReason),
+
+    /// Role registry: address -> Role
+    Role(Address),
+
+    /// Index: ISBT 128 DIN -> u64 (blood unit ID)
+    DinIndex(String),
+
+    /// Stock summary for a bank -> Vec<StockLevel>
+    BankStock(Address),
+
+    /// Pending inter-bank transfer for a blood unit -> BankTransfer
+    PendingTransfer(u64),
+
+    /// Active reservation on a blood unit -> Reservation
+    Reservation(u64),
+
+    /// Current custodian of a blood unit (defaults to its bank) -> Address
+    Custodian(u64),
+
+    /// Delivery destination and time of a blood unit -> DeliveryRecord
+    Delivery(u64),
+
+    /// Handoff awaiting acceptance -> CustodyHandoff
+    PendingHandoff(u64),
+
+    /// Completed handoffs for a blood unit -> Vec<CustodyHandoff>
+    CustodyChain(u64),
+
+    /// Metadata keys banks are allowed to set -> Vec<Symbol>
+    MetadataKeyAllowlist,
+
+    /// Metadata change history for a blood unit
+    MetadataHistory(u64), // u64 is blood_unit_id -> Vec<MetadataChange>
+
+    /// Index: (metadata key, value) -> Vec<u64> (blood unit IDs)
+    MetadataIndex(Symbol, String),
+}
+
+/// Aggregate wastage for one bank and discard reason
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct WastageStats {
+    /// Number of units discarded
+    pub units: u64,
+
+    /// Total volume discarded in milliliters
+    pub volume_ml: u64,
 }
 
 #[contracttype]
@@ -261,6 +723,12 @@ pub struct BloodRegisteredEvent {
     /// Blood type
     pub blood_type: BloodType,
 
+    /// Blood product component
+    pub component: BloodComponent,
+
+    /// ISBT 128 donation identification number
+    pub din: String,
+
     /// Quantity in milliliters
     pub quantity_ml: u32,
 
@@ -275,6 +743,9 @@ pub struct BloodRegisteredEvent {
 #[contracttype]
 #[derive(Clone, Debug)]
 pub struct StatusChangeEvent {
+    /// ID of the status history entry recorded for this change
+    pub history_id: u64,
+
     /// Unique ID of the blood unit
     pub blood_unit_id: u64,
 
@@ -287,16 +758,341 @@ pub struct StatusChangeEvent {
     /// Who authorized this change
     pub authorized_by: Address,
 
+    /// Role the change was authorized under
+    pub role: Role,
+
     /// When the status change occurred
     pub changed_at: u64,
 
     /// Optional reason for status change (e.g., "Delivered to Hospital A")
     pub reason: Option<String>,
+
+    /// Structured detail, e.g. the discard reason
+    pub detail: StatusDetail,
 }
 
-/// Historical record of a status change
+/// Event emitted when a blood unit is discarded
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct UnitDiscardedEvent {
+    /// Unique ID of the blood unit
+    pub blood_unit_id: u64,
+
+    /// Bank whose stock the unit belonged to
+    pub bank_id: Address,
+
+    /// Why the unit was discarded
+    pub reason: DiscardReason,
+
+    /// Volume wasted in milliliters
+    pub quantity_ml: u32,
+
+    /// Who discarded the unit
+    pub discarded_by: Address,
+
+    /// When the unit was discarded
+    pub discarded_at: u64,
+}
+
+/// Aggregate stock for one blood type and component at a bank
+///
+/// Maintained incrementally on registration and status changes so the
+/// summary can be read without iterating units.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StockLevel {
+    /// Blood type
+    pub blood_type: BloodType,
+
+    /// Blood product component
+    pub component: BloodComponent,
+
+    /// Units available for allocation
+    pub available_units: u32,
+
+    /// Volume available for allocation in milliliters
+    pub available_ml: u64,
+
+    /// Units reserved for a request
+    pub reserved_units: u32,
+
+    /// Volume reserved for a request in milliliters
+    pub reserved_ml: u64,
+
+    /// Units awaiting screening
+    pub quarantined_units: u32,
+
+    /// Volume awaiting screening in milliliters
+    pub quarantined_ml: u64,
+
+    /// Minimum available volume before a low-stock alert (0 = no threshold)
+    pub min_available_ml: u64,
+}
+
+impl StockLevel {
+    /// Empty stock line with no threshold
+    pub fn new(blood_type: BloodType, component: BloodComponent) -> Self {
+        Self {
+            blood_type,
+            component,
+            available_units: 0,
+            available_ml: 0,
+            reserved_units: 0,
+            reserved_ml: 0,
+            quarantined_units: 0,
+            quarantined_ml: 0,
+            min_available_ml: 0,
+        }
+    }
+
+    /// Whether available volume is below the configured threshold
+    pub fn is_low(&self) -> bool {
+        self.available_ml < self.min_available_ml
+    }
+
+    /// Add a unit's volume to the bucket for `status`
+    ///
+    /// Statuses outside bank stock (in transit, delivered, terminal) are ignored.
+    pub fn add(&mut self, status: BloodStatus, quantity_ml: u32) {
+        let ml = quantity_ml as u64;
+        match status {
+            BloodStatus::Available => {
+                self.available_units += 1;
+                self.available_ml += ml;
+            }
+            BloodStatus::Reserved => {
+                self.reserved_units += 1;
+                self.reserved_ml += ml;
+            }
+            BloodStatus::Quarantined => {
+                self.quarantined_units += 1;
+                self.quarantined_ml += ml;
+            }
+            _ => {}
+        }
+    }
+
+    /// Remove a unit's volume from the bucket for `status`
+    pub fn remove(&mut self, status: BloodStatus, quantity_ml: u32) {
+        let ml = quantity_ml as u64;
+        match status {
+            BloodStatus::Available => {
+                self.available_units = self.available_units.saturating_sub(1);
+                self.available_ml = self.available_ml.saturating_sub(ml);
+            }
+            BloodStatus::Reserved => {
+                self.reserved_units = self.reserved_units.saturating_sub(1);
+                self.reserved_ml = self.reserved_ml.saturating_sub(ml);
+            }
+            BloodStatus::Quarantined => {
+                self.quarantined_units = self.quarantined_units.saturating_sub(1);
+                self.quarantined_ml = self.quarantined_ml.saturating_sub(ml);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Event emitted when available stock crosses a bank's threshold
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct StockAlertEvent {
+    /// Bank whose stock crossed the threshold
+    pub bank_id: Address,
+
+    /// Blood type
+    pub blood_type: BloodType,
+
+    /// Blood product component
+    pub component: BloodComponent,
+
+    /// Available volume after the change in milliliters
+    pub available_ml: u64,
+
+    /// Configured minimum available volume in milliliters
+    pub min_available_ml: u64,
+
+    /// When the threshold was crossed
+    pub timestamp: u64,
+}
+
+/// Event emitted once per batch registration
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct BatchRegisteredEvent {
+    /// Blood bank that registered the units
+    pub bank_id: Address,
+
+    /// Assigned blood unit IDs (contiguous)
+    pub blood_unit_ids: Vec<u64>,
+
+    /// When the batch was registered
+    pub registered_at: u64,
+}
+
+/// Event emitted when a reservation is placed or released
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct ReservationEvent {
+    /// Unique ID of the blood unit
+    pub blood_unit_id: u64,
+
+    /// Hospital or other party holding the unit
+    pub holder: Address,
+
+    /// Blood request the unit is held for, if any
+    pub request_id: Option<u64>,
+
+    /// When the reservation lapses
+    pub expires_at: u64,
+
+    /// When the event occurred
+    pub timestamp: u64,
+}
+
+/// Event emitted when a custody handoff is initiated, accepted or cancelled
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct CustodyHandoffEvent {
+    /// Unique ID of the blood unit
+    pub blood_unit_id: u64,
+
+    /// Custodian releasing the unit
+    pub from: Address,
+
+    /// Custodian receiving the unit
+    pub to: Address,
+
+    /// Where the handoff takes place
+    pub location: String,
+
+    /// When the event occurred
+    pub timestamp: u64,
+}
+
+/// Event emitted when an inter-bank transfer is initiated or received
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct BankTransferEvent {
+    /// Unique ID of the blood unit
+    pub blood_unit_id: u64,
+
+    /// Bank releasing custody
+    pub from_bank: Address,
+
+    /// Bank taking custody
+    pub to_bank: Address,
+
+    /// When the event occurred
+    pub timestamp: u64,
+}
+
+/// Event emitted once per donor recall
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct DonorRecallEvent {
+    /// Donor whose units were recalled
+    pub donor_id: Address,
+
+    /// Why the recall was raised
+    pub reason: RecallReason,
+
+    /// Number of units moved back to quarantine
+    pub quarantined_count: u32,
+
+    /// Number of delivered units needing hospital lookback
+    pub lookback_count: u32,
+
+    /// When the recall was raised
+    pub recalled_at: u64,
+}
+
+/// Event emitted for each delivered unit affected by a donor recall
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct UnitRecalledEvent {
+    /// Unique ID of the blood unit
+    pub blood_unit_id: u64,
+
+    /// Donor whose units were recalled
+    pub donor_id: Address,
+
+    /// Why the recall was raised
+    pub reason: RecallReason,
+
+    /// Current status (Delivered or Transfused)
+    pub status: BloodStatus,
+
+    /// Delivery location recorded when the unit was marked delivered
+    pub destination: Option<String>,
+
+    /// When the unit was delivered
+    pub delivered_at: u64,
+
+    /// When the recall was raised
+    pub recalled_at: u64,
+}
+
+/// Event emitted when unit metadata is set or removed
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct MetadataUpdatedEvent {
+    /// Unique ID of the blood unit
+    pub blood_unit_id: u64,
+
+    /// Metadata key that changed
+    pub key: Symbol,
+
+    /// New value (None if removed)
+    pub value: Option<String>,
+
+    /// Who made the change
+    pub changed_by: Address,
+
+    /// When the change occurred
+    pub changed_at: u64,
+}
+
+/// Event emitted when a lab records a screening result
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct ScreeningRecordedEvent {
+    /// Unique ID of the blood unit
+    pub blood_unit_id: u64,
+
+    /// Marker that was tested
+    pub marker: TtiMarker,
+
+    /// Test outcome
+    pub result: ScreeningResult,
+
+    /// Lab that performed the test
+    pub lab: Address,
+
+    /// When the result was recorded
+    pub tested_at: u64,
+}
+
+/// Event emitted when a donation screens positive and the donor needs follow-up
 #[contracttype]
 #[derive(Clone, Debug)]
+pub struct DonorFollowUpEvent {
+    /// Donor whose donation was reactive
+    pub donor_id: Address,
+
+    /// Blood unit that screened positive
+    pub blood_unit_id: u64,
+
+    /// Reactive marker
+    pub marker: TtiMarker,
+
+    /// When the follow-up was raised
+    pub flagged_at: u64,
+}
+
+/// Historical record of a status change
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub struct StatusChangeHistory {
     /// Unique ID for this history record
     pub id: u64,
@@ -313,11 +1109,102 @@ pub struct StatusChangeHistory {
     /// Who authorized this change
     pub authorized_by: Address,
 
+    /// Role the change was authorized under
+    pub role: Role,
+
     /// When the status change occurred
     pub changed_at: u64,
 
     /// Optional reason for status change
     pub reason: Option<String>,
+
+    /// Structured detail, e.g. the discard reason
+    pub detail: StatusDetail,
+}
+
+/// One page of a unit's status history, oldest first
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct StatusHistoryPage {
+    /// Entries on this page
+    pub entries: Vec<StatusChangeHistory>,
+
+    /// Cursor for the next page (None when this is the last page)
+    pub next_cursor: Option<u64>,
+}
+
+/// Hash-chained digest of status history entries rolled out of storage
+///
+/// `chain_hash` starts as 32 zero bytes and folds in each archived entry,
+/// oldest first, as `sha256(chain_hash || xdr(entry))`. Each `status_changed`
+/// event carries every field of its history entry, including `history_id`,
+/// so an indexer can rebuild the archived entries and recompute it.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StatusHistorySummary {
+    /// Blood unit ID
+    pub blood_unit_id: u64,
+
+    /// Number of entries rolled into the digest (the unit's oldest)
+    pub archived_count: u64,
+
+    /// History ID of the newest archived entry
+    pub last_history_id: u64,
+
+    /// When the newest archived entry was recorded
+    pub last_changed_at: u64,
+
+    /// Running hash over all archived entries
+    pub chain_hash: BytesN<32>,
+}
+
+/// Historical record of a metadata change
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct MetadataChange {
+    /// Blood unit ID
+    pub blood_unit_id: u64,
+
+    /// Metadata key that changed
+    pub key: Symbol,
+
+    /// Value before the change (None if newly added)
+    pub old_value: Option<String>,
+
+    /// Value after the change (None if removed)
+    pub new_value: Option<String>,
+
+    /// Who made the change
+    pub changed_by: Address,
+
+    /// When the change occurred
+    pub changed_at: u64,
+}
+
+/// One entry in a batch registration
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct NewUnit {
+    /// ISBT 128 DIN with check character
+    pub din: String,
+
+    /// ISBT 128 product code
+    pub product_code: String,
+
+    /// Blood type
+    pub blood_type: BloodType,
+
+    /// Blood product component
+    pub component: BloodComponent,
+
+    /// Quantity in milliliters (100-600ml)
+    pub quantity_ml: u32,
+
+    /// Unix timestamp when the unit expires
+    pub expiration_timestamp: u64,
+
+    /// Optional donor address (None for anonymous)
+    pub donor_id: Option<Address>,
 }
 
 /// Batch status update operation
@@ -334,6 +1221,29 @@ pub struct BatchStatusUpdate {
     pub reason: Option<String>,
 }
 
+/// Outcome for one unit in a partial-success batch update
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum BatchUpdateOutcome {
+    /// Transition applied
+    Updated,
+    /// Transition rejected; carries the `ContractError` code
+    Skipped(u32),
+    /// No unit with this ID
+    NotFound,
+}
+
+/// Per-unit result of a partial-success batch update
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BatchUpdateResult {
+    /// Blood unit ID
+    pub blood_unit_id: u64,
+
+    /// What happened to the unit
+    pub outcome: BatchUpdateOutcome,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -404,18 +1314,68 @@ mod tests {
         // InTransit transitions
         assert!(InTransit.can_transition_to(&Delivered));
         assert!(InTransit.can_transition_to(&Expired));
-        assert!(!InTransit.can_transition_to(&Available));
+        assert!(InTransit.can_transition_to(&Available)); // bank transfer received
         assert!(!InTransit.can_transition_to(&Reserved));
 
-        // Terminal states
+        // Delivered transitions
+        assert!(Delivered.can_transition_to(&Transfused));
+        assert!(Delivered.can_transition_to(&Discarded));
         assert!(!Delivered.can_transition_to(&Expired));
+        assert!(!Delivered.can_transition_to(&Available));
+
+        // Terminal states
         assert!(!Expired.can_transition_to(&Delivered));
+        assert!(!Expired.can_transition_to(&Discarded));
+        assert!(!Transfused.can_transition_to(&Discarded));
+    }
+
+    #[test]
+    fn test_status_transitions_from_quarantined() {
+        use BloodStatus::*;
+
+        assert!(Quarantined.can_transition_to(&Available));
+        assert!(Quarantined.can_transition_to(&Discarded));
+        assert!(Quarantined.can_transition_to(&Expired));
+        assert!(!Quarantined.can_transition_to(&Reserved));
+        assert!(!Quarantined.can_transition_to(&InTransit));
+        assert!(!Quarantined.can_transition_to(&Delivered));
+
+        // Discarded is terminal
+        assert!(!Discarded.can_transition_to(&Available));
+        assert!(!Discarded.can_transition_to(&Quarantined));
+    }
+
+    #[test]
+    fn test_status_discard_reachable_before_transfusion() {
+        use BloodStatus::*;
+
+        for status in [Quarantined, Available, Reserved, InTransit, Delivered] {
+            assert!(status.can_transition_to(&Discarded));
+        }
+        for status in [Quarantined, Available, Reserved, InTransit] {
+            assert!(!status.can_transition_to(&Transfused));
+        }
+    }
+
+    #[test]
+    fn test_status_recall_to_quarantine() {
+        use BloodStatus::*;
+
+        for status in [Available, Reserved, InTransit] {
+            assert!(status.can_transition_to(&Quarantined));
+        }
+        for status in [Delivered, Expired, Discarded, Transfused] {
+            assert!(!status.can_transition_to(&Quarantined));
+        }
     }
 
     #[test]
     fn test_status_terminal_states() {
-        assert!(BloodStatus::Delivered.is_terminal());
         assert!(BloodStatus::Expired.is_terminal());
+        assert!(BloodStatus::Discarded.is_terminal());
+        assert!(BloodStatus::Transfused.is_terminal());
+        assert!(!BloodStatus::Delivered.is_terminal());
+        assert!(!BloodStatus::Quarantined.is_terminal());
         assert!(!BloodStatus::Available.is_terminal());
         assert!(!BloodStatus::Reserved.is_terminal());
         assert!(!BloodStatus::InTransit.is_terminal());
@@ -430,6 +1390,9 @@ mod tests {
         let unit = BloodUnit {
             id: 1,
             blood_type: BloodType::APositive,
+            component: BloodComponent::WholeBlood,
+            din: String::from_str(&env, "W000026000001F"),
+            product_code: String::from_str(&env, "E0001V00"),
             quantity_ml: 450,
             bank_id: bank,
             donor_id: None,
@@ -451,6 +1414,9 @@ mod tests {
         let unit = BloodUnit {
             id: 1,
             blood_type: BloodType::APositive,
+            component: BloodComponent::WholeBlood,
+            din: String::from_str(&env, "W000026000001F"),
+            product_code: String::from_str(&env, "E0001V00"),
             quantity_ml: 50, // Too low
             bank_id: bank,
             donor_id: None,
@@ -475,6 +1441,9 @@ mod tests {
         let unit = BloodUnit {
             id: 1,
             blood_type: BloodType::APositive,
+            component: BloodComponent::WholeBlood,
+            din: String::from_str(&env, "W000026000001F"),
+            product_code: String::from_str(&env, "E0001V00"),
             quantity_ml: 700, // Too high
             bank_id: bank,
             donor_id: None,
@@ -499,6 +1468,9 @@ mod tests {
         let unit = BloodUnit {
             id: 1,
             blood_type: BloodType::APositive,
+            component: BloodComponent::WholeBlood,
+            din: String::from_str(&env, "W000026000001F"),
+            product_code: String::from_str(&env, "E0001V00"),
             quantity_ml: 450,
             bank_id: bank,
             donor_id: None,
@@ -523,6 +1495,9 @@ mod tests {
         let unit = BloodUnit {
             id: 1,
             blood_type: BloodType::APositive,
+            component: BloodComponent::WholeBlood,
+            din: String::from_str(&env, "W000026000001F"),
+            product_code: String::from_str(&env, "E0001V00"),
             quantity_ml: 450,
             bank_id: bank,
             donor_id: None,
@@ -548,6 +1523,9 @@ mod tests {
         let unit = BloodUnit {
             id: 1,
             blood_type: BloodType::APositive,
+            component: BloodComponent::WholeBlood,
+            din: String::from_str(&env, "W000026000001F"),
+            product_code: String::from_str(&env, "E0001V00"),
             quantity_ml: 450,
             bank_id: bank,
             donor_id: None,
@@ -577,6 +1555,9 @@ mod tests {
         let unit = BloodUnit {
             id: 1,
             blood_type: BloodType::APositive,
+            component: BloodComponent::WholeBlood,
+            din: String::from_str(&env, "W000026000001F"),
+            product_code: String::from_str(&env, "E0001V00"),
             quantity_ml: 450,
             bank_id: bank,
             donor_id: None,
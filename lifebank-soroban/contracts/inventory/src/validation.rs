@@ -1,7 +1,12 @@
 use crate::error::ContractError;
-use crate::storage::{MAX_EXPIRATION_DAYS, SECONDS_PER_DAY};
-use crate::types::BloodStatus;
-use soroban_sdk::Env;
+use crate::storage::{
+    MAX_EXPIRATION_DAYS, MAX_METADATA_ENTRIES, MAX_METADATA_VALUE_LEN, MAX_RESERVATION_SECONDS,
+    REQUIRED_TTI_MARKERS, SECONDS_PER_DAY,
+};
+use crate::types::{
+    BloodStatus, BloodUnit, NewUnit, Role, ScreeningRecord, ScreeningResult, TtiMarker,
+};
+use soroban_sdk::{Address, Env, Map, String, Symbol};
 
 /// Validate blood registration parameters
 ///
@@ -15,7 +20,7 @@ pub fn validate_blood_registration(
     expiration_timestamp: u64,
 ) -> Result<(), ContractError> {
     // Validate quantity (typical range: 100-600ml)
-    if quantity_ml < 100 || quantity_ml > 600 {
+    if !(100..=600).contains(&quantity_ml) {
         return Err(ContractError::InvalidQuantity);
     }
 
@@ -52,6 +57,62 @@ pub fn validate_minimum_shelf_life(
     Ok(())
 }
 
+/// Validate a unit before registration
+///
+/// Runs the label, quantity, expiration and shelf-life checks shared by
+/// single and batch registration. DIN uniqueness is checked by the caller.
+pub fn validate_new_unit(env: &Env, new_unit: &NewUnit) -> Result<(), ContractError> {
+    validate_din(&new_unit.din)?;
+    validate_product_code(&new_unit.product_code)?;
+    validate_blood_registration(env, new_unit.quantity_ml, new_unit.expiration_timestamp)?;
+    validate_minimum_shelf_life(env, new_unit.expiration_timestamp)
+}
+
+/// Validate that a donor may donate at the current time
+///
+/// Rejects donors under an active deferral, then donors whose last
+/// collection is still within its minimum donation interval.
+pub fn validate_donor_eligibility(env: &Env, donor: &Address) -> Result<(), ContractError> {
+    validate_donor_not_deferred(env, donor)?;
+
+    if env.ledger().timestamp() < crate::storage::get_donor_next_donation_at(env, donor) {
+        return Err(ContractError::DonationIntervalNotMet);
+    }
+
+    Ok(())
+}
+
+/// Validate that a donor is not under an active deferral
+pub fn validate_donor_not_deferred(env: &Env, donor: &Address) -> Result<(), ContractError> {
+    if let Some(deferral) = crate::storage::get_donor_deferral(env, donor) {
+        if deferral.until.is_none() {
+            return Err(ContractError::DonorPermanentlyDeferred);
+        }
+        if deferral.is_active(env.ledger().timestamp()) {
+            return Err(ContractError::DonorDeferred);
+        }
+    }
+
+    Ok(())
+}
+
+/// Validate that a unit can be registered for a donor
+///
+/// Components of a collection that is still open only need the donor to be
+/// free of deferrals; anything else starts a new collection and must meet
+/// the donation interval.
+pub fn validate_donor_registration(
+    env: &Env,
+    bank_id: &Address,
+    donor: &Address,
+) -> Result<(), ContractError> {
+    if crate::storage::get_open_collection(env, bank_id, donor).is_some() {
+        validate_donor_not_deferred(env, donor)
+    } else {
+        validate_donor_eligibility(env, donor)
+    }
+}
+
 /// Validate status transition is allowed according to state machine
 pub fn validate_status_transition(
     current_status: BloodStatus,
@@ -62,3 +123,195 @@ pub fn validate_status_transition(
     }
     Ok(())
 }
+
+/// Check the transition policy table: which role may move a unit from
+/// `from` to `to`
+///
+/// | Role             | Allowed transitions                                      |
+/// |------------------|----------------------------------------------------------|
+/// | Admin            | any transition allowed by the state machine              |
+/// | BankOperator     | release, reserve/unreserve, dispatch, quarantine, expire |
+/// | Courier          | Reserved -> InTransit, InTransit -> Delivered            |
+/// | HospitalReceiver | InTransit -> Delivered, Delivered -> Transfused          |
+/// | Lab              | any -> Quarantined                                       |
+/// | System           | none                                                     |
+///
+/// Couriers and hospital receivers are further limited to units in their
+/// custody or reserved for them, which `update_status` checks. The state
+/// machine itself is checked separately by `validate_status_transition`.
+pub fn validate_role_transition(
+    role: Role,
+    from: BloodStatus,
+    to: BloodStatus,
+) -> Result<(), ContractError> {
+    use BloodStatus::*;
+
+    let allowed = match role {
+        Role::Admin => true,
+        Role::BankOperator => matches!(
+            (from, to),
+            (Quarantined, Available)
+                | (Available, Reserved)
+                | (Reserved, Available)
+                | (Reserved, InTransit)
+                | (_, Quarantined)
+                | (_, Expired)
+        ),
+        Role::Courier => matches!((from, to), (Reserved, InTransit) | (InTransit, Delivered)),
+        Role::HospitalReceiver => {
+            matches!((from, to), (InTransit, Delivered) | (Delivered, Transfused))
+        }
+        Role::Lab => to == Quarantined,
+        Role::System => false,
+    };
+
+    if !allowed {
+        return Err(ContractError::InsufficientPermissions);
+    }
+
+    Ok(())
+}
+
+/// Reject discards through the generic status update paths
+///
+/// Discards must go through `discard_unit` so a structured reason is
+/// recorded for wastage reporting.
+pub fn validate_not_discard(new_status: BloodStatus) -> Result<(), ContractError> {
+    if new_status == BloodStatus::Discarded {
+        return Err(ContractError::DiscardReasonRequired);
+    }
+    Ok(())
+}
+
+/// Validate that every required marker has been tested and is negative
+///
+/// Units may only leave quarantine once all of `REQUIRED_TTI_MARKERS`
+/// have a recorded negative result.
+pub fn validate_screening_cleared(
+    results: &Map<TtiMarker, ScreeningRecord>,
+) -> Result<(), ContractError> {
+    for marker in REQUIRED_TTI_MARKERS {
+        match results.get(marker) {
+            Some(record) if record.result == ScreeningResult::Negative => {}
+            _ => return Err(ContractError::ScreeningIncomplete),
+        }
+    }
+    Ok(())
+}
+
+/// Validate a metadata entry before it is written to a unit
+///
+/// Checks:
+/// - Key is on the admin-managed allowlist
+/// - Value is not empty and within `MAX_METADATA_VALUE_LEN`
+/// - Adding a new key doesn't exceed `MAX_METADATA_ENTRIES`
+pub fn validate_metadata_entry(
+    env: &Env,
+    blood_unit: &BloodUnit,
+    key: &Symbol,
+    value: &String,
+) -> Result<(), ContractError> {
+    if !crate::storage::is_metadata_key_allowed(env, key) {
+        return Err(ContractError::MetadataKeyNotAllowed);
+    }
+
+    if value.is_empty() {
+        return Err(ContractError::InvalidInput);
+    }
+
+    if value.len() > MAX_METADATA_VALUE_LEN {
+        return Err(ContractError::MetadataValueTooLong);
+    }
+
+    if !blood_unit.metadata.contains_key(key.clone())
+        && blood_unit.metadata.len() >= MAX_METADATA_ENTRIES
+    {
+        return Err(ContractError::MetadataLimitExceeded);
+    }
+
+    Ok(())
+}
+
+/// Validate a reservation length
+///
+/// Must be non-zero and at most `MAX_RESERVATION_SECONDS`.
+pub fn validate_reservation_duration(duration_seconds: u64) -> Result<(), ContractError> {
+    if duration_seconds == 0 || duration_seconds > MAX_RESERVATION_SECONDS {
+        return Err(ContractError::InvalidInput);
+    }
+    Ok(())
+}
+
+/// Length of an ISBT 128 donation identification number (DIN)
+pub const DIN_LENGTH: usize = 13;
+/// Length of an ISBT 128 product code
+pub const PRODUCT_CODE_LENGTH: usize = 8;
+
+/// Validate an ISBT 128 DIN as printed in eye-readable form
+///
+/// Expects the 13-character DIN followed by its ISO 7064 Mod 37-2 check
+/// character (14 characters total):
+/// - Facility code: one of `A-N`, `P-Z`, `1-9` followed by 4 digits
+/// - Collection year: 2 digits
+/// - Sequence number: 6 digits
+/// - Check character: `0-9`, `A-Z` or `*`
+pub fn validate_din(din: &String) -> Result<(), ContractError> {
+    if din.len() as usize != DIN_LENGTH + 1 {
+        return Err(ContractError::InvalidDin);
+    }
+
+    let mut bytes = [0u8; DIN_LENGTH + 1];
+    din.copy_into_slice(&mut bytes);
+
+    let first = bytes[0];
+    let valid_first = matches!(first, b'A'..=b'N' | b'P'..=b'Z' | b'1'..=b'9');
+    if !valid_first || !bytes[1..DIN_LENGTH].iter().all(u8::is_ascii_digit) {
+        return Err(ContractError::InvalidDin);
+    }
+
+    if bytes[DIN_LENGTH] != isbt_check_character(&bytes[..DIN_LENGTH]) {
+        return Err(ContractError::InvalidDin);
+    }
+
+    Ok(())
+}
+
+/// Compute the ISO 7064 Mod 37-2 check character for an ISBT 128 DIN
+pub fn isbt_check_character(din: &[u8]) -> u8 {
+    const CHARSET: &[u8; 37] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ*";
+
+    let mut sum = 0u32;
+    for &c in din {
+        let value = match c {
+            b'0'..=b'9' => (c - b'0') as u32,
+            b'A'..=b'Z' => (c - b'A') as u32 + 10,
+            _ => 36,
+        };
+        sum = ((sum + value) * 2) % 37;
+    }
+
+    CHARSET[((38 - sum) % 37) as usize]
+}
+
+/// Validate an ISBT 128 product code
+///
+/// Format: component class letter, 4 digits, donation type character and
+/// 2-character division code (e.g. `E0001V00`).
+pub fn validate_product_code(product_code: &String) -> Result<(), ContractError> {
+    if product_code.len() as usize != PRODUCT_CODE_LENGTH {
+        return Err(ContractError::InvalidProductCode);
+    }
+
+    let mut bytes = [0u8; PRODUCT_CODE_LENGTH];
+    product_code.copy_into_slice(&mut bytes);
+
+    let is_code_char = |c: &u8| c.is_ascii_uppercase() || c.is_ascii_digit();
+    if !bytes[0].is_ascii_uppercase()
+        || !bytes[1..5].iter().all(u8::is_ascii_digit)
+        || !bytes[5..].iter().all(is_code_char)
+    {
+        return Err(ContractError::InvalidProductCode);
+    }
+
+    Ok(())
+}
@@ -8,6 +8,7 @@ use soroban_sdk::contracterror;
 /// - State errors (20-29): Invalid state or state transitions
 /// - Permission errors (30-39): Authorization failures
 /// - Request-specific errors (40-49): Blood request specific errors
+/// - Inventory errors (50-59): Blood unit checks against the inventory contract
 #[contracterror]
 #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
 #[repr(u32)]
@@ -57,7 +58,7 @@ pub enum ContractError {
     /// Resource already exists (duplicate)
     AlreadyExists = 20,
 
-    /// Resource not found
+    /// Resource not found (including unknown request IDs)
     NotFound = 21,
 
     /// Request has expired (required_by timestamp passed)
@@ -106,37 +107,35 @@ pub enum ContractError {
 
     /// Request is not yet approved
     RequestNotApproved = 47,
-    // General errors (0-9)
-    AlreadyInitialized = 0,
-    NotInitialized = 1,
-    Unauthorized = 2,
 
-    // Validation errors (10-19)
-    InvalidAmount = 10,
-    InvalidAddress = 11,
-    InvalidInput = 12,
-    InvalidBloodType = 13,
-    InvalidStatus = 14,
-    InvalidTimestamp = 15,
-    InvalidQuantity = 16,
-    InvalidExpiration = 17,
+    /// Request would exceed the hospital's configured quota
+    QuotaExceeded = 48,
 
-    // State errors (20-29)
-    AlreadyExists = 20,
-    NotFound = 21,
-    Expired = 22,
-    RequestExpired = 23,
-    DuplicateRequest = 24,
+    /// Pending queue for the request's urgency level is full
+    PendingQueueFull = 49,
 
-    // Permission errors (30-39)
-    InsufficientPermissions = 31,
-    NotAuthorizedHospital = 32,
-    NotAuthorizedBloodBank = 33,
+    // ========== Inventory errors (50-59) ==========
+    /// Inventory contract address has not been configured
+    InventoryNotConfigured = 50,
 
-    // Request-specific errors (40-49)
-    RequestNotFound = 40,
-    InvalidStatusTransition = 41,
-    RequestAlreadyFulfilled = 42,
-    InsufficientBloodUnits = 43,
-    RequestOverdue = 44,
+    /// Blood unit does not exist in the inventory
+    UnitNotFound = 51,
+
+    /// Blood unit is past its expiration
+    UnitExpired = 52,
+
+    /// Blood unit is not available for reservation
+    UnitNotAvailable = 53,
+
+    /// Blood unit's type is not compatible with the requested type
+    IncompatibleBloodType = 54,
+
+    /// Inventory contract refused to reserve or release a unit
+    ReservationFailed = 55,
+
+    /// Blood unit belongs to a different bank than the one shipping it
+    UnitFromOtherBank = 56,
+
+    /// Inventory contract refused to dispatch a shipped unit
+    DispatchFailed = 57,
 }
@@ -1,6 +1,11 @@
+// Events keep the tuple-topic `publish` format that indexers already consume.
+#![allow(deprecated)]
+
 use crate::types::{
-    BloodType, RequestCreatedEvent, RequestStatus, RequestStatusChangedEvent, UnitsAssignedEvent,
-    UrgencyLevel,
+    BloodRequest, BloodType, FulfillmentRecord, FulfillmentRecordedEvent, QuotaExceededEvent,
+    QuotaLimit, RequestAmendedEvent, RequestAmendment, RequestCreatedEvent, RequestEscalatedEvent,
+    RequestRejectedEvent, RequestRejection, RequestStatus, RequestStatusChangedEvent,
+    UnitsAssignedEvent, UrgencyEscalation, UrgencyLevel,
 };
 use soroban_sdk::{Address, Env, Symbol, Vec};
 
@@ -14,42 +19,6 @@ use soroban_sdk::{Address, Env, Symbol, Vec};
 /// * `quantity_ml` - Quantity requested in milliliters
 /// * `urgency` - Urgency level of the request
 /// * `required_by` - Timestamp when blood is required
-use crate::types::{BloodType, RequestStatus, UrgencyLevel};
-use soroban_sdk::{Address, Env, Symbol};
-
-/// Event emitted when a blood request is created
-#[soroban_sdk::contracttype]
-#[derive(Clone)]
-pub struct RequestCreatedEvent {
-    pub request_id: u64,
-    pub hospital_id: Address,
-    pub blood_type: BloodType,
-    pub quantity_ml: u32,
-    pub urgency: UrgencyLevel,
-    pub required_by: u64,
-    pub created_at: u64,
-}
-
-/// Event emitted when a request status changes
-#[soroban_sdk::contracttype]
-#[derive(Clone)]
-pub struct RequestStatusChangedEvent {
-    pub request_id: u64,
-    pub old_status: RequestStatus,
-    pub new_status: RequestStatus,
-    pub changed_at: u64,
-}
-
-/// Event emitted when blood units are assigned to a request
-#[soroban_sdk::contracttype]
-#[derive(Clone)]
-pub struct UnitsAssignedEvent {
-    pub request_id: u64,
-    pub assigned_units: soroban_sdk::Vec<u64>,
-    pub assigned_at: u64,
-}
-
-/// Emit a RequestCreated event
 pub fn emit_request_created(
     env: &Env,
     request_id: u64,
@@ -83,8 +52,6 @@ pub fn emit_request_created(
 /// * `old_status` - Previous status
 /// * `new_status` - New status
 pub fn emit_status_changed(
-/// Emit a RequestStatusChanged event
-pub fn emit_request_status_changed(
     env: &Env,
     request_id: u64,
     old_status: RequestStatus,
@@ -109,29 +76,14 @@ pub fn emit_request_status_changed(
 /// * `env` - Contract environment
 /// * `request_id` - ID of the request
 /// * `unit_ids` - IDs of the assigned blood units
-/// * `total_quantity_ml` - Total quantity assigned in milliliters
-#[allow(dead_code)]
-pub fn emit_units_assigned(
-    env: &Env,
-    request_id: u64,
-    unit_ids: Vec<u64>,
-    total_quantity_ml: u32,
-        .publish((Symbol::new(env, "request_status_changed"),), event);
-}
-
-/// Emit an UnitsAssigned event
-pub fn emit_units_assigned(
-    env: &Env,
-    request_id: u64,
-    assigned_units: soroban_sdk::Vec<u64>,
-) {
+/// * `total_quantity_ml` - Combined volume of the assigned units
+pub fn emit_units_assigned(env: &Env, request_id: u64, unit_ids: Vec<u64>, total_quantity_ml: u32) {
     let assigned_at = env.ledger().timestamp();
 
     let event = UnitsAssignedEvent {
         request_id,
         unit_ids,
         total_quantity_ml,
-        assigned_units,
         assigned_at,
     };
 
@@ -139,11 +91,109 @@ pub fn emit_units_assigned(
         .publish((Symbol::new(env, "units_assigned"),), event);
 }
 
+/// Emit a RequestEscalated event when a hospital raises a request's urgency
+///
+/// # Arguments
+/// * `env` - Contract environment
+/// * `request_id` - ID of the escalated request
+/// * `escalation` - The recorded escalation
+pub fn emit_request_escalated(env: &Env, request_id: u64, escalation: &UrgencyEscalation) {
+    let event = RequestEscalatedEvent {
+        request_id,
+        from_urgency: escalation.from_urgency,
+        to_urgency: escalation.to_urgency,
+        reason: escalation.reason.clone(),
+        escalated_at: escalation.escalated_at,
+    };
+
+    env.events()
+        .publish((Symbol::new(env, "request_escalated"),), event);
+}
+
+/// Emit a RequestAmended event so banks see the changed request details
+///
+/// # Arguments
+/// * `env` - Contract environment
+/// * `request` - The request after the amendment
+/// * `amendment` - The recorded amendment
+pub fn emit_request_amended(env: &Env, request: &BloodRequest, amendment: &RequestAmendment) {
+    let event = RequestAmendedEvent {
+        request_id: request.id,
+        hospital_id: request.hospital_id.clone(),
+        amendment: amendment.clone(),
+    };
+
+    env.events()
+        .publish((Symbol::new(env, "request_amended"),), event);
+}
+
+/// Emit a FulfillmentRecorded event when a shipment is delivered
+///
+/// # Arguments
+/// * `env` - Contract environment
+/// * `request` - The request after the shipment was applied
+/// * `fulfillment` - The recorded shipment
+pub fn emit_fulfillment_recorded(
+    env: &Env,
+    request: &BloodRequest,
+    fulfillment: &FulfillmentRecord,
+) {
+    let event = FulfillmentRecordedEvent {
+        request_id: request.id,
+        fulfillment: fulfillment.clone(),
+        fulfilled_quantity_ml: request.fulfilled_quantity_ml,
+        outstanding_quantity_ml: request.outstanding_quantity_ml(),
+    };
+
+    env.events()
+        .publish((Symbol::new(env, "fulfillment_recorded"),), event);
+}
+
+/// Emit a RequestRejected event when a bank or admin declines a request
+///
+/// # Arguments
+/// * `env` - Contract environment
+/// * `request` - The rejected request
+/// * `rejection` - The recorded rejection
+pub fn emit_request_rejected(env: &Env, request: &BloodRequest, rejection: &RequestRejection) {
+    let event = RequestRejectedEvent {
+        request_id: request.id,
+        hospital_id: request.hospital_id.clone(),
+        rejection: rejection.clone(),
+    };
+
+    env.events()
+        .publish((Symbol::new(env, "request_rejected"),), event);
+}
+
+/// Emit a QuotaExceeded event when a critical request is let through over
+/// its hospital's quota and flagged for review
+///
+/// # Arguments
+/// * `env` - Contract environment
+/// * `request` - The flagged request
+/// * `exceeded` - Limits the request went over
+pub fn emit_quota_exceeded(env: &Env, request: &BloodRequest, exceeded: Vec<QuotaLimit>) {
+    let event = QuotaExceededEvent {
+        request_id: request.id,
+        hospital_id: request.hospital_id.clone(),
+        exceeded,
+    };
+
+    env.events()
+        .publish((Symbol::new(env, "quota_exceeded"),), event);
+}
+
 /// Emit a request approved event
 ///
 /// This is a simplified event using just the request ID
 pub fn emit_request_approved(env: &Env, request_id: u64) {
-    emit_status_changed(env, request_id, RequestStatus::Pending, RequestStatus::Approved);
+    emit_status_changed(
+        env,
+        request_id,
+        RequestStatus::Pending,
+        RequestStatus::Approved,
+    );
 }
 
 /// Emit a request cancelled event
@@ -162,7 +212,6 @@ pub fn emit_request_cancelled(env: &Env, request_id: u64, previous_status: Reque
 /// * `env` - Contract environment
 /// * `request_id` - ID of the expired request
 /// * `previous_status` - Status before expiration
-#[allow(dead_code)]
 pub fn emit_request_expired(env: &Env, request_id: u64, previous_status: RequestStatus) {
     emit_status_changed(env, request_id, previous_status, RequestStatus::Expired);
 }
@@ -0,0 +1,95 @@
+//! Client for the inventory contract
+//!
+//! Declares the part of the inventory contract's interface the request
+//! contract calls. The types mirror the inventory contract's and must keep
+//! the same names and fields to decode its values.
+
+use crate::types::BloodType;
+use soroban_sdk::{contractclient, contracttype, Address, Env, Error, Map, String, Symbol};
+
+/// Blood product component
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq, Copy)]
+pub enum BloodComponent {
+    WholeBlood,
+    RedCells,
+    Platelets,
+    Plasma,
+    Cryoprecipitate,
+}
+
+/// Blood unit status in the inventory supply chain
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq, Copy)]
+pub enum BloodStatus {
+    Quarantined,
+    Available,
+    Reserved,
+    InTransit,
+    Delivered,
+    Expired,
+    Discarded,
+    Transfused,
+}
+
+/// Blood unit as stored by the inventory contract
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct BloodUnit {
+    pub id: u64,
+    pub blood_type: BloodType,
+    pub component: BloodComponent,
+    pub din: String,
+    pub product_code: String,
+    pub quantity_ml: u32,
+    pub bank_id: Address,
+    pub donor_id: Option<Address>,
+    pub donation_timestamp: u64,
+    pub expiration_timestamp: u64,
+    pub status: BloodStatus,
+    pub metadata: Map<Symbol, String>,
+}
+
+/// Time-limited hold on a reserved blood unit
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Reservation {
+    pub holder: Address,
+    pub request_id: Option<u64>,
+    pub reserved_at: u64,
+    pub expires_at: u64,
+}
+
+// Only the generated client is used; the trait itself is never implemented here.
+#[allow(dead_code)]
+#[contractclient(name = "InventoryClient")]
+pub trait InventoryInterface {
+    /// Get blood unit details by ID
+    fn get_blood_unit(env: Env, blood_unit_id: u64) -> Result<BloodUnit, Error>;
+
+    /// Check if a blood bank is authorized
+    fn is_bank_authorized(env: Env, bank: Address) -> bool;
+
+    /// Get the reservation on a unit, if any
+    fn get_reservation(env: Env, unit_id: u64) -> Option<Reservation>;
+
+    /// Reserve an available unit for a holder until the reservation lapses
+    fn reserve_unit(
+        env: Env,
+        unit_id: u64,
+        authorized_by: Address,
+        holder: Address,
+        request_id: Option<u64>,
+        duration_seconds: u64,
+    ) -> Result<Reservation, Error>;
+
+    /// Release an active reservation, returning the unit to `Available`
+    fn cancel_reservation(
+        env: Env,
+        unit_id: u64,
+        authorized_by: Address,
+    ) -> Result<BloodUnit, Error>;
+
+    /// Dispatch a reserved unit, moving it to `InTransit`
+    fn dispatch_unit(env: Env, unit_id: u64, authorized_by: Address) -> Result<BloodUnit, Error>;
+}
@@ -2,19 +2,22 @@
 
 mod error;
 mod events;
+mod inventory;
 mod storage;
 mod types;
 mod validation;
 
 pub use crate::error::ContractError;
 pub use crate::types::{
-    BloodRequest, BloodType, DataKey, RequestCreatedEvent, RequestStatus,
-    RequestStatusChangedEvent, UnitsAssignedEvent, UrgencyLevel,
+    BloodRequest, BloodType, DataKey, ExpirySweep, FulfillmentRecord, FulfillmentRecordedEvent,
+    HospitalQuota, HospitalUsage, QuotaExceededEvent, QuotaLimit, RejectionReason,
+    RequestAmendedEvent, RequestAmendment, RequestCreatedEvent, RequestEscalatedEvent,
+    RequestFingerprint, RequestMetadata, RequestRejectedEvent, RequestRejection, RequestStatus,
+    RequestStatusChangedEvent, UnitsAssignedEvent, UrgencyEscalation, UrgencyLevel,
 };
 
-use soroban_sdk::{contract, contractimpl, Address, Env, Map, String, Vec};
-use crate::error::ContractError;
-use crate::types::{BloodRequest, BloodType, RequestMetadata, RequestStatus, UrgencyLevel};
+use crate::inventory::{BloodStatus, BloodUnit, InventoryClient};
+use soroban_sdk::{contract, contractimpl, Address, Env, String, Vec};
 
 // Pagination constants
 const DEFAULT_QUERY_LIMIT: u32 = 50;
@@ -93,13 +96,102 @@ impl RequestContract {
         Ok(())
     }
 
+    /// Set the request limits for a hospital
+    ///
+    /// Replaces any quota already set. `None` fields leave that limit
+    /// unbounded.
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `hospital` - Hospital address
+    /// * `quota` - Limits on open requests, outstanding volume per blood
+    ///   type and requests per rolling 24 hours
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    pub fn set_hospital_quota(
+        env: Env,
+        hospital: Address,
+        quota: HospitalQuota,
+    ) -> Result<(), ContractError> {
+        if !storage::is_initialized(&env) {
+            return Err(ContractError::NotInitialized);
+        }
+
+        let admin = storage::get_admin(&env);
+        admin.require_auth();
+
+        storage::set_hospital_quota(&env, &hospital, &quota);
+
+        Ok(())
+    }
+
+    /// Remove a hospital's quota, leaving it unlimited
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    pub fn remove_hospital_quota(env: Env, hospital: Address) -> Result<(), ContractError> {
+        if !storage::is_initialized(&env) {
+            return Err(ContractError::NotInitialized);
+        }
+
+        let admin = storage::get_admin(&env);
+        admin.require_auth();
+
+        storage::remove_hospital_quota(&env, &hospital);
+
+        Ok(())
+    }
+
+    /// Get the quota configured for a hospital, if any
+    pub fn get_hospital_quota(env: Env, hospital: Address) -> Option<HospitalQuota> {
+        storage::get_hospital_quota(&env, &hospital)
+    }
+
+    /// Get a hospital's current usage against its quota
+    pub fn get_hospital_usage(env: Env, hospital: Address) -> HospitalUsage {
+        Self::hospital_usage(&env, &hospital, env.ledger().timestamp())
+    }
+
+    /// Get the quota limits a request exceeded (empty if it is not flagged)
+    pub fn get_quota_review(env: Env, request_id: u64) -> Vec<QuotaLimit> {
+        storage::get_quota_review(&env, request_id)
+    }
+
+    /// Get the IDs of critical requests accepted over quota, oldest first
+    pub fn get_requests_for_review(env: Env) -> Vec<u64> {
+        storage::get_quota_review_queue(&env)
+    }
+
+    /// Clear a request's quota review flag once an admin has looked at it
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `NotFound`: Request is not flagged for review
+    pub fn clear_quota_review(env: Env, request_id: u64) -> Result<(), ContractError> {
+        if !storage::is_initialized(&env) {
+            return Err(ContractError::NotInitialized);
+        }
+
+        let admin = storage::get_admin(&env);
+        admin.require_auth();
+
+        if storage::get_quota_review(&env, request_id).is_empty() {
+            return Err(ContractError::NotFound);
+        }
+
+        storage::clear_quota_review(&env, request_id);
+
+        Ok(())
+    }
+
     /// Create a new blood request
     ///
     /// # Arguments
     /// * `env` - Contract environment
     /// * `hospital_id` - Hospital requesting blood (must be authorized)
     /// * `blood_type` - Type of blood requested
-    /// * `quantity_ml` - Quantity in milliliters (50-5000ml)
+    /// * `quantity_ml` - Quantity in milliliters (100-10000ml)
     /// * `urgency` - Urgency level (Critical, Urgent, Normal)
     /// * `required_by` - Unix timestamp when blood is required
     /// * `delivery_address` - Address where blood should be delivered
@@ -114,11 +206,17 @@ impl RequestContract {
     /// - `NotInitialized`: Contract not initialized
     /// - `NotAuthorizedHospital`: Hospital is not authorized
     /// - `InvalidQuantity`: Quantity outside acceptable range
-    /// - `InvalidTimestamp`: Required_by timestamp is invalid
-    /// - `InvalidInput`: Delivery address is empty
+    /// - `InvalidRequiredBy`: Required_by is too soon or too far ahead
+    /// - `InvalidDeliveryAddress`: Delivery address is empty
+    /// - `DuplicateRequest`: An open request for the same patient, blood
+    ///   type, quantity and deadline was submitted in the last 24 hours
+    /// - `QuotaExceeded`: Request would go over the hospital's quota (critical
+    ///   requests are accepted and flagged for review instead)
+    /// - `PendingQueueFull`: Pending queue for the urgency level is at capacity
     ///
     /// # Events
     /// Emits `RequestCreated` event with all request details
+    #[allow(clippy::too_many_arguments)]
     pub fn create_request(
         env: Env,
         hospital_id: Address,
@@ -145,25 +243,46 @@ impl RequestContract {
         }
 
         // 4. Validate request parameters
-        validation::validate_request_creation(&env, quantity_ml, required_by)?;
-        validation::validate_delivery_address(&delivery_address)?;
+        validation::validate_request_creation(&env, quantity_ml, required_by, &delivery_address)?;
         validation::validate_blood_type(&blood_type)?;
 
         // 5. Validate urgency-specific time window
         validation::validate_urgency_time_window(&env, required_by, urgency.priority_weight())?;
 
-        // 6. Generate request ID
-        let request_id = storage::increment_request_id(&env);
+        // 6. Reject retried submissions of an open request
         let current_time = env.ledger().timestamp();
+        let fingerprint = storage::fingerprint_hash(
+            &env,
+            &RequestFingerprint {
+                hospital_id: hospital_id.clone(),
+                patient_id: patient_id.clone(),
+                blood_type,
+                quantity_ml,
+                required_by,
+            },
+        );
+        if storage::find_duplicate_request(&env, &fingerprint, current_time).is_some() {
+            return Err(ContractError::DuplicateRequest);
+        }
+
+        // 7. Enforce the hospital's quota; critical requests go through flagged
+        let exceeded =
+            Self::exceeded_quota_limits(&env, &hospital_id, blood_type, quantity_ml, current_time);
+        if !exceeded.is_empty() && urgency != UrgencyLevel::Critical {
+            return Err(ContractError::QuotaExceeded);
+        }
 
-        // 7. Create request metadata
+        // 8. Generate request ID
+        let request_id = storage::increment_request_id(&env);
+
+        // 9. Create request metadata
         let metadata = RequestMetadata {
             patient_id,
             procedure,
             notes,
         };
 
-        // 8. Create blood request
+        // 10. Create blood request
         let request = BloodRequest {
             id: request_id,
             hospital_id: hospital_id.clone(),
@@ -174,24 +293,35 @@ impl RequestContract {
             created_at: current_time,
             required_by,
             fulfilled_at: None,
+            fulfilled_quantity_ml: 0,
             assigned_units: Vec::new(&env),
             delivery_address,
-            metadata: Map::new(&env),
+            metadata,
         };
 
-        // 9. Validate the complete request
+        // 11. Validate the complete request
         request.validate(current_time)?;
 
-        // 10. Store request
+        // 12. Store request and its fingerprint
         storage::set_blood_request(&env, &request);
+        storage::set_fingerprint(&env, &fingerprint, request_id);
 
-        // 11. Update indexes for efficient querying
+        // 13. Update indexes and the hospital's usage counters
         storage::add_to_hospital_index(&env, &request);
         storage::add_to_blood_type_index(&env, &request);
         storage::add_to_status_index(&env, &request);
         storage::add_to_urgency_index(&env, &request);
+        storage::update_pending_queue(&env, None, &request)?;
+        storage::update_hospital_counters(&env, None, &request);
+        storage::record_daily_request(&env, &request);
+
+        // 14. Flag critical requests over quota for review
+        if !exceeded.is_empty() {
+            storage::add_quota_review(&env, &request, &exceeded);
+            events::emit_quota_exceeded(&env, &request, exceeded);
+        }
 
-        // 12. Emit event
+        // 15. Emit event
         events::emit_request_created(
             &env,
             request_id,
@@ -202,7 +332,7 @@ impl RequestContract {
             required_by,
         );
 
-        // 13. Return request ID
+        // 16. Return request ID
         Ok(request_id)
     }
 
@@ -216,9 +346,9 @@ impl RequestContract {
     /// Blood request details
     ///
     /// # Errors
-    /// - `RequestNotFound`: Request with given ID doesn't exist
+    /// - `NotFound`: Request with given ID doesn't exist
     pub fn get_request(env: Env, request_id: u64) -> Result<BloodRequest, ContractError> {
-        storage::get_blood_request(&env, request_id).ok_or(ContractError::RequestNotFound)
+        storage::get_blood_request(&env, request_id).ok_or(ContractError::NotFound)
     }
 
     /// Approve a pending blood request
@@ -253,30 +383,37 @@ impl RequestContract {
         validation::validate_not_expired(&env, request.required_by)?;
 
         // Update status
-        let old_status = request.status;
+        let before = request.clone();
         request.status = RequestStatus::Approved;
 
-        // Save and update index
+        // Save and update index and usage counters
         storage::set_blood_request(&env, &request);
-        storage::update_status_index(&env, request_id, old_status, RequestStatus::Approved);
+        storage::update_status_index(&env, request_id, before.status, RequestStatus::Approved);
+        storage::update_pending_queue(&env, Some(&before), &request)?;
+        storage::update_hospital_counters(&env, Some(&before), &request);
 
         // Emit event
         events::emit_request_approved(&env, request_id);
-        
+
         Ok(())
     }
 
     /// Update request status
     ///
+    /// Requests are closed through their own entrypoints, which release
+    /// assigned units and keep a record: `cancel_request`, `reject_request`
+    /// and `expire_overdue_requests`. This path refuses `Cancelled`,
+    /// `Rejected` and `Expired`.
+    ///
     /// # Arguments
     /// * `env` - Contract environment
     /// * `request_id` - ID of request to update
     /// * `new_status` - New status for the request
     ///
     /// # Errors
-    /// - `RequestNotFound`: Request does not exist
-    /// - `InvalidStatusTransition`: Status transition is not allowed
-    /// - `Unauthorized`: Caller is not authorized
+    /// - `NotFound`: Request does not exist
+    /// - `InvalidStatusTransition`: Status transition is not allowed, or
+    ///   closes the request
     pub fn update_request_status(
         env: Env,
         request_id: u64,
@@ -286,14 +423,19 @@ impl RequestContract {
         admin.require_auth();
 
         // Get existing request
-        let mut request = storage::get_blood_request(&env, request_id)
-            .ok_or(ContractError::RequestNotFound)?;
+        let mut request =
+            storage::get_blood_request(&env, request_id).ok_or(ContractError::NotFound)?;
 
         // Validate status transition
-        if !request.status.can_transition_to(&new_status) {
+        let closes_request = matches!(
+            new_status,
+            RequestStatus::Cancelled | RequestStatus::Rejected | RequestStatus::Expired
+        );
+        if closes_request || !request.status.can_transition_to(&new_status) {
             return Err(ContractError::InvalidStatusTransition);
         }
 
+        let before = request.clone();
         let old_status = request.status;
         request.status = new_status;
 
@@ -302,18 +444,22 @@ impl RequestContract {
             request.fulfilled_at = Some(env.ledger().timestamp());
         }
 
-        // Store updated request
+        // Store updated request, move it between status indexes and update usage
         storage::set_blood_request(&env, &request);
+        storage::update_status_index(&env, request_id, old_status, new_status);
+        storage::update_pending_queue(&env, Some(&before), &request)?;
+        storage::update_hospital_counters(&env, Some(&before), &request);
 
         // Emit event
-        events::emit_request_status_changed(&env, request_id, old_status, new_status);
+        events::emit_status_changed(&env, request_id, old_status, new_status);
 
         Ok(())
     }
 
     /// Cancel a blood request
     ///
-    /// Can be called by the hospital that created the request or by admin
+    /// Can be called by the hospital that created the request or by admin.
+    /// Assigned units that haven't shipped are released in the inventory.
     ///
     /// # Arguments
     /// * `env` - Contract environment
@@ -324,6 +470,7 @@ impl RequestContract {
     /// - `NotFound`: Request doesn't exist
     /// - `CannotCancelRequest`: Request cannot be cancelled in current state
     /// - `Unauthorized`: Caller is not hospital owner or admin
+    /// - `ReservationFailed`: The inventory contract refused to release a unit
     pub fn cancel_request(env: Env, request_id: u64, caller: Address) -> Result<(), ContractError> {
         caller.require_auth();
 
@@ -346,13 +493,23 @@ impl RequestContract {
             return Err(ContractError::CannotCancelRequest);
         }
 
+        if request.has_assigned_units() {
+            if let Some(inventory) = storage::get_inventory_contract(&env) {
+                let inventory = InventoryClient::new(&env, &inventory);
+                Self::release_assigned_units(&env, &inventory, &request)?;
+            }
+        }
+
         // Update status
+        let before = request.clone();
         let old_status = request.status;
         request.status = RequestStatus::Cancelled;
 
-        // Save and update index
+        // Save and update index and usage counters
         storage::set_blood_request(&env, &request);
         storage::update_status_index(&env, request_id, old_status, RequestStatus::Cancelled);
+        storage::update_pending_queue(&env, Some(&before), &request)?;
+        storage::update_hospital_counters(&env, Some(&before), &request);
 
         // Emit event
         events::emit_request_cancelled(&env, request_id, old_status);
@@ -360,7 +517,337 @@ impl RequestContract {
         Ok(())
     }
 
-    /// Assign blood units to a request
+    /// Decline a pending or approved request
+    ///
+    /// Can be called by the admin or by any bank authorized in the inventory
+    /// contract. Units already assigned to the request are released.
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `request_id` - ID of the request to reject
+    /// * `rejector` - Bank or admin declining the request
+    /// * `reason_code` - Structured reason for the rejection
+    /// * `note` - Explanation for the hospital (required for `Other`)
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `NotFound`: Request doesn't exist
+    /// - `Unauthorized`: Rejector is neither admin nor an authorized bank
+    /// - `InvalidStatusTransition`: Request is not `Pending` or `Approved`
+    /// - `InvalidInput`: Reason is `Other` and the note is empty
+    ///
+    /// # Events
+    /// Emits `request_rejected` with the reason and a status change event
+    pub fn reject_request(
+        env: Env,
+        request_id: u64,
+        rejector: Address,
+        reason_code: RejectionReason,
+        note: String,
+    ) -> Result<(), ContractError> {
+        rejector.require_auth();
+
+        if !storage::is_initialized(&env) {
+            return Err(ContractError::NotInitialized);
+        }
+
+        let mut request =
+            storage::get_blood_request(&env, request_id).ok_or(ContractError::NotFound)?;
+
+        let inventory = storage::get_inventory_contract(&env)
+            .map(|inventory| InventoryClient::new(&env, &inventory));
+        let is_bank = inventory
+            .as_ref()
+            .is_some_and(|inventory| inventory.is_bank_authorized(&rejector));
+        if rejector != storage::get_admin(&env) && !is_bank {
+            return Err(ContractError::Unauthorized);
+        }
+
+        if !request.status.can_transition_to(&RequestStatus::Rejected) {
+            return Err(ContractError::InvalidStatusTransition);
+        }
+
+        if reason_code == RejectionReason::Other && note.is_empty() {
+            return Err(ContractError::InvalidInput);
+        }
+
+        if request.has_assigned_units() {
+            if let Some(inventory) = inventory.as_ref() {
+                Self::release_assigned_units(&env, inventory, &request)?;
+            }
+        }
+
+        let before = request.clone();
+        let old_status = request.status;
+        request.status = RequestStatus::Rejected;
+
+        let rejection = RequestRejection {
+            rejected_by: rejector,
+            reason: reason_code,
+            note,
+            rejected_at: env.ledger().timestamp(),
+        };
+
+        storage::set_blood_request(&env, &request);
+        storage::update_status_index(&env, request_id, old_status, RequestStatus::Rejected);
+        storage::update_pending_queue(&env, Some(&before), &request)?;
+        storage::update_hospital_counters(&env, Some(&before), &request);
+        storage::set_rejection(&env, &request, &rejection);
+
+        events::emit_request_rejected(&env, &request, &rejection);
+        events::emit_status_changed(&env, request_id, old_status, RequestStatus::Rejected);
+
+        Ok(())
+    }
+
+    /// Get the rejection details of a declined request, if any
+    pub fn get_request_rejection(env: Env, request_id: u64) -> Option<RequestRejection> {
+        storage::get_rejection(&env, request_id)
+    }
+
+    /// Raise the urgency of an open request
+    ///
+    /// Only the hospital that created the request can escalate it. The
+    /// request moves to the new urgency bucket and is re-ranked in
+    /// `query_pending_requests`.
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `request_id` - ID of request to escalate
+    /// * `new_urgency` - New urgency (must be higher than the current one)
+    /// * `reason` - Why the urgency is raised (e.g. patient deteriorated)
+    ///
+    /// # Errors
+    /// - `NotFound`: Request does not exist
+    /// - `RequestAlreadyTerminal`: Request is completed, cancelled or expired
+    /// - `InvalidUrgency`: New urgency is not higher than the current one
+    /// - `InvalidInput`: Reason is empty
+    /// - `RequestExpired`: Request's `required_by` has passed
+    /// - `InvalidRequiredBy`: Time left is too short for the new urgency
+    /// - `PendingQueueFull`: Pending queue for the new urgency is at capacity
+    pub fn escalate_request(
+        env: Env,
+        request_id: u64,
+        new_urgency: UrgencyLevel,
+        reason: String,
+    ) -> Result<(), ContractError> {
+        let mut request =
+            storage::get_blood_request(&env, request_id).ok_or(ContractError::NotFound)?;
+
+        // Only the owning hospital can escalate
+        request.hospital_id.require_auth();
+
+        if request.status.is_terminal() {
+            return Err(ContractError::RequestAlreadyTerminal);
+        }
+
+        if !new_urgency.is_higher_than(&request.urgency) {
+            return Err(ContractError::InvalidUrgency);
+        }
+
+        if reason.is_empty() {
+            return Err(ContractError::InvalidInput);
+        }
+
+        validation::validate_not_expired(&env, request.required_by)?;
+        validation::validate_urgency_time_window(
+            &env,
+            request.required_by,
+            new_urgency.priority_weight(),
+        )?;
+
+        let escalation = UrgencyEscalation {
+            from_urgency: request.urgency,
+            to_urgency: new_urgency,
+            reason,
+            escalated_at: env.ledger().timestamp(),
+        };
+
+        let before = request.clone();
+        request.urgency = new_urgency;
+        storage::set_blood_request(&env, &request);
+        storage::update_urgency_index(&env, request_id, escalation.from_urgency, new_urgency);
+        storage::update_pending_queue(&env, Some(&before), &request)?;
+        storage::add_escalation(&env, &request, &escalation);
+
+        events::emit_request_escalated(&env, request_id, &escalation);
+
+        Ok(())
+    }
+
+    /// Get every urgency escalation recorded for a request, oldest first
+    pub fn get_request_escalations(env: Env, request_id: u64) -> Vec<UrgencyEscalation> {
+        storage::get_escalations(&env, request_id)
+    }
+
+    /// Amend the details of a pending request
+    ///
+    /// Only the owning hospital can amend, and only while the request is
+    /// still `Pending`. The new values go through the same validation as
+    /// `create_request` and the old and new values are kept as an amendment
+    /// record.
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `request_id` - ID of request to amend
+    /// * `blood_type` - New blood type
+    /// * `quantity_ml` - New quantity in milliliters (100-10000ml)
+    /// * `required_by` - New deadline
+    /// * `delivery_address` - New delivery address
+    ///
+    /// # Errors
+    /// - `NotFound`: Request does not exist
+    /// - `NotAuthorizedHospital`: Hospital is no longer authorized
+    /// - `InvalidRequestState`: Request is not `Pending`
+    /// - `InvalidInput`: None of the values differ from the current ones
+    /// - `InvalidQuantity`: Quantity outside acceptable range
+    /// - `InvalidRequiredBy`: Required_by is too soon or too far ahead
+    /// - `InvalidDeliveryAddress`: Delivery address is empty
+    /// - `DuplicateRequest`: The amended request matches another open request
+    /// - `QuotaExceeded`: The amendment takes the hospital over its
+    ///   outstanding volume quota (critical requests are flagged instead)
+    ///
+    /// # Events
+    /// Emits `request_amended` with the old and new values, plus
+    /// `quota_exceeded` when a critical request is flagged
+    pub fn amend_request(
+        env: Env,
+        request_id: u64,
+        blood_type: BloodType,
+        quantity_ml: u32,
+        required_by: u64,
+        delivery_address: String,
+    ) -> Result<(), ContractError> {
+        let mut request =
+            storage::get_blood_request(&env, request_id).ok_or(ContractError::NotFound)?;
+
+        // Only the owning hospital can amend
+        request.hospital_id.require_auth();
+
+        if !storage::is_authorized_hospital(&env, &request.hospital_id) {
+            return Err(ContractError::NotAuthorizedHospital);
+        }
+
+        if request.status != RequestStatus::Pending {
+            return Err(ContractError::InvalidRequestState);
+        }
+
+        if blood_type == request.blood_type
+            && quantity_ml == request.quantity_ml
+            && required_by == request.required_by
+            && delivery_address == request.delivery_address
+        {
+            return Err(ContractError::InvalidInput);
+        }
+
+        validation::validate_request_creation(&env, quantity_ml, required_by, &delivery_address)?;
+        validation::validate_urgency_time_window(
+            &env,
+            required_by,
+            request.urgency.priority_weight(),
+        )?;
+
+        let current_time = env.ledger().timestamp();
+        let before = request.clone();
+        let old_fingerprint = storage::fingerprint_hash(&env, &request.fingerprint());
+        let amendment = RequestAmendment {
+            old_blood_type: request.blood_type,
+            new_blood_type: blood_type,
+            old_quantity_ml: request.quantity_ml,
+            new_quantity_ml: quantity_ml,
+            old_required_by: request.required_by,
+            new_required_by: required_by,
+            old_delivery_address: request.delivery_address.clone(),
+            new_delivery_address: delivery_address.clone(),
+            amended_at: current_time,
+        };
+
+        request.blood_type = blood_type;
+        request.quantity_ml = quantity_ml;
+        request.required_by = required_by;
+        request.delivery_address = delivery_address;
+        request.validate(current_time)?;
+
+        let new_fingerprint = storage::fingerprint_hash(&env, &request.fingerprint());
+        if new_fingerprint != old_fingerprint {
+            if storage::find_duplicate_request(&env, &new_fingerprint, current_time).is_some() {
+                return Err(ContractError::DuplicateRequest);
+            }
+            storage::remove_fingerprint(&env, &old_fingerprint, request_id);
+            storage::set_fingerprint(&env, &new_fingerprint, request_id);
+        }
+
+        // Extra volume counts against the quota like a new request would
+        let over_volume = Self::amendment_exceeds_volume(&env, &before, &request);
+        if over_volume && request.urgency != UrgencyLevel::Critical {
+            return Err(ContractError::QuotaExceeded);
+        }
+
+        storage::set_blood_request(&env, &request);
+        storage::update_hospital_counters(&env, Some(&before), &request);
+        storage::update_pending_queue(&env, Some(&before), &request)?;
+        if over_volume {
+            let mut exceeded = storage::get_quota_review(&env, request_id);
+            if !exceeded.contains(QuotaLimit::OutstandingVolume) {
+                exceeded.push_back(QuotaLimit::OutstandingVolume);
+            }
+            storage::add_quota_review(&env, &request, &exceeded);
+            events::emit_quota_exceeded(&env, &request, exceeded);
+        }
+        if amendment.old_blood_type != blood_type {
+            storage::update_blood_type_index(
+                &env,
+                request_id,
+                amendment.old_blood_type,
+                blood_type,
+            );
+        }
+        storage::add_amendment(&env, &request, &amendment);
+
+        events::emit_request_amended(&env, &request, &amendment);
+
+        Ok(())
+    }
+
+    /// Get every amendment recorded for a request, oldest first
+    pub fn get_request_amendments(env: Env, request_id: u64) -> Vec<RequestAmendment> {
+        storage::get_amendments(&env, request_id)
+    }
+
+    /// Set the inventory contract that holds the blood units
+    ///
+    /// The inventory contract must in turn list this contract as its request
+    /// contract so it can reserve and release units.
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    pub fn set_inventory_contract(env: Env, inventory: Address) -> Result<(), ContractError> {
+        if !storage::is_initialized(&env) {
+            return Err(ContractError::NotInitialized);
+        }
+
+        let admin = storage::get_admin(&env);
+        admin.require_auth();
+
+        storage::set_inventory_contract(&env, &inventory);
+
+        Ok(())
+    }
+
+    /// Get the configured inventory contract, if any
+    pub fn get_inventory_contract(env: Env) -> Option<Address> {
+        storage::get_inventory_contract(&env)
+    }
+
+    /// Assign blood units to an approved request, reserving them in the inventory
+    ///
+    /// Every unit must exist, be unexpired, be `Available` (or hold a
+    /// reservation that has lapsed), be ABO/Rh compatible with the
+    /// requested blood type under the rules for its component and not be
+    /// assigned to another open request. Together the units must cover the
+    /// requested volume. Units are reserved for the requesting hospital; if
+    /// any check fails, nothing is assigned or reserved. Units from a
+    /// previous assignment are released first.
     ///
     /// # Arguments
     /// * `env` - Contract environment
@@ -368,19 +855,61 @@ impl RequestContract {
     /// * `unit_ids` - Vector of blood unit IDs to assign
     ///
     /// # Errors
-    /// - `RequestNotFound`: Request does not exist
-    /// - `Unauthorized`: Caller is not authorized
+    /// - `InventoryNotConfigured`: No inventory contract set
+    /// - `NotFound`: Request does not exist
+    /// - `RequestNotApproved`: Request is not `Approved`
+    /// - `InvalidInput`: A unit ID is repeated
+    /// - `UnitsAlreadyAssigned`: A unit is assigned to another open request
+    /// - `UnitNotFound`: A unit does not exist in the inventory
+    /// - `UnitExpired`: A unit is past its expiration
+    /// - `UnitNotAvailable`: A unit is neither `Available` nor under a lapsed
+    ///   reservation
+    /// - `IncompatibleBloodType`: A unit can't be given to the requested type
+    /// - `InsufficientQuantity`: Units don't cover the requested volume
+    /// - `ReservationFailed`: The inventory contract refused a reservation
     pub fn assign_blood_units(
         env: Env,
         request_id: u64,
-        unit_ids: soroban_sdk::Vec<u64>,
+        unit_ids: Vec<u64>,
     ) -> Result<(), ContractError> {
         let admin = storage::get_admin(&env);
         admin.require_auth();
 
+        let inventory =
+            storage::get_inventory_contract(&env).ok_or(ContractError::InventoryNotConfigured)?;
+        let inventory = InventoryClient::new(&env, &inventory);
+
         // Get existing request
-        let mut request = storage::get_blood_request(&env, request_id)
-            .ok_or(ContractError::RequestNotFound)?;
+        let mut request =
+            storage::get_blood_request(&env, request_id).ok_or(ContractError::NotFound)?;
+
+        if request.status != RequestStatus::Approved {
+            return Err(ContractError::RequestNotApproved);
+        }
+
+        for (i, unit_id) in unit_ids.iter().enumerate() {
+            if unit_ids.first_index_of(unit_id) != Some(i as u32) {
+                return Err(ContractError::InvalidInput);
+            }
+        }
+
+        // Release the previous assignment so its units can be reassigned
+        Self::release_assigned_units(&env, &inventory, &request)?;
+
+        // Verify every unit before reserving any
+        let mut total_quantity_ml: u32 = 0;
+        for unit_id in unit_ids.iter() {
+            let unit = Self::check_unit_for_request(&env, &inventory, &request, unit_id)?;
+            total_quantity_ml = total_quantity_ml.saturating_add(unit.quantity_ml);
+        }
+
+        if total_quantity_ml < request.quantity_ml {
+            return Err(ContractError::InsufficientQuantity);
+        }
+
+        for unit_id in unit_ids.iter() {
+            Self::reserve_for_request(&env, &inventory, &request, unit_id)?;
+        }
 
         // Assign units
         request.assigned_units = unit_ids.clone();
@@ -389,11 +918,161 @@ impl RequestContract {
         storage::set_blood_request(&env, &request);
 
         // Emit event
-        events::emit_units_assigned(&env, request_id, unit_ids);
+        events::emit_units_assigned(&env, request_id, unit_ids, total_quantity_ml);
 
         Ok(())
     }
 
+    /// Record a shipment delivered against an approved request
+    ///
+    /// Large requests are often served by several banks in several
+    /// shipments. Each shipment adds its volume to the request; the request
+    /// stays `PartiallyFulfilled` until the delivered volume covers the
+    /// requested quantity and then moves to `Fulfilled`.
+    ///
+    /// Units already assigned to the request are shipped as they are; other
+    /// units go through the same checks as `assign_blood_units` and are
+    /// reserved for the request. Every shipped unit is then dispatched in
+    /// the inventory, moving it from `Reserved` to `InTransit` so its
+    /// reservation can't lapse and return it to stock.
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `request_id` - ID of the request being served
+    /// * `bank_id` - Bank supplying the shipment (must own every unit)
+    /// * `unit_ids` - Units in the shipment
+    ///
+    /// # Errors
+    /// - `InventoryNotConfigured`: No inventory contract set
+    /// - `NotFound`: Request does not exist
+    /// - `RequestNotApproved`: Request is still `Pending`
+    /// - `InvalidRequestState`: Request is already fulfilled or closed
+    /// - `RequestExpired`: Request's `required_by` has passed
+    /// - `InvalidInput`: No units, duplicate units, or a unit already shipped
+    /// - `UnitFromOtherBank`: A unit belongs to another bank
+    /// - `DispatchFailed`: The inventory contract refused to dispatch a unit
+    /// - Unit check errors from `assign_blood_units`
+    ///
+    /// # Events
+    /// Emits `fulfillment_recorded`, plus a status change when the request
+    /// moves to `PartiallyFulfilled` or `Fulfilled`
+    pub fn record_fulfillment(
+        env: Env,
+        request_id: u64,
+        bank_id: Address,
+        unit_ids: Vec<u64>,
+    ) -> Result<(), ContractError> {
+        bank_id.require_auth();
+
+        let inventory =
+            storage::get_inventory_contract(&env).ok_or(ContractError::InventoryNotConfigured)?;
+        let inventory = InventoryClient::new(&env, &inventory);
+
+        let mut request =
+            storage::get_blood_request(&env, request_id).ok_or(ContractError::NotFound)?;
+
+        match request.status {
+            RequestStatus::Pending => return Err(ContractError::RequestNotApproved),
+            RequestStatus::Approved | RequestStatus::PartiallyFulfilled => {}
+            _ => return Err(ContractError::InvalidRequestState),
+        }
+
+        validation::validate_not_expired(&env, request.required_by)?;
+
+        if unit_ids.is_empty() {
+            return Err(ContractError::InvalidInput);
+        }
+        for (i, unit_id) in unit_ids.iter().enumerate() {
+            if unit_ids.first_index_of(unit_id) != Some(i as u32) {
+                return Err(ContractError::InvalidInput);
+            }
+        }
+
+        // Verify every unit before reserving any
+        let mut quantity_ml: u32 = 0;
+        let mut to_reserve = Vec::new(&env);
+        for unit_id in unit_ids.iter() {
+            if storage::is_unit_shipped(&env, request_id, unit_id) {
+                return Err(ContractError::InvalidInput);
+            }
+
+            let unit = if storage::get_unit_assignment(&env, unit_id) == Some(request_id) {
+                // Already reserved for this request by an earlier assignment
+                let unit = match inventory.try_get_blood_unit(&unit_id) {
+                    Ok(Ok(unit)) => unit,
+                    _ => return Err(ContractError::UnitNotFound),
+                };
+                if env.ledger().timestamp() >= unit.expiration_timestamp {
+                    return Err(ContractError::UnitExpired);
+                }
+                unit
+            } else {
+                to_reserve.push_back(unit_id);
+                Self::check_unit_for_request(&env, &inventory, &request, unit_id)?
+            };
+
+            if unit.bank_id != bank_id {
+                return Err(ContractError::UnitFromOtherBank);
+            }
+
+            quantity_ml = quantity_ml.saturating_add(unit.quantity_ml);
+        }
+
+        for unit_id in to_reserve.iter() {
+            Self::reserve_for_request(&env, &inventory, &request, unit_id)?;
+            request.assigned_units.push_back(unit_id);
+        }
+
+        let this = env.current_contract_address();
+        for unit_id in unit_ids.iter() {
+            if !matches!(inventory.try_dispatch_unit(&unit_id, &this), Ok(Ok(_))) {
+                return Err(ContractError::DispatchFailed);
+            }
+        }
+
+        let delivered_at = env.ledger().timestamp();
+        let before = request.clone();
+        let old_status = request.status;
+        request.fulfilled_quantity_ml = request.fulfilled_quantity_ml.saturating_add(quantity_ml);
+        if request.outstanding_quantity_ml() == 0 {
+            request.status = RequestStatus::Fulfilled;
+            request.fulfilled_at = Some(delivered_at);
+        } else {
+            request.status = RequestStatus::PartiallyFulfilled;
+        }
+
+        let fulfillment = FulfillmentRecord {
+            bank_id,
+            unit_ids,
+            quantity_ml,
+            delivered_at,
+        };
+
+        storage::set_blood_request(&env, &request);
+        if request.status != old_status {
+            storage::update_status_index(&env, request_id, old_status, request.status);
+        }
+        storage::update_hospital_counters(&env, Some(&before), &request);
+        storage::add_fulfillment(&env, &request, &fulfillment);
+
+        events::emit_fulfillment_recorded(&env, &request, &fulfillment);
+        if request.status != old_status {
+            events::emit_status_changed(&env, request_id, old_status, request.status);
+        }
+
+        Ok(())
+    }
+
+    /// Get every shipment recorded for a request, oldest first
+    pub fn get_request_fulfillments(env: Env, request_id: u64) -> Vec<FulfillmentRecord> {
+        storage::get_fulfillments(&env, request_id)
+    }
+
+    /// Get the request a blood unit is assigned to, if any
+    pub fn get_unit_assignment(env: Env, unit_id: u64) -> Option<u64> {
+        storage::get_unit_assignment(&env, unit_id)
+    }
+
     /// Get all requests for a specific hospital
     ///
     /// # Arguments
@@ -454,6 +1133,123 @@ impl RequestContract {
         storage::is_authorized_hospital(&env, &hospital)
     }
 
+    /// Extend the storage TTL of a blood request
+    ///
+    /// Permissionless, so keepers can keep request records alive for audits.
+    /// Open requests get the active TTL; terminal ones the longer archive TTL.
+    ///
+    /// # Errors
+    /// - `NotFound`: Request doesn't exist
+    pub fn extend_request_ttl(env: Env, request_id: u64) -> Result<(), ContractError> {
+        let request =
+            storage::get_blood_request(&env, request_id).ok_or(ContractError::NotFound)?;
+
+        storage::extend_request_ttl(&env, &request);
+        storage::extend_instance_ttl(&env);
+
+        Ok(())
+    }
+
+    /// Expire open requests whose `required_by` has passed
+    ///
+    /// Permissionless, so keepers can run it on a schedule. Walks the
+    /// `Pending`, `Approved` and `PartiallyFulfilled` status indexes from
+    /// `cursor`, reading at most `max` requests (capped at
+    /// `MAX_EXPIRY_BATCH`). Overdue ones move to `Expired` and their
+    /// assigned units are released in the inventory. Call again with
+    /// `next_cursor` until it is None.
+    ///
+    /// The cursor is a position across the three indexes, so requests that
+    /// change status between calls can shift it; a sweep started from 0
+    /// picks up anything missed.
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `cursor` - Position to start from (0 for a new sweep, then `next_cursor`)
+    /// * `max` - Maximum number of requests to read
+    ///
+    /// # Returns
+    /// IDs of the requests that were expired and the cursor to continue from
+    ///
+    /// # Errors
+    /// - `NotInitialized`: Contract not initialized
+    /// - `ReservationFailed`: The inventory contract refused to release a unit
+    ///
+    /// # Events
+    /// Emits a status change to `Expired` for each expired request
+    pub fn expire_overdue_requests(
+        env: Env,
+        cursor: u32,
+        max: u32,
+    ) -> Result<ExpirySweep, ContractError> {
+        if !storage::is_initialized(&env) {
+            return Err(ContractError::NotInitialized);
+        }
+
+        let limit = max.min(storage::MAX_EXPIRY_BATCH);
+        let current_time = env.ledger().timestamp();
+        let mut expired = Vec::new(&env);
+        let mut read = 0;
+        // Position of the next unread request; expired requests leave the
+        // indexes, so only requests that stay open move it forward
+        let mut position = cursor;
+        let mut skip = cursor;
+
+        for status in [
+            RequestStatus::Pending,
+            RequestStatus::Approved,
+            RequestStatus::PartiallyFulfilled,
+        ] {
+            let request_ids = storage::get_requests_by_status(&env, status);
+            if skip >= request_ids.len() {
+                skip -= request_ids.len();
+                continue;
+            }
+
+            for request_id in request_ids.slice(skip..).iter() {
+                if read >= limit {
+                    return Ok(ExpirySweep {
+                        expired,
+                        next_cursor: Some(position),
+                    });
+                }
+                read += 1;
+
+                let Some(mut request) = storage::get_blood_request(&env, request_id) else {
+                    position += 1;
+                    continue;
+                };
+                if !request.is_expired(current_time) {
+                    position += 1;
+                    continue;
+                }
+
+                if request.has_assigned_units() {
+                    if let Some(inventory) = storage::get_inventory_contract(&env) {
+                        let inventory = InventoryClient::new(&env, &inventory);
+                        Self::release_assigned_units(&env, &inventory, &request)?;
+                    }
+                }
+
+                let before = request.clone();
+                request.status = RequestStatus::Expired;
+                storage::set_blood_request(&env, &request);
+                storage::update_status_index(&env, request_id, status, RequestStatus::Expired);
+                storage::update_pending_queue(&env, Some(&before), &request)?;
+                storage::update_hospital_counters(&env, Some(&before), &request);
+
+                events::emit_request_expired(&env, request_id, status);
+                expired.push_back(request_id);
+            }
+            skip = 0;
+        }
+
+        Ok(ExpirySweep {
+            expired,
+            next_cursor: None,
+        })
+    }
+
     // ========== Advanced Query Functions ==========
 
     /// Get a blood request by ID
@@ -488,22 +1284,24 @@ impl RequestContract {
     ) -> Vec<BloodRequest> {
         // Get all request IDs for this hospital
         let request_ids = storage::get_requests_by_hospital(&env, &hospital_id);
-        
+
         // Load full request objects
         let mut requests = Self::load_requests_from_ids(&env, request_ids);
-        
+
         // Apply status filter if provided
         if let Some(status) = status_filter {
-            requests = requests.into_iter()
-                .filter(|r| r.status == status)
-                .collect();
+            requests = Self::filter_by_status(&env, requests, status);
         }
-        
+
         // Apply pagination
         Self::apply_pagination(requests, limit, offset)
     }
 
-    /// Query all pending requests across hospitals, sorted by urgency
+    /// Query all pending requests across hospitals in priority order
+    ///
+    /// Reads a page of the pending queue, which is kept ordered by urgency
+    /// (Critical > Urgent > Normal), then `required_by`, then `created_at`.
+    /// Each urgency level is queued in its own shard of bounded pages.
     ///
     /// # Arguments
     /// * `env` - Contract environment
@@ -511,22 +1309,78 @@ impl RequestContract {
     /// * `offset` - Number of results to skip
     ///
     /// # Returns
-    /// Vector of pending requests, sorted by urgency (Critical > Urgent > Normal)
+    /// Vector of pending requests in priority order
     pub fn query_pending_requests(
         env: Env,
         limit: Option<u32>,
         offset: Option<u32>,
     ) -> Vec<BloodRequest> {
-        // Get all pending request IDs
-        let request_ids = storage::get_requests_by_status(&env, RequestStatus::Pending);
-        
-        // Load full request objects
+        let request_ids = storage::get_pending_request_ids(
+            &env,
+            offset.unwrap_or(0),
+            limit.unwrap_or(DEFAULT_QUERY_LIMIT).min(MAX_QUERY_LIMIT),
+        );
+        Self::load_requests_from_ids(&env, request_ids)
+    }
+
+    /// Get the `n` pending requests to serve next, in priority order
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `n` - Number of requests (max 200)
+    pub fn next_requests(env: Env, n: u32) -> Vec<BloodRequest> {
+        Self::query_pending_requests(env, Some(n), None)
+    }
+
+    /// Query requests that have received some but not all of their volume
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `limit` - Maximum number of results (defaults to 50, max 200)
+    /// * `offset` - Number of results to skip
+    ///
+    /// # Returns
+    /// Vector of partially fulfilled requests, sorted by urgency
+    pub fn query_partially_fulfilled(
+        env: Env,
+        limit: Option<u32>,
+        offset: Option<u32>,
+    ) -> Vec<BloodRequest> {
+        let request_ids = storage::get_requests_by_status(&env, RequestStatus::PartiallyFulfilled);
         let mut requests = Self::load_requests_from_ids(&env, request_ids);
-        
-        // Sort by urgency (Critical > Urgent > Normal)
         Self::sort_requests_by_urgency(&mut requests);
-        
-        // Apply pagination
+        Self::apply_pagination(requests, limit, offset)
+    }
+
+    /// Query rejected requests, optionally only those rejected for one reason
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `reason` - Optional rejection reason filter
+    /// * `limit` - Maximum number of results (defaults to 50, max 200)
+    /// * `offset` - Number of results to skip
+    ///
+    /// # Returns
+    /// Vector of rejected requests in rejection order
+    pub fn query_rejected_requests(
+        env: Env,
+        reason: Option<RejectionReason>,
+        limit: Option<u32>,
+        offset: Option<u32>,
+    ) -> Vec<BloodRequest> {
+        let mut request_ids = Vec::new(&env);
+        for request_id in storage::get_requests_by_status(&env, RequestStatus::Rejected).iter() {
+            let matches = match reason {
+                Some(reason) => storage::get_rejection(&env, request_id)
+                    .is_some_and(|rejection| rejection.reason == reason),
+                None => true,
+            };
+            if matches {
+                request_ids.push_back(request_id);
+            }
+        }
+
+        let requests = Self::load_requests_from_ids(&env, request_ids);
         Self::apply_pagination(requests, limit, offset)
     }
 
@@ -557,15 +1411,7 @@ impl RequestContract {
             // If no status filter, we need to check all statuses
             // This is less efficient but necessary without a date index
             let mut all_ids = Vec::new(&env);
-            for status in [
-                RequestStatus::Pending,
-                RequestStatus::Approved,
-                RequestStatus::Fulfilled,
-                RequestStatus::InDelivery,
-                RequestStatus::Completed,
-                RequestStatus::Cancelled,
-                RequestStatus::Expired,
-            ] {
+            for status in RequestStatus::ALL {
                 let ids = storage::get_requests_by_status(&env, status);
                 for id in ids.iter() {
                     all_ids.push_back(id);
@@ -573,13 +1419,15 @@ impl RequestContract {
             }
             all_ids
         };
-        
+
         // Load requests and filter by date range
-        let requests = Self::load_requests_from_ids(&env, request_ids)
-            .into_iter()
-            .filter(|r| r.created_at >= start_time && r.created_at <= end_time)
-            .collect();
-        
+        let mut requests = Vec::new(&env);
+        for request in Self::load_requests_from_ids(&env, request_ids).iter() {
+            if request.created_at >= start_time && request.created_at <= end_time {
+                requests.push_back(request);
+            }
+        }
+
         // Apply pagination
         Self::apply_pagination(requests, limit, offset)
     }
@@ -595,7 +1443,11 @@ impl RequestContract {
     ///
     /// # Returns
     /// Vector of requests matching the urgency and optional status
-    pub fn query_requests_by_urgency_and_status(
+    ///
+    /// Named `query_by_urgency_and_status` rather than
+    /// `query_requests_by_urgency_and_status` because contract function
+    /// names are limited to 32 characters.
+    pub fn query_by_urgency_and_status(
         env: Env,
         urgency: UrgencyLevel,
         status_filter: Option<RequestStatus>,
@@ -604,23 +1456,220 @@ impl RequestContract {
     ) -> Vec<BloodRequest> {
         // Get request IDs by urgency
         let request_ids = storage::get_requests_by_urgency(&env, urgency);
-        
+
         // Load full request objects
         let mut requests = Self::load_requests_from_ids(&env, request_ids);
-        
+
         // Apply status filter if provided
         if let Some(status) = status_filter {
-            requests = requests.into_iter()
-                .filter(|r| r.status == status)
-                .collect();
+            requests = Self::filter_by_status(&env, requests, status);
         }
-        
+
         // Apply pagination
         Self::apply_pagination(requests, limit, offset)
     }
 
     // ========== Helper Functions ==========
 
+    /// Whether a unit is assigned to a different request that is still open
+    ///
+    /// Assignments left behind by completed, cancelled or expired requests
+    /// don't block the unit.
+    fn is_assigned_elsewhere(env: &Env, unit_id: u64, request_id: u64) -> bool {
+        match storage::get_unit_assignment(env, unit_id) {
+            Some(other_id) if other_id != request_id => storage::get_blood_request(env, other_id)
+                .is_some_and(|other| !other.status.is_terminal()),
+            _ => false,
+        }
+    }
+
+    /// Check that a unit can be assigned to a request
+    ///
+    /// The unit must exist, not be expired, be available, be compatible with
+    /// the requested blood type for its component and not be held by another
+    /// open request. A reserved unit whose reservation has lapsed counts as
+    /// available; `reserve_unit` releases the stale reservation.
+    fn check_unit_for_request(
+        env: &Env,
+        inventory: &InventoryClient,
+        request: &BloodRequest,
+        unit_id: u64,
+    ) -> Result<BloodUnit, ContractError> {
+        if Self::is_assigned_elsewhere(env, unit_id, request.id) {
+            return Err(ContractError::UnitsAlreadyAssigned);
+        }
+
+        let unit = match inventory.try_get_blood_unit(&unit_id) {
+            Ok(Ok(unit)) => unit,
+            _ => return Err(ContractError::UnitNotFound),
+        };
+
+        if env.ledger().timestamp() >= unit.expiration_timestamp {
+            return Err(ContractError::UnitExpired);
+        }
+        let available = match unit.status {
+            BloodStatus::Available => true,
+            BloodStatus::Reserved => Self::has_lapsed_reservation(env, inventory, unit_id),
+            _ => false,
+        };
+        if !available {
+            return Err(ContractError::UnitNotAvailable);
+        }
+        if !unit
+            .blood_type
+            .can_donate_component_to(unit.component, &request.blood_type)
+        {
+            return Err(ContractError::IncompatibleBloodType);
+        }
+
+        Ok(unit)
+    }
+
+    /// Whether a unit is held under a reservation that has lapsed
+    fn has_lapsed_reservation(env: &Env, inventory: &InventoryClient, unit_id: u64) -> bool {
+        inventory
+            .get_reservation(&unit_id)
+            .is_some_and(|reservation| env.ledger().timestamp() >= reservation.expires_at)
+    }
+
+    /// Reserve a unit in the inventory for a request and record the assignment
+    fn reserve_for_request(
+        env: &Env,
+        inventory: &InventoryClient,
+        request: &BloodRequest,
+        unit_id: u64,
+    ) -> Result<(), ContractError> {
+        let reserved = inventory.try_reserve_unit(
+            &unit_id,
+            &env.current_contract_address(),
+            &request.hospital_id,
+            &Some(request.id),
+            &storage::UNIT_RESERVATION_SECONDS,
+        );
+        if !matches!(reserved, Ok(Ok(_))) {
+            return Err(ContractError::ReservationFailed);
+        }
+        storage::set_unit_assignment(env, unit_id, request.id);
+        Ok(())
+    }
+
+    /// Release a request's assigned units and clear their assignments
+    ///
+    /// Only reservations still held for this request are cancelled; units
+    /// whose reservation lapsed or was taken over by someone else are left
+    /// alone, as are units already delivered in a shipment.
+    fn release_assigned_units(
+        env: &Env,
+        inventory: &InventoryClient,
+        request: &BloodRequest,
+    ) -> Result<(), ContractError> {
+        let this = env.current_contract_address();
+        for unit_id in request.assigned_units.iter() {
+            if storage::is_unit_shipped(env, request.id, unit_id) {
+                continue;
+            }
+
+            if storage::get_unit_assignment(env, unit_id) == Some(request.id) {
+                storage::remove_unit_assignment(env, unit_id);
+            }
+
+            let held_for_request = inventory
+                .get_reservation(&unit_id)
+                .is_some_and(|reservation| reservation.request_id == Some(request.id));
+            if !held_for_request {
+                continue;
+            }
+
+            if !matches!(inventory.try_cancel_reservation(&unit_id, &this), Ok(Ok(_))) {
+                return Err(ContractError::ReservationFailed);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Count a hospital's open requests, recent requests and awaited volume
+    ///
+    /// Reads the running counters and the day buckets covering the quota
+    /// window rather than the hospital's requests.
+    fn hospital_usage(env: &Env, hospital: &Address, current_time: u64) -> HospitalUsage {
+        let window_start = current_time.saturating_sub(storage::QUOTA_WINDOW_SECONDS);
+        let counters = storage::get_hospital_counters(env, hospital);
+
+        HospitalUsage {
+            open_requests: counters.open_requests,
+            requests_last_day: storage::count_requests_since(
+                env,
+                hospital,
+                window_start,
+                current_time,
+            ),
+            outstanding_ml: counters.outstanding_ml,
+        }
+    }
+
+    /// Whether an amendment takes the hospital over its outstanding volume
+    /// quota
+    ///
+    /// Amendments that don't add awaited volume to a blood type always
+    /// pass, so a hospital already over quota can still reduce a request.
+    fn amendment_exceeds_volume(env: &Env, before: &BloodRequest, after: &BloodRequest) -> bool {
+        let Some(max) = storage::get_hospital_quota(env, &after.hospital_id)
+            .and_then(|quota| quota.max_outstanding_ml)
+        else {
+            return false;
+        };
+
+        let mut awaited = storage::get_hospital_counters(env, &after.hospital_id)
+            .outstanding_ml
+            .get(after.blood_type)
+            .unwrap_or(0);
+        if before.blood_type == after.blood_type {
+            if after.quantity_ml <= before.quantity_ml {
+                return false;
+            }
+            awaited = awaited.saturating_sub(before.outstanding_quantity_ml());
+        }
+
+        awaited.saturating_add(after.outstanding_quantity_ml()) > max
+    }
+
+    /// List the quota limits a new request would go over
+    fn exceeded_quota_limits(
+        env: &Env,
+        hospital: &Address,
+        blood_type: BloodType,
+        quantity_ml: u32,
+        current_time: u64,
+    ) -> Vec<QuotaLimit> {
+        let mut exceeded = Vec::new(env);
+        let Some(quota) = storage::get_hospital_quota(env, hospital) else {
+            return exceeded;
+        };
+
+        let usage = Self::hospital_usage(env, hospital, current_time);
+        if quota
+            .max_open_requests
+            .is_some_and(|max| usage.open_requests >= max)
+        {
+            exceeded.push_back(QuotaLimit::OpenRequests);
+        }
+        if quota.max_outstanding_ml.is_some_and(|max| {
+            let awaited = usage.outstanding_ml.get(blood_type).unwrap_or(0);
+            awaited.saturating_add(quantity_ml) > max
+        }) {
+            exceeded.push_back(QuotaLimit::OutstandingVolume);
+        }
+        if quota
+            .max_requests_per_day
+            .is_some_and(|max| usage.requests_last_day >= max)
+        {
+            exceeded.push_back(QuotaLimit::DailyRequests);
+        }
+
+        exceeded
+    }
+
     /// Load full BloodRequest objects from a vector of request IDs
     fn load_requests_from_ids(env: &Env, ids: Vec<u64>) -> Vec<BloodRequest> {
         let mut requests = Vec::new(env);
@@ -632,26 +1681,41 @@ impl RequestContract {
         requests
     }
 
+    /// Keep only the requests with the given status
+    fn filter_by_status(
+        env: &Env,
+        requests: Vec<BloodRequest>,
+        status: RequestStatus,
+    ) -> Vec<BloodRequest> {
+        let mut filtered = Vec::new(env);
+        for request in requests.iter() {
+            if request.status == status {
+                filtered.push_back(request);
+            }
+        }
+        filtered
+    }
+
     /// Apply pagination to a vector of requests
     fn apply_pagination(
         requests: Vec<BloodRequest>,
         limit: Option<u32>,
         offset: Option<u32>,
     ) -> Vec<BloodRequest> {
-        let env = requests.env();
+        let env = requests.env().clone();
         let offset_val = offset.unwrap_or(0) as usize;
         let limit_val = limit.unwrap_or(DEFAULT_QUERY_LIMIT).min(MAX_QUERY_LIMIT) as usize;
-        
+
         let total = requests.len() as usize;
-        
+
         // If offset is beyond the length, return empty vector
         if offset_val >= total {
             return Vec::new(&env);
         }
-        
+
         // Calculate end index
         let end = (offset_val + limit_val).min(total);
-        
+
         // Slice the vector
         let mut result = Vec::new(&env);
         for i in offset_val..end {
@@ -659,7 +1723,7 @@ impl RequestContract {
                 result.push_back(request);
             }
         }
-        
+
         result
     }
 
@@ -670,12 +1734,12 @@ impl RequestContract {
         if len <= 1 {
             return;
         }
-        
+
         for i in 0..len {
             for j in 0..(len - i - 1) {
                 let curr = requests.get(j).unwrap();
                 let next = requests.get(j + 1).unwrap();
-                
+
                 // Sort by urgency (higher priority first)
                 if curr.urgency.priority_weight() < next.urgency.priority_weight() {
                     // Swap
@@ -1,5 +1,14 @@
-use crate::types::{BloodRequest, BloodType, DataKey, RequestStatus, UrgencyLevel};
-use soroban_sdk::{Address, Env, Vec};
+use crate::error::ContractError;
+use crate::types::{
+    BloodRequest, BloodType, DataKey, FulfillmentRecord, HospitalCounters, HospitalQuota,
+    PendingQueueEntry, PendingQueuePage, PendingQueueShard, QuotaLimit, RequestAmendment,
+    RequestFingerprint, RequestRejection, RequestStatus, UrgencyEscalation, UrgencyLevel,
+};
+pub use lifebank_common::ttl::{
+    ACTIVE_TTL_EXTEND_TO, ACTIVE_TTL_THRESHOLD, ARCHIVE_TTL_EXTEND_TO, ARCHIVE_TTL_THRESHOLD,
+    INSTANCE_TTL_EXTEND_TO, INSTANCE_TTL_THRESHOLD,
+};
+use soroban_sdk::{xdr::ToXdr, Address, BytesN, Env, Map, Vec};
 
 /// Maximum time window for request fulfillment (30 days)
 pub const MAX_REQUEST_WINDOW_DAYS: u64 = 30;
@@ -10,21 +19,38 @@ pub const SECONDS_PER_DAY: u64 = 86400;
 /// Minimum time window for request fulfillment (1 hour for critical)
 pub const MIN_REQUEST_WINDOW_SECONDS: u64 = 3600;
 
+/// Most requests a single `expire_overdue_requests` call will check
+pub const MAX_EXPIRY_BATCH: u32 = 50;
+
+/// How long an open request blocks identical resubmissions (24 hours)
+pub const DUPLICATE_WINDOW_SECONDS: u64 = SECONDS_PER_DAY;
+
+/// Rolling window for the per-day request quota (24 hours)
+pub const QUOTA_WINDOW_SECONDS: u64 = SECONDS_PER_DAY;
+
+/// Most entries stored on one page of the pending queue
+pub const PENDING_QUEUE_PAGE_SIZE: u32 = 100;
+
+/// Most pending requests queued per urgency level
+pub const MAX_PENDING_PER_URGENCY: u32 = 2_500;
+
+/// How long assigned units stay reserved in the inventory (its 7 day maximum)
+pub const UNIT_RESERVATION_SECONDS: u64 = 7 * SECONDS_PER_DAY;
+
 // ========== Admin Management ==========
 
 /// Get the admin address
 ///
 /// # Panics
 /// Panics if admin has not been set (contract not initialized)
-use crate::types::{BloodRequest, DataKey};
-use soroban_sdk::{Address, Env, Vec};
-
-/// Get the admin address
 pub fn get_admin(env: &Env) -> Address {
-    env.storage()
+    let admin = env
+        .storage()
         .instance()
         .get(&DataKey::Admin)
-        .expect("Admin not initialized")
+        .expect("Admin not initialized");
+    extend_instance_ttl(env);
+    admin
 }
 
 /// Set the admin address
@@ -42,16 +68,20 @@ pub fn is_initialized(env: &Env) -> bool {
 /// Check if a hospital is authorized to create requests
 pub fn is_authorized_hospital(env: &Env, hospital: &Address) -> bool {
     // Admin is always authorized
-    if let Some(admin) = env.storage().instance().get::<DataKey, Address>(&DataKey::Admin) {
+    if let Some(admin) = env
+        .storage()
+        .instance()
+        .get::<DataKey, Address>(&DataKey::Admin)
+    {
         if hospital == &admin {
             return true;
         }
     }
 
     // Check if hospital is in authorized set
-    env.storage()
-        .persistent()
-        .has(&DataKey::AuthorizedHospital(hospital.clone()))
+    let key = DataKey::AuthorizedHospital(hospital.clone());
+    extend_active_ttl(env, &key);
+    env.storage().persistent().has(&key)
 }
 
 /// Authorize a hospital to create blood requests
@@ -59,6 +89,7 @@ pub fn authorize_hospital(env: &Env, hospital: &Address) {
     env.storage()
         .persistent()
         .set(&DataKey::AuthorizedHospital(hospital.clone()), &true);
+    extend_active_ttl(env, &DataKey::AuthorizedHospital(hospital.clone()));
 }
 
 /// Revoke hospital authorization
@@ -68,10 +99,198 @@ pub fn revoke_hospital(env: &Env, hospital: &Address) {
         .remove(&DataKey::AuthorizedHospital(hospital.clone()));
 }
 
+// ========== Hospital Quotas ==========
+
+/// Get the quota configured for a hospital, if any
+pub fn get_hospital_quota(env: &Env, hospital: &Address) -> Option<HospitalQuota> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::HospitalQuota(hospital.clone()))
+}
+
+/// Set the quota for a hospital
+pub fn set_hospital_quota(env: &Env, hospital: &Address, quota: &HospitalQuota) {
+    let key = DataKey::HospitalQuota(hospital.clone());
+    env.storage().persistent().set(&key, quota);
+    extend_active_ttl(env, &key);
+}
+
+/// Remove a hospital's quota
+pub fn remove_hospital_quota(env: &Env, hospital: &Address) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::HospitalQuota(hospital.clone()));
+}
+
+/// Get the quota limits a request exceeded (empty if it is not flagged)
+pub fn get_quota_review(env: &Env, request_id: u64) -> Vec<QuotaLimit> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::QuotaReview(request_id))
+        .unwrap_or(Vec::new(env))
+}
+
+/// Flag a request for review with the quota limits it exceeded
+pub fn add_quota_review(env: &Env, request: &BloodRequest, exceeded: &Vec<QuotaLimit>) {
+    let key = DataKey::QuotaReview(request.id);
+    env.storage().persistent().set(&key, exceeded);
+    extend_request_entry_ttl(env, &key, request.status);
+
+    let mut queue = get_quota_review_queue(env);
+    if queue.contains(request.id) {
+        return;
+    }
+    queue.push_back(request.id);
+    env.storage()
+        .persistent()
+        .set(&DataKey::QuotaReviewQueue, &queue);
+    extend_active_ttl(env, &DataKey::QuotaReviewQueue);
+}
+
+/// Clear a request's review flag
+pub fn clear_quota_review(env: &Env, request_id: u64) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::QuotaReview(request_id));
+
+    let mut remaining = Vec::new(env);
+    for id in get_quota_review_queue(env).iter() {
+        if id != request_id {
+            remaining.push_back(id);
+        }
+    }
+    env.storage()
+        .persistent()
+        .set(&DataKey::QuotaReviewQueue, &remaining);
+    extend_active_ttl(env, &DataKey::QuotaReviewQueue);
+}
+
+/// Get the IDs of requests flagged for quota review, oldest first
+pub fn get_quota_review_queue(env: &Env) -> Vec<u64> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::QuotaReviewQueue)
+        .unwrap_or(Vec::new(env))
+}
+
+// ========== Hospital Usage ==========
+
+/// Get a hospital's running open request and awaited volume counters
+pub fn get_hospital_counters(env: &Env, hospital: &Address) -> HospitalCounters {
+    env.storage()
+        .persistent()
+        .get(&DataKey::HospitalCounters(hospital.clone()))
+        .unwrap_or(HospitalCounters {
+            open_requests: 0,
+            outstanding_ml: Map::new(env),
+        })
+}
+
+/// Move a hospital's counters from one version of a request to the next
+///
+/// `before` is None for a new request. Call after every change to a
+/// request's status, blood type or quantities.
+pub fn update_hospital_counters(env: &Env, before: Option<&BloodRequest>, after: &BloodRequest) {
+    let mut counters = get_hospital_counters(env, &after.hospital_id);
+    if let Some(before) = before {
+        let awaited = counters.outstanding_ml.get(before.blood_type).unwrap_or(0);
+        set_awaited_ml(
+            &mut counters,
+            before.blood_type,
+            awaited.saturating_sub(awaited_ml(before)),
+        );
+        if !before.status.is_terminal() {
+            counters.open_requests = counters.open_requests.saturating_sub(1);
+        }
+    }
+
+    let awaited = counters.outstanding_ml.get(after.blood_type).unwrap_or(0);
+    set_awaited_ml(
+        &mut counters,
+        after.blood_type,
+        awaited.saturating_add(awaited_ml(after)),
+    );
+    if !after.status.is_terminal() {
+        counters.open_requests += 1;
+    }
+
+    let key = DataKey::HospitalCounters(after.hospital_id.clone());
+    env.storage().persistent().set(&key, &counters);
+    extend_active_ttl(env, &key);
+}
+
+/// Volume a request still counts against its hospital's outstanding quota
+fn awaited_ml(request: &BloodRequest) -> u32 {
+    match request.status {
+        RequestStatus::Pending | RequestStatus::Approved | RequestStatus::PartiallyFulfilled => {
+            request.outstanding_quantity_ml()
+        }
+        _ => 0,
+    }
+}
+
+/// Set the awaited volume of a blood type, dropping it once nothing is awaited
+fn set_awaited_ml(counters: &mut HospitalCounters, blood_type: BloodType, awaited: u32) {
+    if awaited == 0 {
+        counters.outstanding_ml.remove(blood_type);
+    } else {
+        counters.outstanding_ml.set(blood_type, awaited);
+    }
+}
+
+/// Record a new request in its hospital's bucket for the day it was created
+pub fn record_daily_request(env: &Env, request: &BloodRequest) {
+    let key = DataKey::HospitalDailyRequests(
+        request.hospital_id.clone(),
+        request.created_at / SECONDS_PER_DAY,
+    );
+    let mut created: Vec<u64> = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or(Vec::new(env));
+    created.push_back(request.created_at);
+    env.storage().persistent().set(&key, &created);
+    extend_active_ttl(env, &key);
+}
+
+/// Count a hospital's requests created after `window_start`
+///
+/// Only the day buckets overlapping the window are read, so the cost
+/// doesn't grow with the hospital's request history.
+pub fn count_requests_since(
+    env: &Env,
+    hospital: &Address,
+    window_start: u64,
+    current_time: u64,
+) -> u32 {
+    let mut count = 0;
+    for day in (window_start / SECONDS_PER_DAY)..=(current_time / SECONDS_PER_DAY) {
+        let key = DataKey::HospitalDailyRequests(hospital.clone(), day);
+        if let Some(created) = env.storage().persistent().get::<DataKey, Vec<u64>>(&key) {
+            count += created.iter().filter(|&at| at > window_start).count() as u32;
+        }
+    }
+    count
+}
+
+// ========== Inventory Contract ==========
+
+/// Get the inventory contract address, if configured
+pub fn get_inventory_contract(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&DataKey::InventoryContract)
+}
+
+/// Set the inventory contract address
+pub fn set_inventory_contract(env: &Env, inventory: &Address) {
+    env.storage()
+        .instance()
+        .set(&DataKey::InventoryContract, inventory);
+}
+
 // ========== Request ID Generation ==========
 
 /// Get the current request counter value
-/// Get the current request counter
 pub fn get_request_counter(env: &Env) -> u64 {
     env.storage()
         .instance()
@@ -98,11 +317,16 @@ pub fn set_blood_request(env: &Env, request: &BloodRequest) {
     env.storage()
         .persistent()
         .set(&DataKey::BloodRequest(request.id), request);
+    extend_request_entry_ttl(env, &DataKey::BloodRequest(request.id), request.status);
 }
 
 /// Get a blood request by ID
 pub fn get_blood_request(env: &Env, id: u64) -> Option<BloodRequest> {
-    env.storage().persistent().get(&DataKey::BloodRequest(id))
+    let request: Option<BloodRequest> = env.storage().persistent().get(&DataKey::BloodRequest(id));
+    if let Some(found) = &request {
+        extend_request_entry_ttl(env, &DataKey::BloodRequest(id), found.status);
+    }
+    request
 }
 
 /// Check if a blood request exists
@@ -114,7 +338,73 @@ pub fn blood_request_exists(env: &Env, id: u64) -> bool {
 /// Remove a blood request (use sparingly, typically requests should be marked as cancelled)
 #[allow(dead_code)]
 pub fn remove_blood_request(env: &Env, id: u64) {
-    env.storage().persistent().remove(&DataKey::BloodRequest(id));
+    env.storage()
+        .persistent()
+        .remove(&DataKey::BloodRequest(id));
+}
+
+// ========== Unit Assignments ==========
+
+/// Get the request a blood unit is assigned to, if any
+pub fn get_unit_assignment(env: &Env, unit_id: u64) -> Option<u64> {
+    let key = DataKey::UnitAssignment(unit_id);
+    extend_active_ttl(env, &key);
+    env.storage().persistent().get(&key)
+}
+
+/// Record that a blood unit is assigned to a request
+pub fn set_unit_assignment(env: &Env, unit_id: u64, request_id: u64) {
+    let key = DataKey::UnitAssignment(unit_id);
+    env.storage().persistent().set(&key, &request_id);
+    extend_active_ttl(env, &key);
+}
+
+/// Remove a blood unit's assignment
+pub fn remove_unit_assignment(env: &Env, unit_id: u64) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::UnitAssignment(unit_id));
+}
+
+// ========== Duplicate Detection ==========
+
+/// Hash a request fingerprint into its storage key
+pub fn fingerprint_hash(env: &Env, fingerprint: &RequestFingerprint) -> BytesN<32> {
+    env.crypto()
+        .sha256(&fingerprint.clone().to_xdr(env))
+        .to_bytes()
+}
+
+/// Find an open request with the same fingerprint created within the
+/// duplicate window
+pub fn find_duplicate_request(env: &Env, hash: &BytesN<32>, current_time: u64) -> Option<u64> {
+    let request_id: u64 = env
+        .storage()
+        .persistent()
+        .get(&DataKey::Fingerprint(hash.clone()))?;
+    let request = get_blood_request(env, request_id)?;
+
+    let within_window = current_time < request.created_at.saturating_add(DUPLICATE_WINDOW_SECONDS);
+    if within_window && !request.status.is_terminal() {
+        Some(request_id)
+    } else {
+        None
+    }
+}
+
+/// Point a fingerprint at the request it was taken from
+pub fn set_fingerprint(env: &Env, hash: &BytesN<32>, request_id: u64) {
+    let key = DataKey::Fingerprint(hash.clone());
+    env.storage().persistent().set(&key, &request_id);
+    extend_active_ttl(env, &key);
+}
+
+/// Drop a fingerprint if it still points at the given request
+pub fn remove_fingerprint(env: &Env, hash: &BytesN<32>, request_id: u64) {
+    let key = DataKey::Fingerprint(hash.clone());
+    if env.storage().persistent().get::<DataKey, u64>(&key) == Some(request_id) {
+        env.storage().persistent().remove(&key);
+    }
 }
 
 // ========== Index Management ==========
@@ -130,6 +420,7 @@ pub fn add_to_hospital_index(env: &Env, request: &BloodRequest) {
 
     requests.push_back(request.id);
     env.storage().persistent().set(&key, &requests);
+    extend_active_ttl(env, &key);
 }
 
 /// Add request to blood type index
@@ -143,6 +434,7 @@ pub fn add_to_blood_type_index(env: &Env, request: &BloodRequest) {
 
     requests.push_back(request.id);
     env.storage().persistent().set(&key, &requests);
+    extend_active_ttl(env, &key);
 }
 
 /// Add request to status index
@@ -156,6 +448,7 @@ pub fn add_to_status_index(env: &Env, request: &BloodRequest) {
 
     requests.push_back(request.id);
     env.storage().persistent().set(&key, &requests);
+    extend_active_ttl(env, &key);
 }
 
 /// Add request to urgency index
@@ -169,6 +462,7 @@ pub fn add_to_urgency_index(env: &Env, request: &BloodRequest) {
 
     requests.push_back(request.id);
     env.storage().persistent().set(&key, &requests);
+    extend_active_ttl(env, &key);
 }
 
 /// Remove request from status index (for status transitions)
@@ -183,6 +477,7 @@ pub fn remove_from_status_index(env: &Env, request_id: u64, status: RequestStatu
             }
         }
         env.storage().persistent().set(&key, &new_requests);
+        extend_active_ttl(env, &key);
     }
 }
 
@@ -204,6 +499,408 @@ pub fn update_status_index(
         .unwrap_or(Vec::new(env));
     requests.push_back(request_id);
     env.storage().persistent().set(&key, &requests);
+    extend_active_ttl(env, &key);
+}
+
+/// Move a request between urgency index buckets
+pub fn update_urgency_index(
+    env: &Env,
+    request_id: u64,
+    old_urgency: UrgencyLevel,
+    new_urgency: UrgencyLevel,
+) {
+    let old_key = DataKey::UrgencyIndex(old_urgency);
+    if let Some(requests) = env
+        .storage()
+        .persistent()
+        .get::<DataKey, Vec<u64>>(&old_key)
+    {
+        let mut remaining = Vec::new(env);
+        for id in requests.iter() {
+            if id != request_id {
+                remaining.push_back(id);
+            }
+        }
+        env.storage().persistent().set(&old_key, &remaining);
+        extend_active_ttl(env, &old_key);
+    }
+
+    let new_key = DataKey::UrgencyIndex(new_urgency);
+    let mut requests: Vec<u64> = env
+        .storage()
+        .persistent()
+        .get(&new_key)
+        .unwrap_or(Vec::new(env));
+    requests.push_back(request_id);
+    env.storage().persistent().set(&new_key, &requests);
+    extend_active_ttl(env, &new_key);
+}
+
+/// Move a request between blood type index buckets
+pub fn update_blood_type_index(
+    env: &Env,
+    request_id: u64,
+    old_blood_type: BloodType,
+    new_blood_type: BloodType,
+) {
+    let old_key = DataKey::BloodTypeIndex(old_blood_type);
+    if let Some(requests) = env
+        .storage()
+        .persistent()
+        .get::<DataKey, Vec<u64>>(&old_key)
+    {
+        let mut remaining = Vec::new(env);
+        for id in requests.iter() {
+            if id != request_id {
+                remaining.push_back(id);
+            }
+        }
+        env.storage().persistent().set(&old_key, &remaining);
+        extend_active_ttl(env, &old_key);
+    }
+
+    let new_key = DataKey::BloodTypeIndex(new_blood_type);
+    let mut requests: Vec<u64> = env
+        .storage()
+        .persistent()
+        .get(&new_key)
+        .unwrap_or(Vec::new(env));
+    requests.push_back(request_id);
+    env.storage().persistent().set(&new_key, &requests);
+    extend_active_ttl(env, &new_key);
+}
+
+// ========== Pending Queue ==========
+
+/// Urgency levels in the order their pending queue shards are served
+const QUEUE_SERVICE_ORDER: [UrgencyLevel; 3] = [
+    UrgencyLevel::Critical,
+    UrgencyLevel::Urgent,
+    UrgencyLevel::Normal,
+];
+
+fn get_pending_shard(env: &Env, urgency: UrgencyLevel) -> PendingQueueShard {
+    env.storage()
+        .persistent()
+        .get(&DataKey::PendingQueueShard(urgency))
+        .unwrap_or(PendingQueueShard {
+            len: 0,
+            next_page_id: 0,
+            pages: Vec::new(env),
+        })
+}
+
+fn set_pending_shard(env: &Env, urgency: UrgencyLevel, shard: &PendingQueueShard) {
+    let key = DataKey::PendingQueueShard(urgency);
+    env.storage().persistent().set(&key, shard);
+    extend_active_ttl(env, &key);
+}
+
+fn get_pending_page(env: &Env, urgency: UrgencyLevel, page_id: u32) -> Vec<PendingQueueEntry> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::PendingQueuePage(urgency, page_id))
+        .unwrap_or(Vec::new(env))
+}
+
+/// Write a page and return its directory entry
+fn set_pending_page(
+    env: &Env,
+    urgency: UrgencyLevel,
+    page_id: u32,
+    entries: &Vec<PendingQueueEntry>,
+) -> PendingQueuePage {
+    let key = DataKey::PendingQueuePage(urgency, page_id);
+    env.storage().persistent().set(&key, entries);
+    extend_active_ttl(env, &key);
+    PendingQueuePage {
+        page_id,
+        last: entries.last_unchecked(),
+        len: entries.len(),
+    }
+}
+
+fn remove_pending_page(env: &Env, urgency: UrgencyLevel, page_id: u32) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::PendingQueuePage(urgency, page_id));
+}
+
+/// Index of the first page whose last entry sorts at or after `entry`
+/// (`pages.len()` if the entry sorts after every page)
+fn find_pending_page(pages: &Vec<PendingQueuePage>, entry: &PendingQueueEntry) -> u32 {
+    let (mut low, mut high) = (0, pages.len());
+    while low < high {
+        let mid = low + (high - low) / 2;
+        if pages.get_unchecked(mid).last < *entry {
+            low = mid + 1;
+        } else {
+            high = mid;
+        }
+    }
+    low
+}
+
+/// Position of `entry` within a sorted page (binary search)
+fn find_in_page(entries: &Vec<PendingQueueEntry>, entry: &PendingQueueEntry) -> u32 {
+    let (mut low, mut high) = (0, entries.len());
+    while low < high {
+        let mid = low + (high - low) / 2;
+        if entries.get_unchecked(mid) < *entry {
+            low = mid + 1;
+        } else {
+            high = mid;
+        }
+    }
+    low
+}
+
+/// Insert an entry at its place in its urgency level's shard
+///
+/// Splits the page in two once it grows past `PENDING_QUEUE_PAGE_SIZE`.
+fn insert_into_pending_queue(
+    env: &Env,
+    urgency: UrgencyLevel,
+    entry: &PendingQueueEntry,
+) -> Result<(), ContractError> {
+    let mut shard = get_pending_shard(env, urgency);
+    if shard.len >= MAX_PENDING_PER_URGENCY {
+        return Err(ContractError::PendingQueueFull);
+    }
+
+    if shard.pages.is_empty() {
+        let mut entries = Vec::new(env);
+        entries.push_back(entry.clone());
+        let page = set_pending_page(env, urgency, shard.next_page_id, &entries);
+        shard.pages.push_back(page);
+        shard.next_page_id += 1;
+    } else {
+        let index = find_pending_page(&shard.pages, entry).min(shard.pages.len() - 1);
+        let page = shard.pages.get_unchecked(index);
+        let mut entries = get_pending_page(env, urgency, page.page_id);
+        entries.insert(find_in_page(&entries, entry), entry.clone());
+
+        if entries.len() > PENDING_QUEUE_PAGE_SIZE {
+            let upper = entries.slice(entries.len() / 2..);
+            let lower = entries.slice(..entries.len() / 2);
+            let lower_page = set_pending_page(env, urgency, page.page_id, &lower);
+            let upper_page = set_pending_page(env, urgency, shard.next_page_id, &upper);
+            shard.pages.set(index, lower_page);
+            shard.pages.insert(index + 1, upper_page);
+            shard.next_page_id += 1;
+        } else {
+            let page = set_pending_page(env, urgency, page.page_id, &entries);
+            shard.pages.set(index, page);
+        }
+    }
+
+    shard.len += 1;
+    set_pending_shard(env, urgency, &shard);
+    Ok(())
+}
+
+/// Remove an entry from its urgency level's shard
+///
+/// Drops the page once it is empty, or folds it into a neighbour when the
+/// two fit on one page, so removals don't leave the directory fragmented.
+fn remove_from_pending_queue(env: &Env, urgency: UrgencyLevel, entry: &PendingQueueEntry) {
+    let mut shard = get_pending_shard(env, urgency);
+    let index = find_pending_page(&shard.pages, entry);
+    if index >= shard.pages.len() {
+        return;
+    }
+
+    let page = shard.pages.get_unchecked(index);
+    let mut entries = get_pending_page(env, urgency, page.page_id);
+    let position = find_in_page(&entries, entry);
+    if position >= entries.len() || entries.get_unchecked(position) != *entry {
+        return;
+    }
+    entries.remove(position);
+    shard.len -= 1;
+
+    if entries.is_empty() {
+        remove_pending_page(env, urgency, page.page_id);
+        shard.pages.remove(index);
+    } else if let Some(next) = shard
+        .pages
+        .get(index + 1)
+        .filter(|next| entries.len() + next.len <= PENDING_QUEUE_PAGE_SIZE)
+    {
+        entries.append(&get_pending_page(env, urgency, next.page_id));
+        remove_pending_page(env, urgency, next.page_id);
+        shard.pages.remove(index + 1);
+        let merged = set_pending_page(env, urgency, page.page_id, &entries);
+        shard.pages.set(index, merged);
+    } else if let Some(previous) = index.checked_sub(1).and_then(|i| {
+        shard
+            .pages
+            .get(i)
+            .filter(|previous| previous.len + entries.len() <= PENDING_QUEUE_PAGE_SIZE)
+    }) {
+        let mut merged = get_pending_page(env, urgency, previous.page_id);
+        merged.append(&entries);
+        remove_pending_page(env, urgency, page.page_id);
+        shard.pages.remove(index);
+        let merged = set_pending_page(env, urgency, previous.page_id, &merged);
+        shard.pages.set(index - 1, merged);
+    } else {
+        let page = set_pending_page(env, urgency, page.page_id, &entries);
+        shard.pages.set(index, page);
+    }
+
+    set_pending_shard(env, urgency, &shard);
+}
+
+/// Keep the pending queue in step with a request change
+///
+/// Removes the request's old entry if it was pending and inserts its new
+/// one if it still is. Call wherever a request's status, urgency or
+/// deadline changes, with `before` set to None for new requests.
+///
+/// # Errors
+/// - `PendingQueueFull`: Shard for the request's urgency is at capacity
+pub fn update_pending_queue(
+    env: &Env,
+    before: Option<&BloodRequest>,
+    after: &BloodRequest,
+) -> Result<(), ContractError> {
+    let old = before
+        .filter(|before| before.status == RequestStatus::Pending)
+        .map(|before| (before.urgency, before.queue_entry()));
+    let new =
+        (after.status == RequestStatus::Pending).then(|| (after.urgency, after.queue_entry()));
+    if old == new {
+        return Ok(());
+    }
+
+    if let Some((urgency, entry)) = old {
+        remove_from_pending_queue(env, urgency, &entry);
+    }
+    if let Some((urgency, entry)) = new {
+        insert_into_pending_queue(env, urgency, &entry)?;
+    }
+    Ok(())
+}
+
+/// Get IDs of pending requests in fulfillment order
+///
+/// Skips whole pages using the shard directories, so only the pages that
+/// hold the requested range are read.
+pub fn get_pending_request_ids(env: &Env, offset: u32, limit: u32) -> Vec<u64> {
+    let mut request_ids = Vec::new(env);
+    let mut skip = offset;
+
+    for urgency in QUEUE_SERVICE_ORDER {
+        let shard = get_pending_shard(env, urgency);
+        if skip >= shard.len {
+            skip -= shard.len;
+            continue;
+        }
+
+        for page in shard.pages.iter() {
+            if request_ids.len() >= limit {
+                return request_ids;
+            }
+            if skip >= page.len {
+                skip -= page.len;
+                continue;
+            }
+
+            for entry in get_pending_page(env, urgency, page.page_id)
+                .slice(skip..)
+                .iter()
+            {
+                if request_ids.len() >= limit {
+                    return request_ids;
+                }
+                request_ids.push_back(entry.request_id);
+            }
+            skip = 0;
+        }
+    }
+
+    request_ids
+}
+
+// ========== Urgency Escalations ==========
+
+/// Get every urgency escalation recorded for a request, oldest first
+pub fn get_escalations(env: &Env, request_id: u64) -> Vec<UrgencyEscalation> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Escalations(request_id))
+        .unwrap_or(Vec::new(env))
+}
+
+/// Record an urgency escalation for a request
+pub fn add_escalation(env: &Env, request: &BloodRequest, escalation: &UrgencyEscalation) {
+    let key = DataKey::Escalations(request.id);
+    let mut escalations = get_escalations(env, request.id);
+    escalations.push_back(escalation.clone());
+    env.storage().persistent().set(&key, &escalations);
+    extend_request_entry_ttl(env, &key, request.status);
+}
+
+// ========== Amendments ==========
+
+/// Get every amendment recorded for a request, oldest first
+pub fn get_amendments(env: &Env, request_id: u64) -> Vec<RequestAmendment> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Amendments(request_id))
+        .unwrap_or(Vec::new(env))
+}
+
+/// Record an amendment for a request
+pub fn add_amendment(env: &Env, request: &BloodRequest, amendment: &RequestAmendment) {
+    let key = DataKey::Amendments(request.id);
+    let mut amendments = get_amendments(env, request.id);
+    amendments.push_back(amendment.clone());
+    env.storage().persistent().set(&key, &amendments);
+    extend_request_entry_ttl(env, &key, request.status);
+}
+
+// ========== Fulfillments ==========
+
+/// Get every shipment recorded for a request, oldest first
+pub fn get_fulfillments(env: &Env, request_id: u64) -> Vec<FulfillmentRecord> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Fulfillments(request_id))
+        .unwrap_or(Vec::new(env))
+}
+
+/// Record a shipment delivered against a request
+pub fn add_fulfillment(env: &Env, request: &BloodRequest, fulfillment: &FulfillmentRecord) {
+    let key = DataKey::Fulfillments(request.id);
+    let mut fulfillments = get_fulfillments(env, request.id);
+    fulfillments.push_back(fulfillment.clone());
+    env.storage().persistent().set(&key, &fulfillments);
+    extend_request_entry_ttl(env, &key, request.status);
+}
+
+/// Check whether a unit was already delivered in one of a request's shipments
+pub fn is_unit_shipped(env: &Env, request_id: u64, unit_id: u64) -> bool {
+    get_fulfillments(env, request_id)
+        .iter()
+        .any(|fulfillment| fulfillment.unit_ids.contains(unit_id))
+}
+
+// ========== Rejections ==========
+
+/// Get the rejection details of a declined request
+pub fn get_rejection(env: &Env, request_id: u64) -> Option<RequestRejection> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Rejection(request_id))
+}
+
+/// Store the rejection details of a declined request
+pub fn set_rejection(env: &Env, request: &BloodRequest, rejection: &RequestRejection) {
+    let key = DataKey::Rejection(request.id);
+    env.storage().persistent().set(&key, rejection);
+    extend_request_entry_ttl(env, &key, request.status);
 }
 
 // ========== Index Queries ==========
@@ -211,6 +908,7 @@ pub fn update_status_index(
 /// Get all request IDs for a hospital
 pub fn get_requests_by_hospital(env: &Env, hospital: &Address) -> Vec<u64> {
     let key = DataKey::HospitalIndex(hospital.clone());
+    extend_active_ttl(env, &key);
     env.storage()
         .persistent()
         .get(&key)
@@ -220,6 +918,7 @@ pub fn get_requests_by_hospital(env: &Env, hospital: &Address) -> Vec<u64> {
 /// Get all request IDs for a blood type
 pub fn get_requests_by_blood_type(env: &Env, blood_type: BloodType) -> Vec<u64> {
     let key = DataKey::BloodTypeIndex(blood_type);
+    extend_active_ttl(env, &key);
     env.storage()
         .persistent()
         .get(&key)
@@ -229,6 +928,7 @@ pub fn get_requests_by_blood_type(env: &Env, blood_type: BloodType) -> Vec<u64>
 /// Get all request IDs with a specific status
 pub fn get_requests_by_status(env: &Env, status: RequestStatus) -> Vec<u64> {
     let key = DataKey::StatusIndex(status);
+    extend_active_ttl(env, &key);
     env.storage()
         .persistent()
         .get(&key)
@@ -238,25 +938,57 @@ pub fn get_requests_by_status(env: &Env, status: RequestStatus) -> Vec<u64> {
 /// Get all request IDs with a specific urgency level
 pub fn get_requests_by_urgency(env: &Env, urgency: UrgencyLevel) -> Vec<u64> {
     let key = DataKey::UrgencyIndex(urgency);
+    extend_active_ttl(env, &key);
     env.storage()
         .persistent()
         .get(&key)
         .unwrap_or(Vec::new(env))
-/// Retrieve a blood request by ID
-pub fn get_blood_request(env: &Env, request_id: u64) -> Option<BloodRequest> {
+}
+
+// ========== Storage TTL ==========
+
+/// Extend the contract instance and its instance storage
+pub fn extend_instance_ttl(env: &Env) {
     env.storage()
-        .persistent()
-        .get(&DataKey::BloodRequest(request_id))
+        .instance()
+        .extend_ttl(INSTANCE_TTL_THRESHOLD, INSTANCE_TTL_EXTEND_TO);
 }
 
-/// Check if a hospital is authorized
-pub fn is_authorized_hospital(env: &Env, hospital: &Address) -> bool {
-    let admin = get_admin(env);
-    hospital == &admin
+/// Extend a blood request using the TTL class of its status
+///
+/// Terminal requests get the longer archive TTL so the audit record
+/// outlives the request's working life.
+pub fn extend_request_ttl(env: &Env, request: &BloodRequest) {
+    extend_request_entry_ttl(env, &DataKey::BloodRequest(request.id), request.status);
+    extend_request_entry_ttl(env, &DataKey::Escalations(request.id), request.status);
+    extend_request_entry_ttl(env, &DataKey::Amendments(request.id), request.status);
+    extend_request_entry_ttl(env, &DataKey::Fulfillments(request.id), request.status);
+    extend_request_entry_ttl(env, &DataKey::Rejection(request.id), request.status);
+    extend_request_entry_ttl(env, &DataKey::QuotaReview(request.id), request.status);
+    for unit_id in request.assigned_units.iter() {
+        extend_request_entry_ttl(env, &DataKey::UnitAssignment(unit_id), request.status);
+    }
+}
+
+/// Extend a request entry, choosing active or archive TTL by status
+fn extend_request_entry_ttl(env: &Env, key: &DataKey, status: RequestStatus) {
+    if status.is_terminal() {
+        extend_persistent_ttl(env, key, ARCHIVE_TTL_THRESHOLD, ARCHIVE_TTL_EXTEND_TO);
+    } else {
+        extend_persistent_ttl(env, key, ACTIVE_TTL_THRESHOLD, ACTIVE_TTL_EXTEND_TO);
+    }
+}
+
+/// Extend a shared index or registry entry
+fn extend_active_ttl(env: &Env, key: &DataKey) {
+    extend_persistent_ttl(env, key, ACTIVE_TTL_THRESHOLD, ACTIVE_TTL_EXTEND_TO);
 }
 
-/// Check if a blood bank is authorized
-pub fn is_authorized_blood_bank(env: &Env, bank: &Address) -> bool {
-    let admin = get_admin(env);
-    bank == &admin
+/// Extend a persistent entry's TTL if it exists
+fn extend_persistent_ttl(env: &Env, key: &DataKey, threshold: u32, extend_to: u32) {
+    if env.storage().persistent().has(key) {
+        env.storage()
+            .persistent()
+            .extend_ttl(key, threshold, extend_to);
+    }
 }
@@ -1,16 +1,18 @@
-use crate::types::{BloodRequest, BloodType, RequestMetadata, RequestStatus, UrgencyLevel};
-use crate::{RequestContract, RequestContractClient};
-use soroban_sdk::{
-    testutils::{Address as _, Ledger},
-    vec, Address, Env, String,
-};
-
 use crate::storage;
-use crate::types::{BloodType, RequestStatus, UrgencyLevel};
-use crate::{RequestContract, RequestContractClient};
+use crate::types::{
+    BloodRequest, BloodType, DataKey, FulfillmentRecordedEvent, HospitalQuota, PendingQueueShard,
+    QuotaExceededEvent, QuotaLimit, RejectionReason, RequestAmendedEvent, RequestEscalatedEvent,
+    RequestMetadata, RequestRejectedEvent, RequestStatus, UnitsAssignedEvent, UrgencyLevel,
+};
+use crate::{ContractError, RequestContract, RequestContractClient};
+use core::sync::atomic::{AtomicU32, Ordering};
+use inventory_contract::{
+    BloodComponent, BloodStatus as UnitStatus, BloodType as UnitBloodType, InventoryContract,
+    InventoryContractClient, ScreeningResult, TtiMarker,
+};
 use soroban_sdk::{
-    testutils::{Address as _, Ledger},
-    Address, Env, String,
+    testutils::{storage::Persistent as _, Address as _, Events, Ledger},
+    vec, Address, Env, IntoVal, String,
 };
 
 // ========== Test Helpers ==========
@@ -29,15 +31,179 @@ fn create_test_contract<'a>() -> (Env, Address, RequestContractClient<'a>, Addre
     (env, admin, client, contract_id)
 }
 
-fn setup_authorized_hospital<'a>(
-    env: &Env,
-    client: &RequestContractClient<'a>,
-) -> Address {
+fn setup_authorized_hospital<'a>(env: &Env, client: &RequestContractClient<'a>) -> Address {
     let hospital = Address::generate(env);
     client.authorize_hospital(&hospital);
     hospital
 }
 
+/// Register an inventory contract and wire it to the request contract
+fn setup_inventory<'a>(
+    env: &Env,
+    client: &RequestContractClient<'a>,
+    request_contract: &Address,
+) -> (InventoryContractClient<'a>, Address) {
+    let inventory_id = env.register(InventoryContract, ());
+    let inventory = InventoryContractClient::new(env, &inventory_id);
+
+    let bank = Address::generate(env);
+    inventory.initialize(&bank);
+    inventory.set_request_contract(request_contract);
+    client.set_inventory_contract(&inventory_id);
+
+    (inventory, bank)
+}
+
+/// Build a unique ISBT 128 DIN (ISO 7064 Mod 37-2 check character)
+fn next_din(env: &Env) -> String {
+    static SEQUENCE: AtomicU32 = AtomicU32::new(1);
+    const CHARSET: &[u8; 37] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ*";
+
+    let mut bytes = *b"W000026000000?";
+    let mut n = SEQUENCE.fetch_add(1, Ordering::Relaxed);
+    for i in (7..13).rev() {
+        bytes[i] = b'0' + (n % 10) as u8;
+        n /= 10;
+    }
+
+    let mut sum = 0u32;
+    for &c in &bytes[..13] {
+        let value = match c {
+            b'0'..=b'9' => (c - b'0') as u32,
+            _ => (c - b'A') as u32 + 10,
+        };
+        sum = ((sum + value) * 2) % 37;
+    }
+    bytes[13] = CHARSET[((38 - sum) % 37) as usize];
+
+    String::from_bytes(env, &bytes)
+}
+
+/// Register a unit in the inventory and clear its screening
+fn add_available_unit(
+    env: &Env,
+    inventory: &InventoryContractClient,
+    bank: &Address,
+    blood_type: UnitBloodType,
+    expiration: u64,
+) -> u64 {
+    add_screened_unit(
+        env,
+        inventory,
+        bank,
+        bank,
+        blood_type,
+        BloodComponent::WholeBlood,
+        expiration,
+    )
+}
+
+/// Register a unit of a component for a bank and have a lab clear its screening
+fn add_screened_unit(
+    env: &Env,
+    inventory: &InventoryContractClient,
+    bank: &Address,
+    lab: &Address,
+    blood_type: UnitBloodType,
+    component: BloodComponent,
+    expiration: u64,
+) -> u64 {
+    let unit_id = inventory.register_blood(
+        bank,
+        &next_din(env),
+        &String::from_str(env, "E0001V00"),
+        &blood_type,
+        &component,
+        &450u32,
+        &expiration,
+        &None,
+    );
+    for marker in [
+        TtiMarker::Hiv,
+        TtiMarker::Hbv,
+        TtiMarker::Hcv,
+        TtiMarker::Syphilis,
+        TtiMarker::Malaria,
+    ] {
+        inventory.record_screening_result(&unit_id, lab, &marker, &ScreeningResult::Negative);
+    }
+    unit_id
+}
+
+/// Request fields the tests vary; everything else is fixed
+///
+/// Defaults to 450ml of O+ for a new patient, Urgent, due in one day.
+struct TestRequest {
+    quantity_ml: u32,
+    urgency: UrgencyLevel,
+    required_by: Option<u64>,
+    patient: Option<Address>,
+}
+
+impl TestRequest {
+    fn new() -> Self {
+        TestRequest {
+            quantity_ml: 450,
+            urgency: UrgencyLevel::Urgent,
+            required_by: None,
+            patient: None,
+        }
+    }
+
+    fn quantity_ml(mut self, quantity_ml: u32) -> Self {
+        self.quantity_ml = quantity_ml;
+        self
+    }
+
+    fn urgency(mut self, urgency: UrgencyLevel) -> Self {
+        self.urgency = urgency;
+        self
+    }
+
+    fn required_by(mut self, required_by: u64) -> Self {
+        self.required_by = Some(required_by);
+        self
+    }
+
+    fn patient(mut self, patient: &Address) -> Self {
+        self.patient = Some(patient.clone());
+        self
+    }
+}
+
+/// Submit a request, returning the contract error if it is refused
+fn submit_request(
+    env: &Env,
+    client: &RequestContractClient,
+    hospital: &Address,
+    request: TestRequest,
+) -> Result<u64, ContractError> {
+    match client.try_create_request(
+        hospital,
+        &BloodType::OPositive,
+        &request.quantity_ml,
+        &request.urgency,
+        &request
+            .required_by
+            .unwrap_or(env.ledger().timestamp() + 86400),
+        &String::from_str(env, "Hospital"),
+        &request.patient.unwrap_or(Address::generate(env)),
+        &String::from_str(env, "Surgery"),
+        &String::from_str(env, "Notes"),
+    ) {
+        Ok(Ok(request_id)) => Ok(request_id),
+        Err(Ok(error)) => Err(error),
+        _ => panic!("unexpected create_request result"),
+    }
+}
+
+/// Create and approve an urgent 450ml O+ request due in one day
+fn create_approved_request(env: &Env, client: &RequestContractClient, hospital: &Address) -> u64 {
+    let request_id = submit_request(env, client, hospital, TestRequest::new()).unwrap();
+    client.approve_request(&request_id);
+    request_id
+}
+
 // ========== Initialization Tests ==========
 
 #[test]
@@ -118,71 +284,23 @@ fn test_create_request_success() {
 
     let request_id = client.create_request(
         &hospital,
-fn create_test_contract<'a>() -> (Env, Address, RequestContractClient<'a>, Address) {
-    let env = Env::default();
-    env.mock_all_auths();
-
-    let contract_id = env.register(RequestContract, ());
-    let client = RequestContractClient::new(&env, &contract_id);
-
-    let admin = Address::generate(&env);
-    client.initialize(&admin);
-
-    (env, admin, client, contract_id)
-}
-
-#[test]
-fn test_initialize_success() {
-    let (env, admin, _client, contract_id) = create_test_contract();
-
-    // Verify admin is set
-    let stored_admin = env.as_contract(&contract_id, || {
-        crate::storage::get_admin(&env)
-    });
-
-    assert_eq!(stored_admin, admin);
-}
-
-#[test]
-#[should_panic(expected = "Error(Contract, #0)")]
-fn test_initialize_already_initialized() {
-    let (env, admin, client, _contract_id) = create_test_contract();
-
-    // Try to initialize again
-    client.initialize(&admin);
-}
-
-#[test]
-#[should_panic(expected = "Error(Contract, #32)")]
-fn test_create_request_success() {
-    let (env, admin, client, _contract_id) = create_test_contract();
-
-    let hospital = Address::generate(&env);
-    let patient = Address::generate(&env);
-    let blood_type = BloodType::OPositive;
-    let quantity_ml = 450u32;
-    let urgency = UrgencyLevel::Urgent;
-
-    let current_time = 1000u64;
-    env.ledger().set_timestamp(current_time);
-    let required_by = current_time + (2 * 86400); // 2 days from now
-
-    let delivery_address = String::from_str(&env, "Hospital Main Building");
-    let procedure = String::from_str(&env, "Emergency Surgery");
-    let notes = String::from_str(&env, "Type O+ preferred");
-
-    // This should fail because hospital is not authorized (not the admin)
-    client.create_request(
-        &hospital,
         &blood_type,
         &quantity_ml,
         &urgency,
         &required_by,
         &delivery_address,
-        &patient,
-        &procedure,
-        &notes,
+        &Address::generate(&env),
+        &String::from_str(&env, "Surgery"),
+        &String::from_str(&env, "Notes"),
     );
+
+    let request = client.get_request(&request_id);
+    assert_eq!(request.hospital_id, hospital);
+    assert_eq!(request.blood_type, blood_type);
+    assert_eq!(request.quantity_ml, quantity_ml);
+    assert_eq!(request.status, RequestStatus::Pending);
+    assert_eq!(request.created_at, current_time);
+    assert_eq!(request.required_by, required_by);
 }
 
 #[test]
@@ -225,12 +343,12 @@ fn test_create_request_invalid_quantity_too_low() {
 
     let patient = Address::generate(&env);
     let blood_type = BloodType::BPositive;
-    let quantity_ml = 25u32; // Below minimum of 50ml
+    let quantity_ml = 25u32; // Below minimum of 100ml
     let urgency = UrgencyLevel::Critical;
 
     let current_time = 1000u64;
     env.ledger().set_timestamp(current_time);
-    let required_by = current_time + (1 * 86400);
+    let required_by = current_time + 86400;
 
     let delivery_address = String::from_str(&env, "Hospital");
     let procedure = String::from_str(&env, "Surgery");
@@ -256,7 +374,7 @@ fn test_create_request_invalid_quantity_too_high() {
 
     let patient = Address::generate(&env);
     let blood_type = BloodType::BNegative;
-    let quantity_ml = 6000u32; // Above maximum of 5000ml
+    let quantity_ml = 12000u32; // Above maximum of 10000ml
     let urgency = UrgencyLevel::Normal;
 
     let current_time = 1000u64;
@@ -281,7 +399,7 @@ fn test_create_request_invalid_quantity_too_high() {
 }
 
 #[test]
-#[should_panic(expected = "Error(Contract, #15)")]
+#[should_panic(expected = "Error(Contract, #17)")]
 fn test_create_request_invalid_timestamp_in_past() {
     let (env, admin, client, _contract_id) = create_test_contract();
 
@@ -312,7 +430,7 @@ fn test_create_request_invalid_timestamp_in_past() {
 }
 
 #[test]
-#[should_panic(expected = "Error(Contract, #15)")]
+#[should_panic(expected = "Error(Contract, #17)")]
 fn test_create_request_invalid_timestamp_too_far() {
     let (env, admin, client, _contract_id) = create_test_contract();
 
@@ -342,37 +460,6 @@ fn test_create_request_invalid_timestamp_too_far() {
     );
 }
 
-#[test]
-#[should_panic(expected = "Error(Contract, #12)")]
-fn test_create_request_empty_delivery_address() {
-    let (env, admin, client, _contract_id) = create_test_contract();
-
-    let patient = Address::generate(&env);
-    let blood_type = BloodType::OPositive;
-    let quantity_ml = 450u32;
-    let urgency = UrgencyLevel::Critical;
-
-    let current_time = 1000u64;
-    env.ledger().set_timestamp(current_time);
-    let required_by = current_time + (1 * 86400);
-
-    let delivery_address = String::from_str(&env, ""); // Empty address
-    let procedure = String::from_str(&env, "Surgery");
-    let notes = String::from_str(&env, "Notes");
-
-    client.create_request(
-        &admin,
-        &blood_type,
-        &quantity_ml,
-        &urgency,
-        &required_by,
-        &delivery_address,
-        &patient,
-        &procedure,
-        &notes,
-    );
-}
-
 #[test]
 fn test_create_multiple_requests() {
     let (env, admin, client, _contract_id) = create_test_contract();
@@ -543,116 +630,602 @@ fn test_update_request_status_from_terminal_state() {
         &notes,
     );
 
-    // Transition to Rejected (terminal state)
-    client.update_request_status(&request_id, &RequestStatus::Rejected);
+    // Transition to Cancelled (terminal state)
+    client.cancel_request(&request_id, &admin);
 
-    // Try to transition from Rejected (should fail)
+    // Try to transition from Cancelled (should fail)
     client.update_request_status(&request_id, &RequestStatus::Approved);
 }
 
 #[test]
-fn test_assign_blood_units() {
+fn test_update_request_status_refuses_closing_statuses() {
     let (env, admin, client, _contract_id) = create_test_contract();
+    env.ledger().set_timestamp(1000);
+
+    let request_id = submit_request(&env, &client, &admin, TestRequest::new()).unwrap();
+
+    // Closing goes through cancel_request, reject_request or the expiry sweep
+    for status in [
+        RequestStatus::Cancelled,
+        RequestStatus::Rejected,
+        RequestStatus::Expired,
+    ] {
+        assert_eq!(
+            client.try_update_request_status(&request_id, &status),
+            Err(Ok(ContractError::InvalidStatusTransition))
+        );
+    }
+    assert_eq!(
+        client.get_request(&request_id).status,
+        RequestStatus::Pending
+    );
+}
 
-    let patient = Address::generate(&env);
-    let current_time = 1000u64;
-    env.ledger().set_timestamp(current_time);
-
-    let delivery_address = String::from_str(&env, "Hospital");
-    let procedure = String::from_str(&env, "Surgery");
-    let notes = String::from_str(&env, "Notes");
+#[test]
+fn test_assign_blood_units() {
+    let (env, admin, client, contract_id) = create_test_contract();
+    env.ledger().set_timestamp(1000);
+    let (inventory, bank) = setup_inventory(&env, &client, &contract_id);
+    assert_eq!(
+        client.get_inventory_contract(),
+        Some(inventory.address.clone())
+    );
 
-    let request_id = client.create_request(
-        &admin,
-        &BloodType::OPositive,
-        &900u32,
-        &UrgencyLevel::Urgent,
-        &(current_time + 86400),
-        &delivery_address,
-        &patient,
-        &procedure,
-        &notes,
+    let expiration = 1000 + 30 * 86400;
+    let first = add_available_unit(
+        &env,
+        &inventory,
+        &bank,
+        UnitBloodType::OPositive,
+        expiration,
+    );
+    let second = add_available_unit(
+        &env,
+        &inventory,
+        &bank,
+        UnitBloodType::ONegative,
+        expiration,
     );
+    let request_id = create_approved_request(&env, &client, &admin);
 
     // Assign blood units
-    let unit_ids = vec![&env, 1u64, 2u64];
+    let unit_ids = vec![&env, first, second];
     client.assign_blood_units(&request_id, &unit_ids);
 
     let request = client.get_request(&request_id);
-    assert_eq!(request.assigned_units.len(), 2);
-    assert_eq!(request.assigned_units.get(0).unwrap(), 1u64);
-    assert_eq!(request.assigned_units.get(1).unwrap(), 2u64);
+    assert_eq!(request.assigned_units, unit_ids);
+
+    // Units are reserved in the inventory for the requesting hospital
+    for unit_id in unit_ids.iter() {
+        assert_eq!(
+            inventory.get_blood_unit(&unit_id).status,
+            UnitStatus::Reserved
+        );
+        let reservation = inventory.get_reservation(&unit_id).unwrap();
+        assert_eq!(reservation.holder, admin);
+        assert_eq!(reservation.request_id, Some(request_id));
+        assert_eq!(
+            reservation.expires_at,
+            1000 + storage::UNIT_RESERVATION_SECONDS
+        );
+    }
 }
 
 #[test]
-#[should_panic(expected = "Error(Contract, #40)")]
-fn test_request_not_found() {
-    let (env, _admin, client, _contract_id) = create_test_contract();
+fn test_reassign_blood_units_releases_previous_units() {
+    let (env, admin, client, contract_id) = create_test_contract();
+    env.ledger().set_timestamp(1000);
+    let (inventory, bank) = setup_inventory(&env, &client, &contract_id);
+
+    let expiration = 1000 + 30 * 86400;
+    let first = add_available_unit(
+        &env,
+        &inventory,
+        &bank,
+        UnitBloodType::OPositive,
+        expiration,
+    );
+    let second = add_available_unit(
+        &env,
+        &inventory,
+        &bank,
+        UnitBloodType::OPositive,
+        expiration,
+    );
+    let third = add_available_unit(
+        &env,
+        &inventory,
+        &bank,
+        UnitBloodType::OPositive,
+        expiration,
+    );
+    let request_id = create_approved_request(&env, &client, &admin);
 
-    // Try to get a request that doesn't exist
-    client.get_request(&999u64);
+    client.assign_blood_units(&request_id, &vec![&env, first, second]);
+
+    // Keeping a unit across reassignment re-reserves it
+    client.assign_blood_units(&request_id, &vec![&env, second, third]);
+    assert_eq!(
+        client.get_request(&request_id).assigned_units,
+        vec![&env, second, third]
+    );
+    assert_eq!(
+        inventory.get_blood_unit(&first).status,
+        UnitStatus::Available
+    );
+    assert_eq!(inventory.get_reservation(&first), None);
+    for unit_id in [second, third] {
+        assert_eq!(
+            inventory.get_blood_unit(&unit_id).status,
+            UnitStatus::Reserved
+        );
+    }
 }
 
 #[test]
-fn test_urgency_level_max_fulfillment_time() {
-    assert_eq!(UrgencyLevel::Critical.max_fulfillment_time(), 3600); // 1 hour
-    assert_eq!(UrgencyLevel::Urgent.max_fulfillment_time(), 21600); // 6 hours
-    assert_eq!(UrgencyLevel::Normal.max_fulfillment_time(), 86400); // 24 hours
+fn test_assign_blood_units_is_atomic() {
+    let (env, admin, client, contract_id) = create_test_contract();
+    env.ledger().set_timestamp(1000);
+    let (inventory, bank) = setup_inventory(&env, &client, &contract_id);
+
+    let expiration = 1000 + 30 * 86400;
+    let compatible = add_available_unit(
+        &env,
+        &inventory,
+        &bank,
+        UnitBloodType::OPositive,
+        expiration,
+    );
+    let incompatible = add_available_unit(
+        &env,
+        &inventory,
+        &bank,
+        UnitBloodType::APositive,
+        expiration,
+    );
+    let request_id = create_approved_request(&env, &client, &admin);
+
+    let result = client.try_assign_blood_units(&request_id, &vec![&env, compatible, incompatible]);
+    assert_eq!(result, Err(Ok(ContractError::IncompatibleBloodType)));
+
+    assert_eq!(client.get_request(&request_id).assigned_units.len(), 0);
+    assert_eq!(
+        inventory.get_blood_unit(&compatible).status,
+        UnitStatus::Available
+    );
 }
 
 #[test]
-fn test_request_status_transitions() {
-    // Test valid transitions
-    assert!(RequestStatus::Pending.can_transition_to(&RequestStatus::Approved));
-    assert!(RequestStatus::Pending.can_transition_to(&RequestStatus::Rejected));
-    assert!(RequestStatus::Pending.can_transition_to(&RequestStatus::Cancelled));
-
-    assert!(RequestStatus::Approved.can_transition_to(&RequestStatus::Fulfilled));
-    assert!(RequestStatus::Approved.can_transition_to(&RequestStatus::Cancelled));
+fn test_assign_plasma_uses_plasma_compatibility() {
+    let (env, admin, client, contract_id) = create_test_contract();
+    env.ledger().set_timestamp(1000);
+    let (inventory, bank) = setup_inventory(&env, &client, &contract_id);
+
+    let expiration = 1000 + 30 * 86400;
+    let a_plasma = add_screened_unit(
+        &env,
+        &inventory,
+        &bank,
+        &bank,
+        UnitBloodType::APositive,
+        BloodComponent::Plasma,
+        expiration,
+    );
+    let ab_plasma = add_screened_unit(
+        &env,
+        &inventory,
+        &bank,
+        &bank,
+        UnitBloodType::ABPositive,
+        BloodComponent::Plasma,
+        expiration,
+    );
+    let request_id = create_approved_request(&env, &client, &admin);
+
+    // A plasma carries anti-B but suits an O recipient; AB plasma suits anyone
+    client.assign_blood_units(&request_id, &vec![&env, a_plasma, ab_plasma]);
+    assert_eq!(client.get_request(&request_id).assigned_units.len(), 2);
+
+    // O+ red cells would be fine for an A+ request, O+ plasma isn't
+    let o_plasma = add_screened_unit(
+        &env,
+        &inventory,
+        &bank,
+        &bank,
+        UnitBloodType::OPositive,
+        BloodComponent::Plasma,
+        expiration,
+    );
+    let a_request = client.create_request(
+        &admin,
+        &BloodType::APositive,
+        &450u32,
+        &UrgencyLevel::Urgent,
+        &(env.ledger().timestamp() + 86400),
+        &String::from_str(&env, "Hospital"),
+        &Address::generate(&env),
+        &String::from_str(&env, "Surgery"),
+        &String::from_str(&env, "Notes"),
+    );
+    client.approve_request(&a_request);
+    let result = client.try_assign_blood_units(&a_request, &vec![&env, o_plasma]);
+    assert_eq!(result, Err(Ok(ContractError::IncompatibleBloodType)));
+}
 
-    assert!(RequestStatus::Fulfilled.can_transition_to(&RequestStatus::Completed));
+#[test]
+fn test_red_cell_rules_match_inventory() {
+    // Both contracts keep their own copy of the ABO/Rh table
+    let types = [
+        (BloodType::APositive, UnitBloodType::APositive),
+        (BloodType::ANegative, UnitBloodType::ANegative),
+        (BloodType::BPositive, UnitBloodType::BPositive),
+        (BloodType::BNegative, UnitBloodType::BNegative),
+        (BloodType::ABPositive, UnitBloodType::ABPositive),
+        (BloodType::ABNegative, UnitBloodType::ABNegative),
+        (BloodType::OPositive, UnitBloodType::OPositive),
+        (BloodType::ONegative, UnitBloodType::ONegative),
+    ];
 
-    // Test invalid transitions
-    assert!(!RequestStatus::Pending.can_transition_to(&RequestStatus::Fulfilled));
-    assert!(!RequestStatus::Rejected.can_transition_to(&RequestStatus::Approved));
-    assert!(!RequestStatus::Completed.can_transition_to(&RequestStatus::Approved));
-    assert!(!RequestStatus::Cancelled.can_transition_to(&RequestStatus::Fulfilled));
+    for (donor, unit_donor) in types {
+        for (recipient, unit_recipient) in types {
+            assert_eq!(
+                donor.can_donate_to(&recipient),
+                unit_donor.can_donate_to(&unit_recipient),
+                "{:?} -> {:?}",
+                donor,
+                recipient
+            );
+        }
+    }
 }
 
 #[test]
-fn test_request_status_is_terminal() {
-    assert!(!RequestStatus::Pending.is_terminal());
-    assert!(!RequestStatus::Approved.is_terminal());
-    assert!(!RequestStatus::Fulfilled.is_terminal());
+fn test_failed_reassignment_keeps_previous_units() {
+    let (env, admin, client, contract_id) = create_test_contract();
+    env.ledger().set_timestamp(1000);
+    let (inventory, bank) = setup_inventory(&env, &client, &contract_id);
+
+    let expiration = 1000 + 30 * 86400;
+    let first = add_available_unit(
+        &env,
+        &inventory,
+        &bank,
+        UnitBloodType::OPositive,
+        expiration,
+    );
+    let request_id = create_approved_request(&env, &client, &admin);
+    client.assign_blood_units(&request_id, &vec![&env, first]);
 
-    assert!(RequestStatus::Completed.is_terminal());
-    assert!(RequestStatus::Rejected.is_terminal());
-    assert!(RequestStatus::Cancelled.is_terminal());
+    let result = client.try_assign_blood_units(&request_id, &vec![&env, 999]);
+    assert_eq!(result, Err(Ok(ContractError::UnitNotFound)));
+
+    assert_eq!(
+        client.get_request(&request_id).assigned_units,
+        vec![&env, first]
+    );
+    assert_eq!(
+        inventory.get_reservation(&first).unwrap().request_id,
+        Some(request_id)
+    );
 }
 
 #[test]
-fn test_blood_request_validate_all_blood_types() {
-    let env = Env::default();
-    env.mock_all_auths();
-    env.ledger().set_timestamp(1000u64);
+fn test_assign_blood_units_tracks_assignment_and_volume() {
+    let (env, admin, client, contract_id) = create_test_contract();
+    env.ledger().set_timestamp(1000);
+    let (inventory, bank) = setup_inventory(&env, &client, &contract_id);
+
+    let expiration = 1000 + 30 * 86400;
+    let first = add_available_unit(
+        &env,
+        &inventory,
+        &bank,
+        UnitBloodType::OPositive,
+        expiration,
+    );
+    let second = add_available_unit(
+        &env,
+        &inventory,
+        &bank,
+        UnitBloodType::OPositive,
+        expiration,
+    );
+    let request_id = create_approved_request(&env, &client, &admin);
 
-    let hospital = Address::generate(&env);
-    let patient = Address::generate(&env);
+    client.assign_blood_units(&request_id, &vec![&env, first, second]);
 
-    let blood_types = [
-        BloodType::APositive,
-        BloodType::ANegative,
-        BloodType::BPositive,
-        BloodType::BNegative,
-        BloodType::ABPositive,
-        BloodType::ABNegative,
-        BloodType::OPositive,
-        BloodType::ONegative,
-    ];
+    let events = env.events().all();
+    let (_, _, data) = events.get(events.len() - 1).unwrap();
+    let event: UnitsAssignedEvent = data.into_val(&env);
+    assert_eq!(event.total_quantity_ml, 900);
+    assert_eq!(event.unit_ids, vec![&env, first, second]);
 
-    for blood_type in blood_types.iter() {
-        let metadata = RequestMetadata {
-            patient_id: patient.clone(),
+    assert_eq!(client.get_unit_assignment(&first), Some(request_id));
+    assert_eq!(client.get_unit_assignment(&second), Some(request_id));
+
+    // Reassignment drops the released unit from the reverse index
+    client.assign_blood_units(&request_id, &vec![&env, second]);
+    assert_eq!(client.get_unit_assignment(&first), None);
+    assert_eq!(client.get_unit_assignment(&second), Some(request_id));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #46)")]
+fn test_assign_unit_to_two_requests() {
+    let (env, admin, client, contract_id) = create_test_contract();
+    env.ledger().set_timestamp(1000);
+    let (inventory, bank) = setup_inventory(&env, &client, &contract_id);
+
+    let unit_id = add_available_unit(
+        &env,
+        &inventory,
+        &bank,
+        UnitBloodType::OPositive,
+        1000 + 30 * 86400,
+    );
+    let first = create_approved_request(&env, &client, &admin);
+    let second = create_approved_request(&env, &client, &admin);
+
+    client.assign_blood_units(&first, &vec![&env, unit_id]);
+    client.assign_blood_units(&second, &vec![&env, unit_id]);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #44)")]
+fn test_assign_insufficient_volume() {
+    let (env, admin, client, contract_id) = create_test_contract();
+    env.ledger().set_timestamp(1000);
+    let (inventory, bank) = setup_inventory(&env, &client, &contract_id);
+
+    let unit_id = add_available_unit(
+        &env,
+        &inventory,
+        &bank,
+        UnitBloodType::OPositive,
+        1000 + 30 * 86400,
+    );
+    let request_id = client.create_request(
+        &admin,
+        &BloodType::OPositive,
+        &900u32,
+        &UrgencyLevel::Urgent,
+        &(1000 + 86400),
+        &String::from_str(&env, "Hospital"),
+        &Address::generate(&env),
+        &String::from_str(&env, "Surgery"),
+        &String::from_str(&env, "Notes"),
+    );
+    client.approve_request(&request_id);
+
+    client.assign_blood_units(&request_id, &vec![&env, unit_id]);
+}
+
+#[test]
+fn test_assign_requires_approved_request() {
+    let (env, admin, client, contract_id) = create_test_contract();
+    env.ledger().set_timestamp(1000);
+    let (inventory, bank) = setup_inventory(&env, &client, &contract_id);
+
+    let unit_id = add_available_unit(
+        &env,
+        &inventory,
+        &bank,
+        UnitBloodType::OPositive,
+        1000 + 30 * 86400,
+    );
+    let pending = client.create_request(
+        &admin,
+        &BloodType::OPositive,
+        &450u32,
+        &UrgencyLevel::Urgent,
+        &(1000 + 86400),
+        &String::from_str(&env, "Hospital"),
+        &Address::generate(&env),
+        &String::from_str(&env, "Surgery"),
+        &String::from_str(&env, "Notes"),
+    );
+    let result = client.try_assign_blood_units(&pending, &vec![&env, unit_id]);
+    assert_eq!(result, Err(Ok(ContractError::RequestNotApproved)));
+
+    let cancelled = create_approved_request(&env, &client, &admin);
+    client.cancel_request(&cancelled, &admin);
+    let result = client.try_assign_blood_units(&cancelled, &vec![&env, unit_id]);
+    assert_eq!(result, Err(Ok(ContractError::RequestNotApproved)));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #53)")]
+fn test_assign_unit_reserved_elsewhere() {
+    let (env, admin, client, contract_id) = create_test_contract();
+    env.ledger().set_timestamp(1000);
+    let (inventory, bank) = setup_inventory(&env, &client, &contract_id);
+
+    let unit_id = add_available_unit(
+        &env,
+        &inventory,
+        &bank,
+        UnitBloodType::OPositive,
+        1000 + 30 * 86400,
+    );
+    inventory.reserve_unit(&unit_id, &bank, &bank, &None, &3600u64);
+
+    let request_id = create_approved_request(&env, &client, &admin);
+    client.assign_blood_units(&request_id, &vec![&env, unit_id]);
+}
+
+#[test]
+fn test_assign_unit_after_reservation_lapses() {
+    let (env, admin, client, contract_id) = create_test_contract();
+    env.ledger().set_timestamp(1000);
+    let (inventory, bank) = setup_inventory(&env, &client, &contract_id);
+
+    let unit_id = add_available_unit(
+        &env,
+        &inventory,
+        &bank,
+        UnitBloodType::OPositive,
+        1000 + 30 * 86400,
+    );
+    inventory.reserve_unit(&unit_id, &bank, &bank, &None, &3600u64);
+
+    // The hold lapses but nobody has released it yet
+    env.ledger().set_timestamp(1000 + 3600);
+    assert_eq!(
+        inventory.get_blood_unit(&unit_id).status,
+        UnitStatus::Reserved
+    );
+
+    let request_id = create_approved_request(&env, &client, &admin);
+    client.assign_blood_units(&request_id, &vec![&env, unit_id]);
+
+    let reservation = inventory.get_reservation(&unit_id).unwrap();
+    assert_eq!(reservation.request_id, Some(request_id));
+    assert_eq!(
+        reservation.expires_at,
+        1000 + 3600 + storage::UNIT_RESERVATION_SECONDS
+    );
+    assert_eq!(client.get_unit_assignment(&unit_id), Some(request_id));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #52)")]
+fn test_assign_expired_unit() {
+    let (env, admin, client, contract_id) = create_test_contract();
+    env.ledger().set_timestamp(1000);
+    let (inventory, bank) = setup_inventory(&env, &client, &contract_id);
+
+    let unit_id = add_available_unit(
+        &env,
+        &inventory,
+        &bank,
+        UnitBloodType::OPositive,
+        1000 + 2 * 86400,
+    );
+
+    env.ledger().set_timestamp(1000 + 3 * 86400);
+    let request_id = create_approved_request(&env, &client, &admin);
+    client.assign_blood_units(&request_id, &vec![&env, unit_id]);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #12)")]
+fn test_assign_duplicate_units() {
+    let (env, admin, client, contract_id) = create_test_contract();
+    env.ledger().set_timestamp(1000);
+    let (inventory, bank) = setup_inventory(&env, &client, &contract_id);
+
+    let unit_id = add_available_unit(
+        &env,
+        &inventory,
+        &bank,
+        UnitBloodType::OPositive,
+        1000 + 30 * 86400,
+    );
+    let request_id = create_approved_request(&env, &client, &admin);
+    client.assign_blood_units(&request_id, &vec![&env, unit_id, unit_id]);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #50)")]
+fn test_assign_without_inventory_contract() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+    env.ledger().set_timestamp(1000);
+
+    let request_id = create_approved_request(&env, &client, &admin);
+    client.assign_blood_units(&request_id, &vec![&env, 1u64]);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #55)")]
+fn test_assign_when_inventory_does_not_trust_requests() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+    env.ledger().set_timestamp(1000);
+
+    // Inventory lists a different request contract
+    let (inventory, bank) = setup_inventory(&env, &client, &Address::generate(&env));
+    let unit_id = add_available_unit(
+        &env,
+        &inventory,
+        &bank,
+        UnitBloodType::OPositive,
+        1000 + 30 * 86400,
+    );
+
+    let request_id = create_approved_request(&env, &client, &admin);
+    client.assign_blood_units(&request_id, &vec![&env, unit_id]);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #21)")]
+fn test_request_not_found() {
+    let (_env, _admin, client, _contract_id) = create_test_contract();
+
+    // Try to get a request that doesn't exist
+    client.get_request(&999u64);
+}
+
+#[test]
+fn test_urgency_level_max_fulfillment_time() {
+    assert_eq!(UrgencyLevel::Critical.max_fulfillment_time(), 3600); // 1 hour
+    assert_eq!(UrgencyLevel::Urgent.max_fulfillment_time(), 21600); // 6 hours
+    assert_eq!(UrgencyLevel::Normal.max_fulfillment_time(), 86400); // 24 hours
+}
+
+#[test]
+fn test_request_status_transitions() {
+    // Test valid transitions
+    assert!(RequestStatus::Pending.can_transition_to(&RequestStatus::Approved));
+    assert!(RequestStatus::Pending.can_transition_to(&RequestStatus::Rejected));
+    assert!(RequestStatus::Pending.can_transition_to(&RequestStatus::Cancelled));
+
+    assert!(RequestStatus::Approved.can_transition_to(&RequestStatus::Fulfilled));
+    assert!(RequestStatus::Approved.can_transition_to(&RequestStatus::Cancelled));
+
+    assert!(RequestStatus::Fulfilled.can_transition_to(&RequestStatus::InDelivery));
+    assert!(RequestStatus::InDelivery.can_transition_to(&RequestStatus::Completed));
+
+    // Test invalid transitions
+    assert!(!RequestStatus::Pending.can_transition_to(&RequestStatus::Fulfilled));
+    assert!(!RequestStatus::Fulfilled.can_transition_to(&RequestStatus::Completed));
+    assert!(!RequestStatus::Rejected.can_transition_to(&RequestStatus::Approved));
+    assert!(!RequestStatus::Completed.can_transition_to(&RequestStatus::Approved));
+    assert!(!RequestStatus::Cancelled.can_transition_to(&RequestStatus::Fulfilled));
+}
+
+#[test]
+fn test_request_status_is_terminal() {
+    assert!(!RequestStatus::Pending.is_terminal());
+    assert!(!RequestStatus::Approved.is_terminal());
+    assert!(!RequestStatus::Fulfilled.is_terminal());
+
+    assert!(RequestStatus::Completed.is_terminal());
+    assert!(RequestStatus::Rejected.is_terminal());
+    assert!(RequestStatus::Cancelled.is_terminal());
+}
+
+#[test]
+fn test_blood_request_validate_all_blood_types() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1000u64);
+
+    let hospital = Address::generate(&env);
+    let patient = Address::generate(&env);
+
+    let blood_types = [
+        BloodType::APositive,
+        BloodType::ANegative,
+        BloodType::BPositive,
+        BloodType::BNegative,
+        BloodType::ABPositive,
+        BloodType::ABNegative,
+        BloodType::OPositive,
+        BloodType::ONegative,
+    ];
+
+    for blood_type in blood_types.iter() {
+        let metadata = RequestMetadata {
+            patient_id: patient.clone(),
             procedure: String::from_str(&env, "Surgery"),
             notes: String::from_str(&env, "Notes"),
         };
@@ -667,6 +1240,7 @@ fn test_blood_request_validate_all_blood_types() {
             created_at: 1000u64,
             required_by: 2000u64,
             fulfilled_at: None,
+            fulfilled_quantity_ml: 0,
             assigned_units: soroban_sdk::vec![&env],
             delivery_address: String::from_str(&env, "Hospital"),
             metadata,
@@ -700,6 +1274,7 @@ fn test_blood_request_is_overdue() {
         created_at: 1000u64,
         required_by: 2000u64,
         fulfilled_at: None,
+        fulfilled_quantity_ml: 0,
         assigned_units: soroban_sdk::vec![&env],
         delivery_address: String::from_str(&env, "Hospital"),
         metadata,
@@ -734,6 +1309,7 @@ fn test_blood_request_time_remaining() {
         created_at: 1000u64,
         required_by: 2000u64,
         fulfilled_at: None,
+        fulfilled_quantity_ml: 0,
         assigned_units: soroban_sdk::vec![&env],
         delivery_address: String::from_str(&env, "Hospital"),
         metadata,
@@ -769,6 +1345,7 @@ fn test_blood_request_can_fulfill() {
         created_at: 1000u64,
         required_by: 2000u64,
         fulfilled_at: None,
+        fulfilled_quantity_ml: 0,
         assigned_units: soroban_sdk::vec![&env],
         delivery_address: String::from_str(&env, "Hospital"),
         metadata,
@@ -785,7 +1362,6 @@ fn test_blood_request_can_fulfill() {
     assert!(!request.can_fulfill(1500u64));
 }
 
-
 #[test]
 fn test_create_request_as_admin_success() {
     let (env, admin, client, _contract_id) = create_test_contract();
@@ -810,6 +1386,9 @@ fn test_create_request_as_admin_success() {
         &urgency,
         &required_by,
         &delivery_address,
+        &patient,
+        &procedure,
+        &notes,
     );
 
     assert_eq!(request_id, 1);
@@ -817,7 +1396,7 @@ fn test_create_request_as_admin_success() {
     // Verify stored request
     let stored_request = client.get_request(&request_id);
     assert_eq!(stored_request.id, 1);
-    assert_eq!(stored_request.hospital_id, hospital);
+    assert_eq!(stored_request.hospital_id, admin);
     assert_eq!(stored_request.blood_type, blood_type);
     assert_eq!(stored_request.quantity_ml, quantity_ml);
     assert_eq!(stored_request.urgency, urgency);
@@ -848,6 +1427,9 @@ fn test_create_request_increments_id() {
         &UrgencyLevel::Normal,
         &required_by,
         &delivery_address,
+        &Address::generate(&env),
+        &String::from_str(&env, "Surgery"),
+        &String::from_str(&env, "Notes"),
     );
     assert_eq!(id1, 1);
 
@@ -859,6 +1441,9 @@ fn test_create_request_increments_id() {
         &UrgencyLevel::Urgent,
         &required_by,
         &delivery_address,
+        &Address::generate(&env),
+        &String::from_str(&env, "Surgery"),
+        &String::from_str(&env, "Notes"),
     );
     assert_eq!(id2, 2);
 
@@ -870,6 +1455,9 @@ fn test_create_request_increments_id() {
         &UrgencyLevel::Critical,
         &(current_time + 2 * 3600), // Critical needs less time
         &delivery_address,
+        &Address::generate(&env),
+        &String::from_str(&env, "Surgery"),
+        &String::from_str(&env, "Notes"),
     );
     assert_eq!(id3, 3);
 }
@@ -905,6 +1493,9 @@ fn test_create_request_all_blood_types() {
             &UrgencyLevel::Normal,
             &required_by,
             &delivery_address,
+            &Address::generate(&env),
+            &String::from_str(&env, "Surgery"),
+            &String::from_str(&env, "Notes"),
         );
 
         assert_eq!(id, (i + 1) as u64);
@@ -933,6 +1524,9 @@ fn test_create_request_all_urgency_levels() {
         &UrgencyLevel::Critical,
         &(current_time + 2 * 3600), // 2 hours
         &delivery_address,
+        &Address::generate(&env),
+        &String::from_str(&env, "Surgery"),
+        &String::from_str(&env, "Notes"),
     );
     let req1 = client.get_request(&id1);
     assert_eq!(req1.urgency, UrgencyLevel::Critical);
@@ -945,6 +1539,9 @@ fn test_create_request_all_urgency_levels() {
         &UrgencyLevel::Urgent,
         &(current_time + 6 * 3600), // 6 hours
         &delivery_address,
+        &Address::generate(&env),
+        &String::from_str(&env, "Surgery"),
+        &String::from_str(&env, "Notes"),
     );
     let req2 = client.get_request(&id2);
     assert_eq!(req2.urgency, UrgencyLevel::Urgent);
@@ -957,31 +1554,14 @@ fn test_create_request_all_urgency_levels() {
         &UrgencyLevel::Normal,
         &(current_time + 48 * 3600), // 48 hours
         &delivery_address,
+        &Address::generate(&env),
+        &String::from_str(&env, "Surgery"),
+        &String::from_str(&env, "Notes"),
     );
     let req3 = client.get_request(&id3);
     assert_eq!(req3.urgency, UrgencyLevel::Normal);
 }
 
-#[test]
-#[should_panic(expected = "Error(Contract, #32)")]
-fn test_create_request_unauthorized_hospital() {
-    let (env, _admin, client, _contract_id) = create_test_contract();
-
-    let unauthorized_hospital = Address::generate(&env);
-
-    let current_time = 1000000u64;
-    env.ledger().set_timestamp(current_time);
-
-    client.create_request(
-        &unauthorized_hospital,
-        &BloodType::APositive,
-        &450u32,
-        &UrgencyLevel::Normal,
-        &(current_time + 7 * 86400),
-        &String::from_str(&env, "123 Hospital Street"),
-    );
-}
-
 #[test]
 #[should_panic(expected = "Error(Contract, #16)")]
 fn test_create_request_quantity_too_low() {
@@ -999,6 +1579,9 @@ fn test_create_request_quantity_too_low() {
         &UrgencyLevel::Normal,
         &(current_time + 7 * 86400),
         &String::from_str(&env, "123 Hospital Street"),
+        &Address::generate(&env),
+        &String::from_str(&env, "Surgery"),
+        &String::from_str(&env, "Notes"),
     );
 }
 
@@ -1019,6 +1602,9 @@ fn test_create_request_quantity_too_high() {
         &UrgencyLevel::Normal,
         &(current_time + 7 * 86400),
         &String::from_str(&env, "123 Hospital Street"),
+        &Address::generate(&env),
+        &String::from_str(&env, "Surgery"),
+        &String::from_str(&env, "Notes"),
     );
 }
 
@@ -1040,6 +1626,9 @@ fn test_create_request_required_by_too_soon() {
         &UrgencyLevel::Normal,
         &(current_time + 1800),
         &String::from_str(&env, "123 Hospital Street"),
+        &Address::generate(&env),
+        &String::from_str(&env, "Surgery"),
+        &String::from_str(&env, "Notes"),
     );
 }
 
@@ -1061,6 +1650,9 @@ fn test_create_request_required_by_too_far() {
         &UrgencyLevel::Normal,
         &(current_time + 60 * 86400),
         &String::from_str(&env, "123 Hospital Street"),
+        &Address::generate(&env),
+        &String::from_str(&env, "Surgery"),
+        &String::from_str(&env, "Notes"),
     );
 }
 
@@ -1081,6 +1673,9 @@ fn test_create_request_empty_delivery_address() {
         &UrgencyLevel::Normal,
         &(current_time + 7 * 86400),
         &String::from_str(&env, ""), // Empty address
+        &Address::generate(&env),
+        &String::from_str(&env, "Surgery"),
+        &String::from_str(&env, "Notes"),
     );
 }
 
@@ -1104,6 +1699,9 @@ fn test_create_request_edge_case_quantities() {
         &UrgencyLevel::Normal,
         &required_by,
         &delivery_address,
+        &Address::generate(&env),
+        &String::from_str(&env, "Surgery"),
+        &String::from_str(&env, "Notes"),
     );
     let req1 = client.get_request(&id1);
     assert_eq!(req1.quantity_ml, 100);
@@ -1116,6 +1714,9 @@ fn test_create_request_edge_case_quantities() {
         &UrgencyLevel::Normal,
         &required_by,
         &delivery_address,
+        &Address::generate(&env),
+        &String::from_str(&env, "Surgery"),
+        &String::from_str(&env, "Notes"),
     );
     let req2 = client.get_request(&id2);
     assert_eq!(req2.quantity_ml, 10000);
@@ -1139,6 +1740,9 @@ fn test_approve_request_success() {
         &UrgencyLevel::Normal,
         &(current_time + 7 * 86400),
         &String::from_str(&env, "123 Hospital Street"),
+        &Address::generate(&env),
+        &String::from_str(&env, "Surgery"),
+        &String::from_str(&env, "Notes"),
     );
 
     // Verify initial status
@@ -1178,6 +1782,9 @@ fn test_approve_request_already_approved() {
         &UrgencyLevel::Normal,
         &(current_time + 7 * 86400),
         &String::from_str(&env, "123 Hospital Street"),
+        &Address::generate(&env),
+        &String::from_str(&env, "Surgery"),
+        &String::from_str(&env, "Notes"),
     );
 
     // Approve first time
@@ -1206,6 +1813,9 @@ fn test_approve_request_expired() {
         &UrgencyLevel::Normal,
         &required_by,
         &String::from_str(&env, "123 Hospital Street"),
+        &Address::generate(&env),
+        &String::from_str(&env, "Surgery"),
+        &String::from_str(&env, "Notes"),
     );
 
     // Fast forward past required_by
@@ -1233,6 +1843,9 @@ fn test_cancel_request_by_hospital() {
         &UrgencyLevel::Normal,
         &(current_time + 7 * 86400),
         &String::from_str(&env, "123 Hospital Street"),
+        &Address::generate(&env),
+        &String::from_str(&env, "Surgery"),
+        &String::from_str(&env, "Notes"),
     );
 
     // Cancel by hospital
@@ -1259,6 +1872,9 @@ fn test_cancel_request_by_admin() {
         &UrgencyLevel::Normal,
         &(current_time + 7 * 86400),
         &String::from_str(&env, "123 Hospital Street"),
+        &Address::generate(&env),
+        &String::from_str(&env, "Surgery"),
+        &String::from_str(&env, "Notes"),
     );
 
     // Cancel by admin
@@ -1287,6 +1903,9 @@ fn test_cancel_request_unauthorized() {
         &UrgencyLevel::Normal,
         &(current_time + 7 * 86400),
         &String::from_str(&env, "123 Hospital Street"),
+        &Address::generate(&env),
+        &String::from_str(&env, "Surgery"),
+        &String::from_str(&env, "Notes"),
     );
 
     // Try to cancel by unauthorized party
@@ -1310,6 +1929,9 @@ fn test_cancel_request_already_cancelled() {
         &UrgencyLevel::Normal,
         &(current_time + 7 * 86400),
         &String::from_str(&env, "123 Hospital Street"),
+        &Address::generate(&env),
+        &String::from_str(&env, "Surgery"),
+        &String::from_str(&env, "Notes"),
     );
 
     // Cancel first time
@@ -1319,6 +1941,57 @@ fn test_cancel_request_already_cancelled() {
     client.cancel_request(&request_id, &hospital);
 }
 
+#[test]
+fn test_cancel_request_releases_assigned_units() {
+    let (env, _admin, client, contract_id) = create_test_contract();
+    env.ledger().set_timestamp(1000);
+    let (inventory, bank) = setup_inventory(&env, &client, &contract_id);
+    let hospital = setup_authorized_hospital(&env, &client);
+
+    let expiration = 1000 + 30 * 86400;
+    let shipped = add_available_unit(
+        &env,
+        &inventory,
+        &bank,
+        UnitBloodType::OPositive,
+        expiration,
+    );
+    let unshipped = add_available_unit(
+        &env,
+        &inventory,
+        &bank,
+        UnitBloodType::OPositive,
+        expiration,
+    );
+    let request_id = submit_request(
+        &env,
+        &client,
+        &hospital,
+        TestRequest::new().quantity_ml(900),
+    )
+    .unwrap();
+    client.approve_request(&request_id);
+    client.assign_blood_units(&request_id, &vec![&env, shipped, unshipped]);
+    client.record_fulfillment(&request_id, &bank, &vec![&env, shipped]);
+
+    client.cancel_request(&request_id, &hospital);
+
+    // The unit still on the shelf goes back to stock at once
+    assert_eq!(
+        inventory.get_blood_unit(&unshipped).status,
+        UnitStatus::Available
+    );
+    assert_eq!(inventory.get_reservation(&unshipped), None);
+    assert_eq!(client.get_unit_assignment(&unshipped), None);
+
+    // The unit already on its way is left alone
+    assert_eq!(
+        inventory.get_blood_unit(&shipped).status,
+        UnitStatus::InTransit
+    );
+    assert_eq!(client.get_unit_assignment(&shipped), Some(request_id));
+}
+
 // ========== Query Tests ==========
 
 #[test]
@@ -1342,6 +2015,9 @@ fn test_get_hospital_requests() {
         &UrgencyLevel::Normal,
         &required_by,
         &delivery_address,
+        &Address::generate(&env),
+        &String::from_str(&env, "Surgery"),
+        &String::from_str(&env, "Notes"),
     );
     let id2 = client.create_request(
         &hospital1,
@@ -1350,6 +2026,9 @@ fn test_get_hospital_requests() {
         &UrgencyLevel::Normal,
         &required_by,
         &delivery_address,
+        &Address::generate(&env),
+        &String::from_str(&env, "Surgery"),
+        &String::from_str(&env, "Notes"),
     );
 
     // Create request for hospital2
@@ -1360,6 +2039,9 @@ fn test_get_hospital_requests() {
         &UrgencyLevel::Normal,
         &required_by,
         &delivery_address,
+        &Address::generate(&env),
+        &String::from_str(&env, "Surgery"),
+        &String::from_str(&env, "Notes"),
     );
 
     // Query hospital1 requests
@@ -1393,6 +2075,9 @@ fn test_get_requests_by_status() {
         &UrgencyLevel::Normal,
         &required_by,
         &delivery_address,
+        &Address::generate(&env),
+        &String::from_str(&env, "Surgery"),
+        &String::from_str(&env, "Notes"),
     );
     let id2 = client.create_request(
         &hospital,
@@ -1401,6 +2086,9 @@ fn test_get_requests_by_status() {
         &UrgencyLevel::Normal,
         &required_by,
         &delivery_address,
+        &Address::generate(&env),
+        &String::from_str(&env, "Surgery"),
+        &String::from_str(&env, "Notes"),
     );
 
     // Approve one request
@@ -1437,6 +2125,9 @@ fn test_get_requests_by_blood_type() {
         &UrgencyLevel::Normal,
         &required_by,
         &delivery_address,
+        &Address::generate(&env),
+        &String::from_str(&env, "Surgery"),
+        &String::from_str(&env, "Notes"),
     );
     let _id2 = client.create_request(
         &hospital,
@@ -1445,6 +2136,9 @@ fn test_get_requests_by_blood_type() {
         &UrgencyLevel::Normal,
         &required_by,
         &delivery_address,
+        &Address::generate(&env),
+        &String::from_str(&env, "Surgery"),
+        &String::from_str(&env, "Notes"),
     );
     let id3 = client.create_request(
         &hospital,
@@ -1453,6 +2147,9 @@ fn test_get_requests_by_blood_type() {
         &UrgencyLevel::Urgent,
         &(current_time + 6 * 3600),
         &delivery_address,
+        &Address::generate(&env),
+        &String::from_str(&env, "Surgery"),
+        &String::from_str(&env, "Notes"),
     );
 
     // Query A+ requests
@@ -1481,6 +2178,9 @@ fn test_get_requests_by_urgency() {
         &UrgencyLevel::Critical,
         &(current_time + 2 * 3600),
         &delivery_address,
+        &Address::generate(&env),
+        &String::from_str(&env, "Surgery"),
+        &String::from_str(&env, "Notes"),
     );
     let _id2 = client.create_request(
         &hospital,
@@ -1489,6 +2189,9 @@ fn test_get_requests_by_urgency() {
         &UrgencyLevel::Normal,
         &(current_time + 48 * 3600),
         &delivery_address,
+        &Address::generate(&env),
+        &String::from_str(&env, "Surgery"),
+        &String::from_str(&env, "Notes"),
     );
     let id3 = client.create_request(
         &hospital,
@@ -1497,6 +2200,9 @@ fn test_get_requests_by_urgency() {
         &UrgencyLevel::Critical,
         &(current_time + 3 * 3600),
         &delivery_address,
+        &Address::generate(&env),
+        &String::from_str(&env, "Surgery"),
+        &String::from_str(&env, "Notes"),
     );
 
     // Query critical requests
@@ -1534,6 +2240,9 @@ fn test_critical_request_insufficient_time() {
         &UrgencyLevel::Critical,
         &(current_time + 1800), // 30 minutes - too short for critical
         &String::from_str(&env, "123 Hospital Street"),
+        &Address::generate(&env),
+        &String::from_str(&env, "Surgery"),
+        &String::from_str(&env, "Notes"),
     );
 }
 
@@ -1555,6 +2264,9 @@ fn test_urgent_request_insufficient_time() {
         &UrgencyLevel::Urgent,
         &(current_time + 2 * 3600), // 2 hours - too short for urgent
         &String::from_str(&env, "123 Hospital Street"),
+        &Address::generate(&env),
+        &String::from_str(&env, "Surgery"),
+        &String::from_str(&env, "Notes"),
     );
 }
 
@@ -1576,25 +2288,10 @@ fn test_normal_request_insufficient_time() {
         &UrgencyLevel::Normal,
         &(current_time + 12 * 3600), // 12 hours - too short for normal
         &String::from_str(&env, "123 Hospital Street"),
-        &patient,
-        &procedure,
-        &notes,
+        &Address::generate(&env),
+        &String::from_str(&env, "Surgery"),
+        &String::from_str(&env, "Notes"),
     );
-
-    assert_eq!(request_id, 1);
-
-    // Verify request was created
-    let request = client.get_request(&request_id);
-    assert_eq!(request.id, request_id);
-    assert_eq!(request.hospital_id, admin);
-    assert_eq!(request.blood_type, blood_type);
-    assert_eq!(request.quantity_ml, quantity_ml);
-    assert_eq!(request.urgency, urgency);
-    assert_eq!(request.status, RequestStatus::Pending);
-    assert_eq!(request.created_at, current_time);
-    assert_eq!(request.required_by, required_by);
-    assert_eq!(request.fulfilled_at, None);
-    assert_eq!(request.delivery_address, delivery_address);
 }
 
 // ========== Advanced Query Function Tests ==========
@@ -1626,7 +2323,7 @@ fn test_get_request_by_id_exists() {
     // Test get_request_by_id
     let result = client.get_request_by_id(&request_id);
     assert!(result.is_some());
-    
+
     let request = result.unwrap();
     assert_eq!(request.id, request_id);
     assert_eq!(request.blood_type, BloodType::OPositive);
@@ -1643,11 +2340,11 @@ fn test_get_request_by_id_non_existent() {
 
 #[test]
 fn test_query_hospital_requests_all() {
-    let (env, admin, client, _contract_id) = create_test_contract();
+    let (env, _admin, client, _contract_id) = create_test_contract();
 
     let hospital = setup_authorized_hospital(&env, &client);
     let patient = Address::generate(&env);
-    
+
     let current_time = 1000u64;
     env.ledger().set_timestamp(current_time);
 
@@ -1699,11 +2396,11 @@ fn test_query_hospital_requests_all() {
 
 #[test]
 fn test_query_hospital_requests_with_status_filter() {
-    let (env, admin, client, _contract_id) = create_test_contract();
+    let (env, _admin, client, _contract_id) = create_test_contract();
 
     let hospital = setup_authorized_hospital(&env, &client);
     let patient = Address::generate(&env);
-    
+
     let current_time = 1000u64;
     env.ledger().set_timestamp(current_time);
 
@@ -1740,33 +2437,25 @@ fn test_query_hospital_requests_with_status_filter() {
     client.approve_request(&req1);
 
     // Query pending requests only
-    let pending_requests = client.query_hospital_requests(
-        &hospital,
-        &Some(RequestStatus::Pending),
-        &None,
-        &None,
-    );
+    let pending_requests =
+        client.query_hospital_requests(&hospital, &Some(RequestStatus::Pending), &None, &None);
     assert_eq!(pending_requests.len(), 1);
     assert_eq!(pending_requests.get(0).unwrap().id, req2);
 
     // Query approved requests only
-    let approved_requests = client.query_hospital_requests(
-        &hospital,
-        &Some(RequestStatus::Approved),
-        &None,
-        &None,
-    );
+    let approved_requests =
+        client.query_hospital_requests(&hospital, &Some(RequestStatus::Approved), &None, &None);
     assert_eq!(approved_requests.len(), 1);
     assert_eq!(approved_requests.get(0).unwrap().id, req1);
 }
 
 #[test]
 fn test_query_hospital_requests_pagination() {
-    let (env, admin, client, _contract_id) = create_test_contract();
+    let (env, _admin, client, _contract_id) = create_test_contract();
 
     let hospital = setup_authorized_hospital(&env, &client);
     let patient = Address::generate(&env);
-    
+
     let current_time = 1000u64;
     env.ledger().set_timestamp(current_time);
 
@@ -1833,7 +2522,7 @@ fn test_query_pending_requests_sorted_by_urgency() {
         &admin,
         &BloodType::OPositive,
         &450u32,
-        &UrgencyLevel::Normal,  // Priority 1
+        &UrgencyLevel::Normal, // Priority 1
         &(current_time + 86400),
         &delivery_address,
         &patient,
@@ -1845,7 +2534,7 @@ fn test_query_pending_requests_sorted_by_urgency() {
         &admin,
         &BloodType::BPositive,
         &500u32,
-        &UrgencyLevel::Critical,  // Priority 3
+        &UrgencyLevel::Critical, // Priority 3
         &(current_time + 7200),
         &delivery_address,
         &patient,
@@ -1857,7 +2546,7 @@ fn test_query_pending_requests_sorted_by_urgency() {
         &admin,
         &BloodType::ABNegative,
         &350u32,
-        &UrgencyLevel::Urgent,  // Priority 2
+        &UrgencyLevel::Urgent, // Priority 2
         &(current_time + 21600),
         &delivery_address,
         &patient,
@@ -1879,7 +2568,6 @@ fn test_query_pending_requests_sorted_by_urgency() {
 fn test_query_pending_requests_pagination() {
     let (env, admin, client, _contract_id) = create_test_contract();
 
-    let patient = Address::generate(&env);
     let current_time = 1000u64;
     env.ledger().set_timestamp(current_time);
 
@@ -1896,7 +2584,7 @@ fn test_query_pending_requests_pagination() {
             &UrgencyLevel::Urgent,
             &(current_time + 86400),
             &delivery_address,
-            &patient,
+            &Address::generate(&env),
             &procedure,
             &notes,
         );
@@ -1929,7 +2617,7 @@ fn test_query_requests_by_date_range() {
         &BloodType::OPositive,
         &450u32,
         &UrgencyLevel::Normal,
-        &2000u64,
+        &(1000u64 + 86400),
         &delivery_address,
         &patient,
         &procedure,
@@ -1942,7 +2630,7 @@ fn test_query_requests_by_date_range() {
         &BloodType::BPositive,
         &500u32,
         &UrgencyLevel::Urgent,
-        &6000u64,
+        &(5000u64 + 21600),
         &delivery_address,
         &patient,
         &procedure,
@@ -1955,7 +2643,7 @@ fn test_query_requests_by_date_range() {
         &BloodType::ABNegative,
         &350u32,
         &UrgencyLevel::Critical,
-        &11000u64,
+        &(10000u64 + 7200),
         &delivery_address,
         &patient,
         &procedure,
@@ -1963,33 +2651,15 @@ fn test_query_requests_by_date_range() {
     );
 
     // Query requests created between 1000 and 6000
-    let requests = client.query_requests_by_date_range(
-        &1000u64,
-        &6000u64,
-        &None,
-        &None,
-        &None,
-    );
+    let requests = client.query_requests_by_date_range(&1000u64, &6000u64, &None, &None, &None);
     assert_eq!(requests.len(), 2);
 
     // Query requests created between 5000 and 10000
-    let requests2 = client.query_requests_by_date_range(
-        &5000u64,
-        &10000u64,
-        &None,
-        &None,
-        &None,
-    );
+    let requests2 = client.query_requests_by_date_range(&5000u64, &10000u64, &None, &None, &None);
     assert_eq!(requests2.len(), 2);
 
     // Query with narrow range
-    let requests3 = client.query_requests_by_date_range(
-        &4000u64,
-        &6000u64,
-        &None,
-        &None,
-        &None,
-    );
+    let requests3 = client.query_requests_by_date_range(&4000u64, &6000u64, &None, &None, &None);
     assert_eq!(requests3.len(), 1);
 }
 
@@ -2009,7 +2679,7 @@ fn test_query_requests_by_date_range_with_status_filter() {
         &BloodType::OPositive,
         &450u32,
         &UrgencyLevel::Normal,
-        &2000u64,
+        &(1000u64 + 86400),
         &delivery_address,
         &patient,
         &procedure,
@@ -2022,7 +2692,7 @@ fn test_query_requests_by_date_range_with_status_filter() {
         &BloodType::BPositive,
         &500u32,
         &UrgencyLevel::Urgent,
-        &3000u64,
+        &(2000u64 + 21600),
         &delivery_address,
         &patient,
         &procedure,
@@ -2108,16 +2778,12 @@ fn test_query_requests_by_urgency_and_status() {
     client.approve_request(&req1);
 
     // Query all critical requests (no status filter)
-    let critical_all = client.query_requests_by_urgency_and_status(
-        &UrgencyLevel::Critical,
-        &None,
-        &None,
-        &None,
-    );
+    let critical_all =
+        client.query_by_urgency_and_status(&UrgencyLevel::Critical, &None, &None, &None);
     assert_eq!(critical_all.len(), 2);
 
     // Query critical pending requests only
-    let critical_pending = client.query_requests_by_urgency_and_status(
+    let critical_pending = client.query_by_urgency_and_status(
         &UrgencyLevel::Critical,
         &Some(RequestStatus::Pending),
         &None,
@@ -2126,7 +2792,7 @@ fn test_query_requests_by_urgency_and_status() {
     assert_eq!(critical_pending.len(), 1);
 
     // Query critical approved requests only
-    let critical_approved = client.query_requests_by_urgency_and_status(
+    let critical_approved = client.query_by_urgency_and_status(
         &UrgencyLevel::Critical,
         &Some(RequestStatus::Approved),
         &None,
@@ -2139,7 +2805,6 @@ fn test_query_requests_by_urgency_and_status() {
 fn test_pagination_edge_cases() {
     let (env, admin, client, _contract_id) = create_test_contract();
 
-    let patient = Address::generate(&env);
     let current_time = 1000u64;
     env.ledger().set_timestamp(current_time);
 
@@ -2156,7 +2821,7 @@ fn test_pagination_edge_cases() {
             &UrgencyLevel::Normal,
             &(current_time + 86400),
             &delivery_address,
-            &patient,
+            &Address::generate(&env),
             &procedure,
             &notes,
         );
@@ -2179,7 +2844,6 @@ fn test_pagination_edge_cases() {
 fn test_max_query_limit_enforcement() {
     let (env, admin, client, _contract_id) = create_test_contract();
 
-    let patient = Address::generate(&env);
     let current_time = 1000u64;
     env.ledger().set_timestamp(current_time);
 
@@ -2196,7 +2860,7 @@ fn test_max_query_limit_enforcement() {
             &UrgencyLevel::Normal,
             &(current_time + 86400),
             &delivery_address,
-            &patient,
+            &Address::generate(&env),
             &procedure,
             &notes,
         );
@@ -2208,3 +2872,1806 @@ fn test_max_query_limit_enforcement() {
     assert_eq!(result.len(), 10);
 }
 
+// ========== Pending Queue Tests ==========
+
+/// Submit a pending request with the given urgency and deadline
+fn submit_pending(
+    env: &Env,
+    client: &RequestContractClient,
+    hospital: &Address,
+    urgency: UrgencyLevel,
+    required_by: u64,
+) -> u64 {
+    let request = TestRequest::new().urgency(urgency).required_by(required_by);
+    submit_request(env, client, hospital, request).unwrap()
+}
+
+fn ids(requests: &soroban_sdk::Vec<BloodRequest>) -> soroban_sdk::Vec<u64> {
+    let mut ids = soroban_sdk::Vec::new(requests.env());
+    for request in requests.iter() {
+        ids.push_back(request.id);
+    }
+    ids
+}
+
+#[test]
+fn test_pending_queue_orders_by_urgency_then_deadline() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+    env.ledger().set_timestamp(1000);
+
+    let normal = submit_pending(&env, &client, &admin, UrgencyLevel::Normal, 1000 + 86400);
+    let urgent_late = submit_pending(&env, &client, &admin, UrgencyLevel::Urgent, 1000 + 86400);
+    let urgent_soon = submit_pending(&env, &client, &admin, UrgencyLevel::Urgent, 1000 + 43200);
+    env.ledger().set_timestamp(1100);
+    let urgent_late_newer =
+        submit_pending(&env, &client, &admin, UrgencyLevel::Urgent, 1000 + 86400);
+    let critical = submit_pending(&env, &client, &admin, UrgencyLevel::Critical, 1000 + 86400);
+
+    assert_eq!(
+        ids(&client.query_pending_requests(&None, &None)),
+        vec![
+            &env,
+            critical,
+            urgent_soon,
+            urgent_late,
+            urgent_late_newer,
+            normal
+        ]
+    );
+    assert_eq!(
+        ids(&client.next_requests(&2)),
+        vec![&env, critical, urgent_soon]
+    );
+    assert_eq!(
+        ids(&client.query_pending_requests(&Some(2), &Some(3))),
+        vec![&env, urgent_late_newer, normal]
+    );
+}
+
+#[test]
+fn test_pending_queue_follows_status_changes() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+    env.ledger().set_timestamp(1000);
+    let hospital = setup_authorized_hospital(&env, &client);
+
+    let approved = submit_pending(
+        &env,
+        &client,
+        &hospital,
+        UrgencyLevel::Critical,
+        1000 + 7200,
+    );
+    let cancelled = submit_pending(&env, &client, &hospital, UrgencyLevel::Urgent, 1000 + 86400);
+    let rejected = submit_pending(&env, &client, &hospital, UrgencyLevel::Urgent, 1000 + 86400);
+    let open = submit_pending(&env, &client, &hospital, UrgencyLevel::Normal, 1000 + 86400);
+
+    client.approve_request(&approved);
+    client.cancel_request(&cancelled, &hospital);
+    client.reject_request(
+        &rejected,
+        &admin,
+        &RejectionReason::NoStock,
+        &String::from_str(&env, ""),
+    );
+
+    assert_eq!(ids(&client.next_requests(&10)), vec![&env, open]);
+}
+
+#[test]
+fn test_pending_queue_repositions_on_escalation_and_amendment() {
+    let (env, _admin, client, _contract_id) = create_test_contract();
+    env.ledger().set_timestamp(1000);
+    let hospital = setup_authorized_hospital(&env, &client);
+
+    let first = submit_pending(&env, &client, &hospital, UrgencyLevel::Urgent, 1000 + 43200);
+    let second = submit_pending(&env, &client, &hospital, UrgencyLevel::Urgent, 1000 + 86400);
+    let third = submit_pending(&env, &client, &hospital, UrgencyLevel::Normal, 1000 + 86400);
+
+    client.escalate_request(
+        &third,
+        &UrgencyLevel::Critical,
+        &String::from_str(&env, "Massive transfusion protocol"),
+    );
+    assert_eq!(
+        ids(&client.next_requests(&3)),
+        vec![&env, third, first, second]
+    );
+
+    // Bringing the deadline forward moves the request ahead of its peers
+    let request = client.get_request(&second);
+    client.amend_request(
+        &second,
+        &request.blood_type,
+        &request.quantity_ml,
+        &(1000 + 21600),
+        &request.delivery_address,
+    );
+    assert_eq!(
+        ids(&client.next_requests(&3)),
+        vec![&env, third, second, first]
+    );
+}
+
+fn get_pending_shard(env: &Env, contract_id: &Address, urgency: UrgencyLevel) -> PendingQueueShard {
+    env.as_contract(contract_id, || {
+        env.storage()
+            .persistent()
+            .get(&DataKey::PendingQueueShard(urgency))
+            .unwrap()
+    })
+}
+
+#[test]
+fn test_pending_queue_spans_pages() {
+    let (env, _admin, client, contract_id) = create_test_contract();
+    env.ledger().set_timestamp(1000);
+    let hospital = setup_authorized_hospital(&env, &client);
+
+    // Each request is due sooner than the last, so it goes to the front
+    let mut expected = soroban_sdk::Vec::new(&env);
+    for i in 0..250u64 {
+        let required_by = 1000 + 86400 + (250 - i) * 60;
+        let id = submit_pending(&env, &client, &hospital, UrgencyLevel::Normal, required_by);
+        expected.push_front(id);
+    }
+    let critical = submit_pending(
+        &env,
+        &client,
+        &hospital,
+        UrgencyLevel::Critical,
+        1000 + 7200,
+    );
+    expected.push_front(critical);
+
+    let shard = get_pending_shard(&env, &contract_id, UrgencyLevel::Normal);
+    assert_eq!(shard.len, 250);
+    assert!(shard.pages.len() > 2);
+    assert!(shard
+        .pages
+        .iter()
+        .all(|page| page.len <= storage::PENDING_QUEUE_PAGE_SIZE));
+
+    // Pages of the query cross page and shard boundaries
+    let mut queued = ids(&client.query_pending_requests(&Some(200), &Some(0)));
+    queued.append(&ids(&client.query_pending_requests(&Some(200), &Some(200))));
+    assert_eq!(queued, expected);
+    assert_eq!(
+        ids(&client.query_pending_requests(&Some(3), &Some(99))),
+        expected.slice(99..102)
+    );
+
+    // Emptied pages are folded back together
+    let mut remaining = soroban_sdk::Vec::new(&env);
+    for (i, id) in expected.iter().enumerate() {
+        if id == critical || i % 5 == 0 {
+            remaining.push_back(id);
+        } else {
+            client.cancel_request(&id, &hospital);
+        }
+    }
+    let shard = get_pending_shard(&env, &contract_id, UrgencyLevel::Normal);
+    assert_eq!(shard.len, remaining.len() - 1);
+    assert_eq!(shard.pages.len(), 1);
+    assert_eq!(ids(&client.next_requests(&200)), remaining);
+}
+
+#[test]
+fn test_full_pending_queue_rejects_request() {
+    let (env, _admin, client, contract_id) = create_test_contract();
+    env.ledger().set_timestamp(1000);
+    let hospital = setup_authorized_hospital(&env, &client);
+
+    env.as_contract(&contract_id, || {
+        let shard = PendingQueueShard {
+            len: storage::MAX_PENDING_PER_URGENCY,
+            next_page_id: 0,
+            pages: soroban_sdk::Vec::new(&env),
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::PendingQueueShard(UrgencyLevel::Critical), &shard);
+    });
+
+    let result = client.try_create_request(
+        &hospital,
+        &BloodType::OPositive,
+        &450u32,
+        &UrgencyLevel::Critical,
+        &(1000 + 7200),
+        &String::from_str(&env, "Hospital"),
+        &Address::generate(&env),
+        &String::from_str(&env, "Surgery"),
+        &String::from_str(&env, "Notes"),
+    );
+    assert_eq!(result, Err(Ok(ContractError::PendingQueueFull)));
+
+    // Other urgency levels are queued separately
+    let normal = submit_pending(&env, &client, &hospital, UrgencyLevel::Normal, 1000 + 86400);
+    let result = client.try_escalate_request(
+        &normal,
+        &UrgencyLevel::Critical,
+        &String::from_str(&env, "Patient deteriorated"),
+    );
+    assert_eq!(result, Err(Ok(ContractError::PendingQueueFull)));
+    assert_eq!(client.get_request(&normal).urgency, UrgencyLevel::Normal);
+    assert_eq!(ids(&client.next_requests(&10)), vec![&env, normal]);
+}
+
+// ========== Urgency Escalation Tests ==========
+
+#[test]
+fn test_escalate_request() {
+    let (env, _admin, client, _contract_id) = create_test_contract();
+    env.ledger().set_timestamp(1000);
+    let hospital = setup_authorized_hospital(&env, &client);
+
+    let request_id = submit_request(
+        &env,
+        &client,
+        &hospital,
+        TestRequest::new().urgency(UrgencyLevel::Normal),
+    )
+    .unwrap();
+    let reason = String::from_str(&env, "Hemoglobin dropping");
+
+    env.ledger().set_timestamp(5000);
+    client.escalate_request(&request_id, &UrgencyLevel::Critical, &reason);
+
+    // Only the owning hospital signed
+    let auths = env.auths();
+    assert_eq!(auths.len(), 1);
+    assert_eq!(auths[0].0, hospital);
+
+    let events = env.events().all();
+    let (_, _, data) = events.get(events.len() - 1).unwrap();
+    let event: RequestEscalatedEvent = data.into_val(&env);
+    assert_eq!(event.request_id, request_id);
+    assert_eq!(event.from_urgency, UrgencyLevel::Normal);
+    assert_eq!(event.to_urgency, UrgencyLevel::Critical);
+
+    assert_eq!(
+        client.get_request(&request_id).urgency,
+        UrgencyLevel::Critical
+    );
+    assert_eq!(
+        client.get_requests_by_urgency(&UrgencyLevel::Normal).len(),
+        0
+    );
+    assert_eq!(
+        client.get_requests_by_urgency(&UrgencyLevel::Critical),
+        vec![&env, request_id]
+    );
+
+    let escalations = client.get_request_escalations(&request_id);
+    assert_eq!(escalations.len(), 1);
+    let escalation = escalations.get(0).unwrap();
+    assert_eq!(escalation.from_urgency, UrgencyLevel::Normal);
+    assert_eq!(escalation.reason, reason);
+    assert_eq!(escalation.escalated_at, 5000);
+}
+
+#[test]
+fn test_escalated_request_moves_up_pending_queue() {
+    let (env, _admin, client, _contract_id) = create_test_contract();
+    env.ledger().set_timestamp(1000);
+    let hospital = setup_authorized_hospital(&env, &client);
+
+    let normal = submit_request(
+        &env,
+        &client,
+        &hospital,
+        TestRequest::new().urgency(UrgencyLevel::Normal),
+    )
+    .unwrap();
+    let urgent = submit_request(&env, &client, &hospital, TestRequest::new()).unwrap();
+    let escalated = submit_request(
+        &env,
+        &client,
+        &hospital,
+        TestRequest::new().urgency(UrgencyLevel::Normal),
+    )
+    .unwrap();
+
+    let queue = client.query_pending_requests(&None, &None);
+    assert_eq!(queue.get(0).unwrap().id, urgent);
+
+    client.escalate_request(
+        &escalated,
+        &UrgencyLevel::Critical,
+        &String::from_str(&env, "Patient in shock"),
+    );
+
+    let queue = client.query_pending_requests(&None, &None);
+    assert_eq!(queue.get(0).unwrap().id, escalated);
+    assert_eq!(queue.get(1).unwrap().id, urgent);
+    assert_eq!(queue.get(2).unwrap().id, normal);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #18)")]
+fn test_escalate_request_cannot_lower_urgency() {
+    let (env, _admin, client, _contract_id) = create_test_contract();
+    env.ledger().set_timestamp(1000);
+    let hospital = setup_authorized_hospital(&env, &client);
+
+    let request_id = submit_request(&env, &client, &hospital, TestRequest::new()).unwrap();
+    client.escalate_request(
+        &request_id,
+        &UrgencyLevel::Normal,
+        &String::from_str(&env, "Stable"),
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #17)")]
+fn test_escalate_request_rechecks_time_window() {
+    let (env, _admin, client, _contract_id) = create_test_contract();
+    env.ledger().set_timestamp(1000);
+    let hospital = setup_authorized_hospital(&env, &client);
+
+    // Less than an hour left is too short even for a critical request
+    let request_id = submit_pending(
+        &env,
+        &client,
+        &hospital,
+        UrgencyLevel::Normal,
+        1000 + 7 * 86400,
+    );
+    env.ledger().set_timestamp(1000 + 7 * 86400 - 1800);
+    client.escalate_request(
+        &request_id,
+        &UrgencyLevel::Critical,
+        &String::from_str(&env, "Deteriorating"),
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #23)")]
+fn test_escalate_cancelled_request() {
+    let (env, _admin, client, _contract_id) = create_test_contract();
+    env.ledger().set_timestamp(1000);
+    let hospital = setup_authorized_hospital(&env, &client);
+
+    let request_id = submit_request(
+        &env,
+        &client,
+        &hospital,
+        TestRequest::new().urgency(UrgencyLevel::Normal),
+    )
+    .unwrap();
+    client.cancel_request(&request_id, &hospital);
+    client.escalate_request(
+        &request_id,
+        &UrgencyLevel::Urgent,
+        &String::from_str(&env, "Deteriorating"),
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #12)")]
+fn test_escalate_request_requires_reason() {
+    let (env, _admin, client, _contract_id) = create_test_contract();
+    env.ledger().set_timestamp(1000);
+    let hospital = setup_authorized_hospital(&env, &client);
+
+    let request_id = submit_request(
+        &env,
+        &client,
+        &hospital,
+        TestRequest::new().urgency(UrgencyLevel::Normal),
+    )
+    .unwrap();
+    client.escalate_request(
+        &request_id,
+        &UrgencyLevel::Urgent,
+        &String::from_str(&env, ""),
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_escalate_request_requires_owning_hospital() {
+    let env = Env::default();
+    let contract_id = env.register(RequestContract, ());
+    let client = RequestContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let hospital = Address::generate(&env);
+    env.mock_all_auths();
+    client.initialize(&admin);
+    client.authorize_hospital(&hospital);
+    env.ledger().set_timestamp(1000);
+    let request_id = submit_request(
+        &env,
+        &client,
+        &hospital,
+        TestRequest::new().urgency(UrgencyLevel::Normal),
+    )
+    .unwrap();
+
+    // Only the admin signs; the owning hospital's auth is missing
+    env.mock_auths(&[soroban_sdk::testutils::MockAuth {
+        address: &admin,
+        invoke: &soroban_sdk::testutils::MockAuthInvoke {
+            contract: &contract_id,
+            fn_name: "escalate_request",
+            args: (
+                request_id,
+                UrgencyLevel::Urgent,
+                String::from_str(&env, "Deteriorating"),
+            )
+                .into_val(&env),
+            sub_invokes: &[],
+        },
+    }]);
+    client.escalate_request(
+        &request_id,
+        &UrgencyLevel::Urgent,
+        &String::from_str(&env, "Deteriorating"),
+    );
+}
+
+// ========== Amendment Tests ==========
+
+#[test]
+fn test_amend_request() {
+    let (env, _admin, client, _contract_id) = create_test_contract();
+    env.ledger().set_timestamp(1000);
+    let hospital = setup_authorized_hospital(&env, &client);
+
+    let request_id = submit_request(&env, &client, &hospital, TestRequest::new()).unwrap();
+    let new_address = String::from_str(&env, "Ward 7, General Hospital");
+
+    env.ledger().set_timestamp(2000);
+    client.amend_request(
+        &request_id,
+        &BloodType::ONegative,
+        &900u32,
+        &(2000 + 2 * 86400),
+        &new_address,
+    );
+
+    let auths = env.auths();
+    assert_eq!(auths.len(), 1);
+    assert_eq!(auths[0].0, hospital);
+
+    let events = env.events().all();
+    let (_, _, data) = events.get(events.len() - 1).unwrap();
+    let event: RequestAmendedEvent = data.into_val(&env);
+    assert_eq!(event.request_id, request_id);
+    assert_eq!(event.hospital_id, hospital);
+    assert_eq!(event.amendment.old_blood_type, BloodType::OPositive);
+    assert_eq!(event.amendment.new_blood_type, BloodType::ONegative);
+    assert_eq!(event.amendment.old_quantity_ml, 450);
+    assert_eq!(event.amendment.new_quantity_ml, 900);
+
+    let request = client.get_request(&request_id);
+    assert_eq!(request.blood_type, BloodType::ONegative);
+    assert_eq!(request.quantity_ml, 900);
+    assert_eq!(request.required_by, 2000 + 2 * 86400);
+    assert_eq!(request.delivery_address, new_address);
+
+    let amendments = client.get_request_amendments(&request_id);
+    assert_eq!(amendments.len(), 1);
+    let amendment = amendments.get(0).unwrap();
+    assert_eq!(amendment, event.amendment);
+    assert_eq!(amendment.old_required_by, 1000 + 86400);
+    assert_eq!(
+        amendment.old_delivery_address,
+        String::from_str(&env, "Hospital")
+    );
+    assert_eq!(amendment.amended_at, 2000);
+}
+
+#[test]
+fn test_amend_request_moves_blood_type_index() {
+    let (env, _admin, client, _contract_id) = create_test_contract();
+    env.ledger().set_timestamp(1000);
+    let hospital = setup_authorized_hospital(&env, &client);
+
+    let request_id = submit_request(&env, &client, &hospital, TestRequest::new()).unwrap();
+    let request = client.get_request(&request_id);
+    client.amend_request(
+        &request_id,
+        &BloodType::ABPositive,
+        &request.quantity_ml,
+        &request.required_by,
+        &request.delivery_address,
+    );
+
+    assert_eq!(
+        client
+            .get_requests_by_blood_type(&BloodType::OPositive)
+            .len(),
+        0
+    );
+    assert_eq!(
+        client.get_requests_by_blood_type(&BloodType::ABPositive),
+        vec![&env, request_id]
+    );
+}
+
+#[test]
+fn test_amend_request_keeps_history() {
+    let (env, _admin, client, _contract_id) = create_test_contract();
+    env.ledger().set_timestamp(1000);
+    let hospital = setup_authorized_hospital(&env, &client);
+
+    let request_id = submit_request(&env, &client, &hospital, TestRequest::new()).unwrap();
+    let request = client.get_request(&request_id);
+    client.amend_request(
+        &request_id,
+        &request.blood_type,
+        &600u32,
+        &request.required_by,
+        &request.delivery_address,
+    );
+    client.amend_request(
+        &request_id,
+        &request.blood_type,
+        &750u32,
+        &request.required_by,
+        &request.delivery_address,
+    );
+
+    let amendments = client.get_request_amendments(&request_id);
+    assert_eq!(amendments.len(), 2);
+    assert_eq!(amendments.get(0).unwrap().old_quantity_ml, 450);
+    assert_eq!(amendments.get(1).unwrap().old_quantity_ml, 600);
+    assert_eq!(amendments.get(1).unwrap().new_quantity_ml, 750);
+}
+
+#[test]
+fn test_amend_request_only_while_pending() {
+    let (env, _admin, client, _contract_id) = create_test_contract();
+    env.ledger().set_timestamp(1000);
+    let hospital = setup_authorized_hospital(&env, &client);
+
+    let request_id = create_approved_request(&env, &client, &hospital);
+    let request = client.get_request(&request_id);
+    assert_eq!(
+        client.try_amend_request(
+            &request_id,
+            &request.blood_type,
+            &900u32,
+            &request.required_by,
+            &request.delivery_address,
+        ),
+        Err(Ok(ContractError::InvalidRequestState))
+    );
+}
+
+#[test]
+fn test_amend_request_revalidates() {
+    let (env, _admin, client, _contract_id) = create_test_contract();
+    env.ledger().set_timestamp(1000);
+    let hospital = setup_authorized_hospital(&env, &client);
+
+    let request_id = submit_request(&env, &client, &hospital, TestRequest::new()).unwrap();
+    let request = client.get_request(&request_id);
+
+    assert_eq!(
+        client.try_amend_request(
+            &request_id,
+            &request.blood_type,
+            &50u32,
+            &request.required_by,
+            &request.delivery_address,
+        ),
+        Err(Ok(ContractError::InvalidQuantity))
+    );
+    // Urgent requests need at least four hours
+    assert_eq!(
+        client.try_amend_request(
+            &request_id,
+            &request.blood_type,
+            &request.quantity_ml,
+            &(1000 + 2 * 3600),
+            &request.delivery_address,
+        ),
+        Err(Ok(ContractError::InvalidRequiredBy))
+    );
+    assert_eq!(
+        client.try_amend_request(
+            &request_id,
+            &request.blood_type,
+            &request.quantity_ml,
+            &request.required_by,
+            &String::from_str(&env, ""),
+        ),
+        Err(Ok(ContractError::InvalidDeliveryAddress))
+    );
+    // Nothing changed
+    assert_eq!(
+        client.try_amend_request(
+            &request_id,
+            &request.blood_type,
+            &request.quantity_ml,
+            &request.required_by,
+            &request.delivery_address,
+        ),
+        Err(Ok(ContractError::InvalidInput))
+    );
+    assert_eq!(client.get_request_amendments(&request_id).len(), 0);
+}
+
+// ========== Duplicate Detection Tests ==========
+
+#[test]
+fn test_create_request_rejects_resubmission() {
+    let (env, _admin, client, _contract_id) = create_test_contract();
+    env.ledger().set_timestamp(1000);
+    let hospital = setup_authorized_hospital(&env, &client);
+    let patient = Address::generate(&env);
+    let required_by = 1000 + 3 * 86400;
+
+    let request_id = submit_request(
+        &env,
+        &client,
+        &hospital,
+        TestRequest::new()
+            .patient(&patient)
+            .required_by(required_by),
+    )
+    .unwrap();
+
+    // A retried call a few minutes later is refused
+    env.ledger().set_timestamp(1300);
+    assert_eq!(
+        submit_request(
+            &env,
+            &client,
+            &hospital,
+            TestRequest::new()
+                .patient(&patient)
+                .required_by(required_by)
+        ),
+        Err(ContractError::DuplicateRequest)
+    );
+    assert_eq!(
+        client.get_hospital_requests(&hospital),
+        vec![&env, request_id]
+    );
+
+    // Another patient, or another deadline, is a different request
+    assert!(submit_request(
+        &env,
+        &client,
+        &hospital,
+        TestRequest::new().required_by(required_by)
+    )
+    .is_ok());
+    assert!(submit_request(
+        &env,
+        &client,
+        &hospital,
+        TestRequest::new()
+            .patient(&patient)
+            .required_by(required_by + 3600)
+    )
+    .is_ok());
+}
+
+#[test]
+fn test_resubmission_allowed_after_window() {
+    let (env, _admin, client, _contract_id) = create_test_contract();
+    env.ledger().set_timestamp(1000);
+    let hospital = setup_authorized_hospital(&env, &client);
+    let patient = Address::generate(&env);
+    let required_by = 1000 + 3 * 86400;
+
+    let first = submit_request(
+        &env,
+        &client,
+        &hospital,
+        TestRequest::new()
+            .patient(&patient)
+            .required_by(required_by),
+    )
+    .unwrap();
+
+    env.ledger().set_timestamp(1000 + 86400);
+    let second = submit_request(
+        &env,
+        &client,
+        &hospital,
+        TestRequest::new()
+            .patient(&patient)
+            .required_by(required_by),
+    )
+    .unwrap();
+    assert_ne!(first, second);
+}
+
+#[test]
+fn test_resubmission_allowed_after_cancel() {
+    let (env, _admin, client, _contract_id) = create_test_contract();
+    env.ledger().set_timestamp(1000);
+    let hospital = setup_authorized_hospital(&env, &client);
+    let patient = Address::generate(&env);
+    let required_by = 1000 + 3 * 86400;
+
+    let first = submit_request(
+        &env,
+        &client,
+        &hospital,
+        TestRequest::new()
+            .patient(&patient)
+            .required_by(required_by),
+    )
+    .unwrap();
+    client.cancel_request(&first, &hospital);
+
+    assert!(submit_request(
+        &env,
+        &client,
+        &hospital,
+        TestRequest::new()
+            .patient(&patient)
+            .required_by(required_by)
+    )
+    .is_ok());
+}
+
+#[test]
+fn test_amend_request_into_duplicate() {
+    let (env, _admin, client, _contract_id) = create_test_contract();
+    env.ledger().set_timestamp(1000);
+    let hospital = setup_authorized_hospital(&env, &client);
+    let patient = Address::generate(&env);
+    let required_by = 1000 + 3 * 86400;
+
+    submit_request(
+        &env,
+        &client,
+        &hospital,
+        TestRequest::new()
+            .patient(&patient)
+            .required_by(required_by),
+    )
+    .unwrap();
+    let other = submit_request(
+        &env,
+        &client,
+        &hospital,
+        TestRequest::new()
+            .patient(&patient)
+            .required_by(required_by + 3600),
+    )
+    .unwrap();
+
+    let request = client.get_request(&other);
+    assert_eq!(
+        client.try_amend_request(
+            &other,
+            &request.blood_type,
+            &request.quantity_ml,
+            &required_by,
+            &request.delivery_address,
+        ),
+        Err(Ok(ContractError::DuplicateRequest))
+    );
+
+    // Moving it elsewhere frees its old fingerprint for a new submission
+    client.amend_request(
+        &other,
+        &request.blood_type,
+        &request.quantity_ml,
+        &(required_by + 7200),
+        &request.delivery_address,
+    );
+    assert!(submit_request(
+        &env,
+        &client,
+        &hospital,
+        TestRequest::new()
+            .patient(&patient)
+            .required_by(required_by + 3600)
+    )
+    .is_ok());
+}
+
+// ========== Hospital Quota Tests ==========
+
+#[test]
+fn test_open_request_quota() {
+    let (env, _admin, client, _contract_id) = create_test_contract();
+    env.ledger().set_timestamp(1000);
+    let hospital = setup_authorized_hospital(&env, &client);
+    let quota = HospitalQuota {
+        max_open_requests: Some(2),
+        max_outstanding_ml: None,
+        max_requests_per_day: None,
+    };
+    client.set_hospital_quota(&hospital, &quota);
+    assert_eq!(client.get_hospital_quota(&hospital), Some(quota));
+
+    let first = submit_request(&env, &client, &hospital, TestRequest::new()).unwrap();
+    submit_request(&env, &client, &hospital, TestRequest::new()).unwrap();
+    assert_eq!(
+        submit_request(&env, &client, &hospital, TestRequest::new()),
+        Err(ContractError::QuotaExceeded)
+    );
+
+    // Closing a request frees a slot
+    client.cancel_request(&first, &hospital);
+    assert!(submit_request(&env, &client, &hospital, TestRequest::new()).is_ok());
+}
+
+#[test]
+fn test_outstanding_volume_quota_is_per_blood_type() {
+    let (env, _admin, client, _contract_id) = create_test_contract();
+    env.ledger().set_timestamp(1000);
+    let hospital = setup_authorized_hospital(&env, &client);
+    client.set_hospital_quota(
+        &hospital,
+        &HospitalQuota {
+            max_open_requests: None,
+            max_outstanding_ml: Some(900),
+            max_requests_per_day: None,
+        },
+    );
+
+    submit_request(&env, &client, &hospital, TestRequest::new()).unwrap();
+    submit_request(&env, &client, &hospital, TestRequest::new()).unwrap();
+    assert_eq!(
+        submit_request(&env, &client, &hospital, TestRequest::new()),
+        Err(ContractError::QuotaExceeded)
+    );
+
+    // A different blood type has its own allowance
+    client.create_request(
+        &hospital,
+        &BloodType::ANegative,
+        &900u32,
+        &UrgencyLevel::Urgent,
+        &(1000 + 2 * 86400),
+        &String::from_str(&env, "Hospital"),
+        &Address::generate(&env),
+        &String::from_str(&env, "Surgery"),
+        &String::from_str(&env, "Notes"),
+    );
+
+    let usage = client.get_hospital_usage(&hospital);
+    assert_eq!(usage.open_requests, 3);
+    assert_eq!(usage.outstanding_ml.get(BloodType::OPositive), Some(900));
+    assert_eq!(usage.outstanding_ml.get(BloodType::ANegative), Some(900));
+}
+
+#[test]
+fn test_daily_request_quota_is_rolling() {
+    let (env, _admin, client, _contract_id) = create_test_contract();
+    env.ledger().set_timestamp(1000);
+    let hospital = setup_authorized_hospital(&env, &client);
+    client.set_hospital_quota(
+        &hospital,
+        &HospitalQuota {
+            max_open_requests: None,
+            max_outstanding_ml: None,
+            max_requests_per_day: Some(1),
+        },
+    );
+
+    submit_request(&env, &client, &hospital, TestRequest::new()).unwrap();
+
+    env.ledger().set_timestamp(1000 + 86400 - 1);
+    assert_eq!(
+        submit_request(&env, &client, &hospital, TestRequest::new()),
+        Err(ContractError::QuotaExceeded)
+    );
+    assert_eq!(client.get_hospital_usage(&hospital).requests_last_day, 1);
+
+    env.ledger().set_timestamp(1000 + 86400);
+    assert_eq!(client.get_hospital_usage(&hospital).requests_last_day, 0);
+    assert!(submit_request(&env, &client, &hospital, TestRequest::new()).is_ok());
+}
+
+#[test]
+fn test_critical_request_over_quota_is_flagged() {
+    let (env, _admin, client, _contract_id) = create_test_contract();
+    env.ledger().set_timestamp(1000);
+    let hospital = setup_authorized_hospital(&env, &client);
+    client.set_hospital_quota(
+        &hospital,
+        &HospitalQuota {
+            max_open_requests: Some(1),
+            max_outstanding_ml: None,
+            max_requests_per_day: Some(1),
+        },
+    );
+
+    let normal = submit_request(&env, &client, &hospital, TestRequest::new()).unwrap();
+    assert_eq!(client.get_quota_review(&normal).len(), 0);
+
+    let critical = submit_request(
+        &env,
+        &client,
+        &hospital,
+        TestRequest::new().urgency(UrgencyLevel::Critical),
+    )
+    .unwrap();
+
+    let events = env.events().all();
+    let (_, _, data) = events.get(events.len() - 2).unwrap();
+    let event: QuotaExceededEvent = data.into_val(&env);
+    assert_eq!(event.request_id, critical);
+    assert_eq!(event.hospital_id, hospital);
+
+    let exceeded = vec![&env, QuotaLimit::OpenRequests, QuotaLimit::DailyRequests];
+    assert_eq!(event.exceeded, exceeded);
+    assert_eq!(client.get_quota_review(&critical), exceeded);
+    assert_eq!(client.get_requests_for_review(), vec![&env, critical]);
+
+    client.clear_quota_review(&critical);
+    assert_eq!(client.get_quota_review(&critical).len(), 0);
+    assert_eq!(client.get_requests_for_review().len(), 0);
+    assert_eq!(
+        client.try_clear_quota_review(&critical),
+        Err(Ok(ContractError::NotFound))
+    );
+}
+
+#[test]
+fn test_usage_counters_follow_status_changes() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+    env.ledger().set_timestamp(1000);
+    let hospital = setup_authorized_hospital(&env, &client);
+
+    let first = submit_request(&env, &client, &hospital, TestRequest::new()).unwrap();
+    let second = submit_request(&env, &client, &hospital, TestRequest::new()).unwrap();
+    client.approve_request(&first);
+
+    let usage = client.get_hospital_usage(&hospital);
+    assert_eq!(usage.open_requests, 2);
+    assert_eq!(usage.requests_last_day, 2);
+    assert_eq!(usage.outstanding_ml.get(BloodType::OPositive), Some(900));
+
+    client.cancel_request(&second, &hospital);
+    let usage = client.get_hospital_usage(&hospital);
+    assert_eq!(usage.open_requests, 1);
+    assert_eq!(usage.outstanding_ml.get(BloodType::OPositive), Some(450));
+
+    client.reject_request(
+        &first,
+        &admin,
+        &RejectionReason::NoStock,
+        &String::from_str(&env, ""),
+    );
+    let usage = client.get_hospital_usage(&hospital);
+    assert_eq!(usage.open_requests, 0);
+    assert_eq!(usage.outstanding_ml.len(), 0);
+    // Closed requests still count towards the daily limit
+    assert_eq!(usage.requests_last_day, 2);
+}
+
+#[test]
+fn test_amendment_counts_against_volume_quota() {
+    let (env, _admin, client, _contract_id) = create_test_contract();
+    env.ledger().set_timestamp(1000);
+    let hospital = setup_authorized_hospital(&env, &client);
+    client.set_hospital_quota(
+        &hospital,
+        &HospitalQuota {
+            max_open_requests: None,
+            max_outstanding_ml: Some(900),
+            max_requests_per_day: None,
+        },
+    );
+
+    let request_id = submit_request(&env, &client, &hospital, TestRequest::new()).unwrap();
+    submit_request(&env, &client, &hospital, TestRequest::new()).unwrap();
+    let request = client.get_request(&request_id);
+
+    let result = client.try_amend_request(
+        &request_id,
+        &BloodType::OPositive,
+        &600u32,
+        &request.required_by,
+        &request.delivery_address,
+    );
+    assert_eq!(result, Err(Ok(ContractError::QuotaExceeded)));
+
+    // Reducing a request always goes through
+    client.amend_request(
+        &request_id,
+        &BloodType::OPositive,
+        &300u32,
+        &request.required_by,
+        &request.delivery_address,
+    );
+    let usage = client.get_hospital_usage(&hospital);
+    assert_eq!(usage.outstanding_ml.get(BloodType::OPositive), Some(750));
+
+    // Moving to another blood type moves the awaited volume with it
+    client.amend_request(
+        &request_id,
+        &BloodType::ANegative,
+        &900u32,
+        &request.required_by,
+        &request.delivery_address,
+    );
+    let usage = client.get_hospital_usage(&hospital);
+    assert_eq!(usage.outstanding_ml.get(BloodType::OPositive), Some(450));
+    assert_eq!(usage.outstanding_ml.get(BloodType::ANegative), Some(900));
+}
+
+#[test]
+fn test_critical_amendment_over_quota_is_flagged() {
+    let (env, _admin, client, _contract_id) = create_test_contract();
+    env.ledger().set_timestamp(1000);
+    let hospital = setup_authorized_hospital(&env, &client);
+    client.set_hospital_quota(
+        &hospital,
+        &HospitalQuota {
+            max_open_requests: None,
+            max_outstanding_ml: Some(450),
+            max_requests_per_day: None,
+        },
+    );
+
+    let request_id = submit_request(
+        &env,
+        &client,
+        &hospital,
+        TestRequest::new().urgency(UrgencyLevel::Critical),
+    )
+    .unwrap();
+    assert_eq!(client.get_quota_review(&request_id).len(), 0);
+
+    let request = client.get_request(&request_id);
+    client.amend_request(
+        &request_id,
+        &BloodType::OPositive,
+        &600u32,
+        &request.required_by,
+        &request.delivery_address,
+    );
+
+    assert_eq!(
+        client.get_quota_review(&request_id),
+        vec![&env, QuotaLimit::OutstandingVolume]
+    );
+    assert_eq!(client.get_requests_for_review(), vec![&env, request_id]);
+}
+
+#[test]
+fn test_remove_hospital_quota() {
+    let (env, _admin, client, _contract_id) = create_test_contract();
+    env.ledger().set_timestamp(1000);
+    let hospital = setup_authorized_hospital(&env, &client);
+    client.set_hospital_quota(
+        &hospital,
+        &HospitalQuota {
+            max_open_requests: Some(0),
+            max_outstanding_ml: None,
+            max_requests_per_day: None,
+        },
+    );
+    assert_eq!(
+        submit_request(
+            &env,
+            &client,
+            &hospital,
+            TestRequest::new().urgency(UrgencyLevel::Normal)
+        ),
+        Err(ContractError::QuotaExceeded)
+    );
+
+    client.remove_hospital_quota(&hospital);
+    assert_eq!(client.get_hospital_quota(&hospital), None);
+    assert!(submit_request(
+        &env,
+        &client,
+        &hospital,
+        TestRequest::new().urgency(UrgencyLevel::Normal)
+    )
+    .is_ok());
+}
+
+// ========== Rejection Tests ==========
+
+#[test]
+fn test_reject_request_by_bank() {
+    let (env, _admin, client, contract_id) = create_test_contract();
+    env.ledger().set_timestamp(1000);
+    let (_inventory, bank) = setup_inventory(&env, &client, &contract_id);
+    let hospital = setup_authorized_hospital(&env, &client);
+
+    let request_id = submit_request(&env, &client, &hospital, TestRequest::new()).unwrap();
+    let note = String::from_str(&env, "No O+ stock until Friday");
+
+    env.ledger().set_timestamp(2000);
+    client.reject_request(&request_id, &bank, &RejectionReason::NoStock, &note);
+
+    let events = env.events().all();
+    let (_, _, data) = events.get(events.len() - 2).unwrap();
+    let event: RequestRejectedEvent = data.into_val(&env);
+    assert_eq!(event.request_id, request_id);
+    assert_eq!(event.hospital_id, hospital);
+    assert_eq!(event.rejection.reason, RejectionReason::NoStock);
+
+    let request = client.get_request(&request_id);
+    assert_eq!(request.status, RequestStatus::Rejected);
+    assert!(request.status.is_terminal());
+    assert_eq!(
+        client.get_requests_by_status(&RequestStatus::Pending).len(),
+        0
+    );
+    assert_eq!(
+        client.get_requests_by_status(&RequestStatus::Rejected),
+        vec![&env, request_id]
+    );
+
+    let rejection = client.get_request_rejection(&request_id).unwrap();
+    assert_eq!(rejection, event.rejection);
+    assert_eq!(rejection.rejected_by, bank);
+    assert_eq!(rejection.note, note);
+    assert_eq!(rejection.rejected_at, 2000);
+}
+
+#[test]
+fn test_reject_request_releases_assigned_units() {
+    let (env, admin, client, contract_id) = create_test_contract();
+    env.ledger().set_timestamp(1000);
+    let (inventory, bank) = setup_inventory(&env, &client, &contract_id);
+
+    let unit_id = add_available_unit(
+        &env,
+        &inventory,
+        &bank,
+        UnitBloodType::OPositive,
+        1000 + 30 * 86400,
+    );
+    let request_id = create_approved_request(&env, &client, &admin);
+    client.assign_blood_units(&request_id, &vec![&env, unit_id]);
+
+    client.reject_request(
+        &request_id,
+        &admin,
+        &RejectionReason::OutOfServiceArea,
+        &String::from_str(&env, ""),
+    );
+
+    assert_eq!(
+        inventory.get_blood_unit(&unit_id).status,
+        UnitStatus::Available
+    );
+    assert_eq!(client.get_unit_assignment(&unit_id), None);
+}
+
+#[test]
+fn test_reject_request_unauthorized() {
+    let (env, _admin, client, contract_id) = create_test_contract();
+    env.ledger().set_timestamp(1000);
+    setup_inventory(&env, &client, &contract_id);
+    let hospital = setup_authorized_hospital(&env, &client);
+
+    let request_id = submit_request(&env, &client, &hospital, TestRequest::new()).unwrap();
+
+    // The owning hospital cancels; it cannot reject its own request
+    assert_eq!(
+        client.try_reject_request(
+            &request_id,
+            &hospital,
+            &RejectionReason::NoStock,
+            &String::from_str(&env, ""),
+        ),
+        Err(Ok(ContractError::Unauthorized))
+    );
+}
+
+#[test]
+fn test_reject_request_other_requires_note() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+    env.ledger().set_timestamp(1000);
+
+    let request_id = submit_request(&env, &client, &admin, TestRequest::new()).unwrap();
+    assert_eq!(
+        client.try_reject_request(
+            &request_id,
+            &admin,
+            &RejectionReason::Other,
+            &String::from_str(&env, ""),
+        ),
+        Err(Ok(ContractError::InvalidInput))
+    );
+
+    client.reject_request(
+        &request_id,
+        &admin,
+        &RejectionReason::Other,
+        &String::from_str(&env, "Duplicate of an earlier request"),
+    );
+}
+
+#[test]
+fn test_reject_request_after_fulfillment_started() {
+    let (env, admin, client, contract_id) = create_test_contract();
+    env.ledger().set_timestamp(1000);
+    let (inventory, bank) = setup_inventory(&env, &client, &contract_id);
+
+    let unit_id = add_available_unit(
+        &env,
+        &inventory,
+        &bank,
+        UnitBloodType::OPositive,
+        1000 + 30 * 86400,
+    );
+    let request_id =
+        submit_request(&env, &client, &admin, TestRequest::new().quantity_ml(900)).unwrap();
+    client.approve_request(&request_id);
+    client.record_fulfillment(&request_id, &bank, &vec![&env, unit_id]);
+
+    assert_eq!(
+        client.try_reject_request(
+            &request_id,
+            &bank,
+            &RejectionReason::NoStock,
+            &String::from_str(&env, ""),
+        ),
+        Err(Ok(ContractError::InvalidStatusTransition))
+    );
+}
+
+#[test]
+fn test_date_range_query_includes_rejected() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+    env.ledger().set_timestamp(1000);
+
+    let rejected = submit_request(&env, &client, &admin, TestRequest::new()).unwrap();
+    client.reject_request(
+        &rejected,
+        &admin,
+        &RejectionReason::NoStock,
+        &String::from_str(&env, ""),
+    );
+
+    let requests = client.query_requests_by_date_range(&1000, &1000, &None, &None, &None);
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests.get(0).unwrap().status, RequestStatus::Rejected);
+}
+
+#[test]
+fn test_query_rejected_requests_by_reason() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+    env.ledger().set_timestamp(1000);
+    let empty = String::from_str(&env, "");
+
+    let no_stock = submit_request(&env, &client, &admin, TestRequest::new()).unwrap();
+    let documentation = submit_request(&env, &client, &admin, TestRequest::new()).unwrap();
+    let open = submit_request(&env, &client, &admin, TestRequest::new()).unwrap();
+    client.reject_request(&no_stock, &admin, &RejectionReason::NoStock, &empty);
+    client.reject_request(
+        &documentation,
+        &admin,
+        &RejectionReason::InvalidDocumentation,
+        &empty,
+    );
+
+    let all = client.query_rejected_requests(&None, &None, &None);
+    assert_eq!(all.len(), 2);
+
+    let filtered =
+        client.query_rejected_requests(&Some(RejectionReason::InvalidDocumentation), &None, &None);
+    assert_eq!(filtered.len(), 1);
+    assert_eq!(filtered.get(0).unwrap().id, documentation);
+    assert_eq!(client.get_request_rejection(&open), None);
+}
+
+// ========== Partial Fulfillment Tests ==========
+
+#[test]
+fn test_date_range_query_includes_partially_fulfilled() {
+    let (env, admin, client, contract_id) = create_test_contract();
+    env.ledger().set_timestamp(1000);
+    let (inventory, bank) = setup_inventory(&env, &client, &contract_id);
+
+    let unit_id = add_available_unit(
+        &env,
+        &inventory,
+        &bank,
+        UnitBloodType::OPositive,
+        1000 + 30 * 86400,
+    );
+    let request_id =
+        submit_request(&env, &client, &admin, TestRequest::new().quantity_ml(900)).unwrap();
+    client.approve_request(&request_id);
+    client.record_fulfillment(&request_id, &bank, &vec![&env, unit_id]);
+
+    let requests = client.query_requests_by_date_range(&1000, &1000, &None, &None, &None);
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests.get(0).unwrap().id, request_id);
+    assert_eq!(
+        requests.get(0).unwrap().status,
+        RequestStatus::PartiallyFulfilled
+    );
+}
+
+#[test]
+fn test_shipped_units_cannot_return_to_stock() {
+    let (env, admin, client, contract_id) = create_test_contract();
+    env.ledger().set_timestamp(1000);
+    let (inventory, bank) = setup_inventory(&env, &client, &contract_id);
+
+    let unit_id = add_available_unit(
+        &env,
+        &inventory,
+        &bank,
+        UnitBloodType::OPositive,
+        1000 + 30 * 86400,
+    );
+    let request_id =
+        submit_request(&env, &client, &admin, TestRequest::new().quantity_ml(900)).unwrap();
+    client.approve_request(&request_id);
+    client.record_fulfillment(&request_id, &bank, &vec![&env, unit_id]);
+
+    // Long after the request's reservation would have lapsed
+    env.ledger()
+        .set_timestamp(1000 + storage::UNIT_RESERVATION_SECONDS + 1);
+    assert!(inventory.try_release_expired_reservation(&unit_id).is_err());
+    assert_eq!(
+        inventory.get_blood_unit(&unit_id).status,
+        UnitStatus::InTransit
+    );
+}
+
+/// Authorize another bank in the inventory
+fn add_bank(inventory: &InventoryContractClient, env: &Env) -> Address {
+    let bank = Address::generate(env);
+    inventory.authorize_bank(&bank);
+    bank
+}
+
+#[test]
+fn test_record_fulfillment_across_shipments() {
+    let (env, admin, client, contract_id) = create_test_contract();
+    env.ledger().set_timestamp(1000);
+    let (inventory, bank) = setup_inventory(&env, &client, &contract_id);
+    let other_bank = add_bank(&inventory, &env);
+    let expiration = 1000 + 30 * 86400;
+
+    let first = add_available_unit(
+        &env,
+        &inventory,
+        &bank,
+        UnitBloodType::OPositive,
+        expiration,
+    );
+    let second = add_screened_unit(
+        &env,
+        &inventory,
+        &other_bank,
+        &bank,
+        UnitBloodType::ONegative,
+        BloodComponent::WholeBlood,
+        expiration,
+    );
+    let request_id =
+        submit_request(&env, &client, &admin, TestRequest::new().quantity_ml(900)).unwrap();
+    client.approve_request(&request_id);
+
+    env.ledger().set_timestamp(2000);
+    client.record_fulfillment(&request_id, &bank, &vec![&env, first]);
+
+    let events = env.events().all();
+    let (_, _, data) = events.get(events.len() - 2).unwrap();
+    let event: FulfillmentRecordedEvent = data.into_val(&env);
+    assert_eq!(event.request_id, request_id);
+    assert_eq!(event.fulfillment.bank_id, bank);
+    assert_eq!(event.fulfilled_quantity_ml, 450);
+    assert_eq!(event.outstanding_quantity_ml, 450);
+
+    let request = client.get_request(&request_id);
+    assert_eq!(request.status, RequestStatus::PartiallyFulfilled);
+    assert_eq!(request.fulfilled_quantity_ml, 450);
+    assert_eq!(request.outstanding_quantity_ml(), 450);
+    assert_eq!(request.fulfilled_at, None);
+    assert_eq!(client.get_unit_assignment(&first), Some(request_id));
+    assert_eq!(
+        inventory.get_blood_unit(&first).status,
+        UnitStatus::InTransit
+    );
+    assert_eq!(inventory.get_reservation(&first), None);
+
+    let partial = client.query_partially_fulfilled(&None, &None);
+    assert_eq!(partial.len(), 1);
+    assert_eq!(partial.get(0).unwrap().id, request_id);
+
+    env.ledger().set_timestamp(3000);
+    client.record_fulfillment(&request_id, &other_bank, &vec![&env, second]);
+
+    let request = client.get_request(&request_id);
+    assert_eq!(request.status, RequestStatus::Fulfilled);
+    assert_eq!(request.fulfilled_quantity_ml, 900);
+    assert_eq!(request.outstanding_quantity_ml(), 0);
+    assert_eq!(request.fulfilled_at, Some(3000));
+    assert_eq!(request.assigned_units, vec![&env, first, second]);
+    assert_eq!(client.query_partially_fulfilled(&None, &None).len(), 0);
+    assert_eq!(
+        client.get_requests_by_status(&RequestStatus::Fulfilled),
+        vec![&env, request_id]
+    );
+
+    let fulfillments = client.get_request_fulfillments(&request_id);
+    assert_eq!(fulfillments.len(), 2);
+    let shipment = fulfillments.get(1).unwrap();
+    assert_eq!(shipment.bank_id, other_bank);
+    assert_eq!(shipment.unit_ids, vec![&env, second]);
+    assert_eq!(shipment.quantity_ml, 450);
+    assert_eq!(shipment.delivered_at, 3000);
+}
+
+#[test]
+fn test_record_fulfillment_of_assigned_units() {
+    let (env, admin, client, contract_id) = create_test_contract();
+    env.ledger().set_timestamp(1000);
+    let (inventory, bank) = setup_inventory(&env, &client, &contract_id);
+    let expiration = 1000 + 30 * 86400;
+
+    let first = add_available_unit(
+        &env,
+        &inventory,
+        &bank,
+        UnitBloodType::OPositive,
+        expiration,
+    );
+    let second = add_available_unit(
+        &env,
+        &inventory,
+        &bank,
+        UnitBloodType::OPositive,
+        expiration,
+    );
+    let request_id =
+        submit_request(&env, &client, &admin, TestRequest::new().quantity_ml(900)).unwrap();
+    client.approve_request(&request_id);
+    client.assign_blood_units(&request_id, &vec![&env, first, second]);
+
+    // Units reserved by the assignment ship without a new reservation
+    client.record_fulfillment(&request_id, &bank, &vec![&env, first]);
+    client.record_fulfillment(&request_id, &bank, &vec![&env, second]);
+
+    let request = client.get_request(&request_id);
+    assert_eq!(request.status, RequestStatus::Fulfilled);
+    assert_eq!(request.assigned_units, vec![&env, first, second]);
+}
+
+#[test]
+fn test_record_fulfillment_rejects_unit_from_other_bank() {
+    let (env, admin, client, contract_id) = create_test_contract();
+    env.ledger().set_timestamp(1000);
+    let (inventory, bank) = setup_inventory(&env, &client, &contract_id);
+    let other_bank = add_bank(&inventory, &env);
+
+    let unit_id = add_available_unit(
+        &env,
+        &inventory,
+        &bank,
+        UnitBloodType::OPositive,
+        1000 + 30 * 86400,
+    );
+    let request_id =
+        submit_request(&env, &client, &admin, TestRequest::new().quantity_ml(900)).unwrap();
+    client.approve_request(&request_id);
+
+    assert_eq!(
+        client.try_record_fulfillment(&request_id, &other_bank, &vec![&env, unit_id]),
+        Err(Ok(ContractError::UnitFromOtherBank))
+    );
+    assert_eq!(
+        inventory.get_blood_unit(&unit_id).status,
+        UnitStatus::Available
+    );
+}
+
+#[test]
+fn test_record_fulfillment_rejects_reshipped_unit() {
+    let (env, admin, client, contract_id) = create_test_contract();
+    env.ledger().set_timestamp(1000);
+    let (inventory, bank) = setup_inventory(&env, &client, &contract_id);
+
+    let unit_id = add_available_unit(
+        &env,
+        &inventory,
+        &bank,
+        UnitBloodType::OPositive,
+        1000 + 30 * 86400,
+    );
+    let request_id =
+        submit_request(&env, &client, &admin, TestRequest::new().quantity_ml(900)).unwrap();
+    client.approve_request(&request_id);
+    client.record_fulfillment(&request_id, &bank, &vec![&env, unit_id]);
+
+    assert_eq!(
+        client.try_record_fulfillment(&request_id, &bank, &vec![&env, unit_id]),
+        Err(Ok(ContractError::InvalidInput))
+    );
+}
+
+#[test]
+fn test_record_fulfillment_requires_open_approved_request() {
+    let (env, admin, client, contract_id) = create_test_contract();
+    env.ledger().set_timestamp(1000);
+    let (inventory, bank) = setup_inventory(&env, &client, &contract_id);
+    let expiration = 1000 + 30 * 86400;
+
+    let unit_id = add_available_unit(
+        &env,
+        &inventory,
+        &bank,
+        UnitBloodType::OPositive,
+        expiration,
+    );
+    let pending = submit_request(&env, &client, &admin, TestRequest::new()).unwrap();
+    assert_eq!(
+        client.try_record_fulfillment(&pending, &bank, &vec![&env, unit_id]),
+        Err(Ok(ContractError::RequestNotApproved))
+    );
+
+    let fulfilled = create_approved_request(&env, &client, &admin);
+    client.record_fulfillment(&fulfilled, &bank, &vec![&env, unit_id]);
+
+    let another = add_available_unit(
+        &env,
+        &inventory,
+        &bank,
+        UnitBloodType::OPositive,
+        expiration,
+    );
+    assert_eq!(
+        client.try_record_fulfillment(&fulfilled, &bank, &vec![&env, another]),
+        Err(Ok(ContractError::InvalidRequestState))
+    );
+}
+
+#[test]
+fn test_expire_partially_fulfilled_request_keeps_shipped_units() {
+    let (env, admin, client, contract_id) = create_test_contract();
+    env.ledger().set_timestamp(1000);
+    let (inventory, bank) = setup_inventory(&env, &client, &contract_id);
+    let expiration = 1000 + 30 * 86400;
+
+    let shipped = add_available_unit(
+        &env,
+        &inventory,
+        &bank,
+        UnitBloodType::OPositive,
+        expiration,
+    );
+    let unshipped = add_available_unit(
+        &env,
+        &inventory,
+        &bank,
+        UnitBloodType::OPositive,
+        expiration,
+    );
+    let request_id =
+        submit_request(&env, &client, &admin, TestRequest::new().quantity_ml(900)).unwrap();
+    client.approve_request(&request_id);
+    client.assign_blood_units(&request_id, &vec![&env, shipped, unshipped]);
+    client.record_fulfillment(&request_id, &bank, &vec![&env, shipped]);
+
+    env.ledger().set_timestamp(1000 + 86400);
+    assert_eq!(
+        client.expire_overdue_requests(&0, &10).expired,
+        vec![&env, request_id]
+    );
+
+    assert_eq!(
+        inventory.get_blood_unit(&shipped).status,
+        UnitStatus::InTransit
+    );
+    assert_eq!(
+        inventory.get_blood_unit(&unshipped).status,
+        UnitStatus::Available
+    );
+    assert_eq!(client.get_unit_assignment(&unshipped), None);
+}
+
+// ========== Request Expiry Tests ==========
+
+#[test]
+fn test_expire_overdue_requests() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+    env.ledger().set_timestamp(1000);
+
+    let pending = submit_request(&env, &client, &admin, TestRequest::new()).unwrap();
+    let approved = create_approved_request(&env, &client, &admin);
+
+    // A later request isn't due yet when the first two lapse
+    env.ledger().set_timestamp(50_000);
+    let open = submit_request(&env, &client, &admin, TestRequest::new()).unwrap();
+
+    env.ledger().set_timestamp(1000 + 86400);
+    let sweep = client.expire_overdue_requests(&0, &10);
+    assert!(env.auths().is_empty());
+    assert_eq!(sweep.expired, vec![&env, pending, approved]);
+    assert_eq!(sweep.next_cursor, None);
+
+    for request_id in [pending, approved] {
+        assert_eq!(
+            client.get_request(&request_id).status,
+            RequestStatus::Expired
+        );
+    }
+    assert_eq!(client.get_request(&open).status, RequestStatus::Pending);
+    assert_eq!(
+        client.get_requests_by_status(&RequestStatus::Expired),
+        vec![&env, pending, approved]
+    );
+    assert_eq!(
+        client.get_requests_by_status(&RequestStatus::Pending),
+        vec![&env, open]
+    );
+
+    // Nothing left to expire
+    assert_eq!(client.expire_overdue_requests(&0, &10).expired.len(), 0);
+}
+
+#[test]
+fn test_expire_overdue_requests_is_bounded() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+    env.ledger().set_timestamp(1000);
+
+    let first = submit_request(&env, &client, &admin, TestRequest::new()).unwrap();
+    let second = submit_request(&env, &client, &admin, TestRequest::new()).unwrap();
+    let third = submit_request(&env, &client, &admin, TestRequest::new()).unwrap();
+
+    env.ledger().set_timestamp(1000 + 86400);
+    let sweep = client.expire_overdue_requests(&0, &2);
+    assert_eq!(sweep.expired, vec![&env, first, second]);
+    // Both expired requests left the index, so the third is now first
+    assert_eq!(sweep.next_cursor, Some(0));
+
+    let events = env.events().all();
+    assert_eq!(events.len(), 2);
+
+    let sweep = client.expire_overdue_requests(&0, &2);
+    assert_eq!(sweep.expired, vec![&env, third]);
+    assert_eq!(sweep.next_cursor, None);
+}
+
+#[test]
+fn test_expire_overdue_requests_counts_reads() {
+    let (env, admin, client, _contract_id) = create_test_contract();
+    env.ledger().set_timestamp(1000);
+    let overdue = create_approved_request(&env, &client, &admin);
+
+    // Pending requests that aren't due yet still use up the read budget
+    env.ledger().set_timestamp(50_000);
+    let open = [
+        submit_request(&env, &client, &admin, TestRequest::new()).unwrap(),
+        submit_request(&env, &client, &admin, TestRequest::new()).unwrap(),
+        submit_request(&env, &client, &admin, TestRequest::new()).unwrap(),
+    ];
+
+    env.ledger().set_timestamp(1000 + 86400);
+    let sweep = client.expire_overdue_requests(&0, &2);
+    assert_eq!(sweep.expired.len(), 0);
+    assert_eq!(sweep.next_cursor, Some(2));
+
+    let sweep = client.expire_overdue_requests(&2, &2);
+    assert_eq!(sweep.expired, vec![&env, overdue]);
+    assert_eq!(sweep.next_cursor, None);
+
+    for request_id in open {
+        assert_eq!(
+            client.get_request(&request_id).status,
+            RequestStatus::Pending
+        );
+    }
+}
+
+#[test]
+fn test_expire_overdue_requests_releases_units() {
+    let (env, admin, client, contract_id) = create_test_contract();
+    env.ledger().set_timestamp(1000);
+    let (inventory, bank) = setup_inventory(&env, &client, &contract_id);
+
+    let unit_id = add_available_unit(
+        &env,
+        &inventory,
+        &bank,
+        UnitBloodType::OPositive,
+        1000 + 30 * 86400,
+    );
+    let request_id = create_approved_request(&env, &client, &admin);
+    client.assign_blood_units(&request_id, &vec![&env, unit_id]);
+
+    env.ledger().set_timestamp(1000 + 86400);
+    client.expire_overdue_requests(&0, &10);
+
+    assert_eq!(
+        inventory.get_blood_unit(&unit_id).status,
+        UnitStatus::Available
+    );
+    assert_eq!(client.get_unit_assignment(&unit_id), None);
+
+    // The unit can go to another request straight away
+    let next = create_approved_request(&env, &client, &admin);
+    client.assign_blood_units(&next, &vec![&env, unit_id]);
+}
+
+// ========== Storage TTL Tests ==========
+
+fn persistent_ttl(env: &Env, contract_id: &Address, key: &DataKey) -> u32 {
+    env.as_contract(contract_id, || env.storage().persistent().get_ttl(key))
+}
+
+#[test]
+fn test_create_request_extends_ttl() {
+    let (env, _admin, client, contract_id) = create_test_contract();
+
+    let hospital = setup_authorized_hospital(&env, &client);
+    let request_id = submit_request(
+        &env,
+        &client,
+        &hospital,
+        TestRequest::new().urgency(UrgencyLevel::Normal),
+    )
+    .unwrap();
+
+    assert_eq!(
+        persistent_ttl(&env, &contract_id, &DataKey::BloodRequest(request_id)),
+        storage::ACTIVE_TTL_EXTEND_TO
+    );
+    assert_eq!(
+        persistent_ttl(&env, &contract_id, &DataKey::HospitalIndex(hospital)),
+        storage::ACTIVE_TTL_EXTEND_TO
+    );
+}
+
+#[test]
+fn test_cancelled_request_moves_to_archive_ttl() {
+    let (env, _admin, client, contract_id) = create_test_contract();
+
+    let hospital = setup_authorized_hospital(&env, &client);
+    let request_id = submit_request(
+        &env,
+        &client,
+        &hospital,
+        TestRequest::new().urgency(UrgencyLevel::Normal),
+    )
+    .unwrap();
+    client.cancel_request(&request_id, &hospital);
+
+    assert_eq!(
+        persistent_ttl(&env, &contract_id, &DataKey::BloodRequest(request_id)),
+        storage::ARCHIVE_TTL_EXTEND_TO
+    );
+}
+
+#[test]
+fn test_extend_request_ttl_is_permissionless() {
+    let (env, _admin, client, contract_id) = create_test_contract();
+
+    let hospital = setup_authorized_hospital(&env, &client);
+    let request_id = submit_request(
+        &env,
+        &client,
+        &hospital,
+        TestRequest::new().urgency(UrgencyLevel::Normal),
+    )
+    .unwrap();
+    let key = DataKey::BloodRequest(request_id);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number += storage::ACTIVE_TTL_EXTEND_TO - storage::ACTIVE_TTL_THRESHOLD + 1
+    });
+    assert!(persistent_ttl(&env, &contract_id, &key) < storage::ACTIVE_TTL_THRESHOLD);
+
+    client.extend_request_ttl(&request_id);
+    assert!(env.auths().is_empty());
+    assert_eq!(
+        persistent_ttl(&env, &contract_id, &key),
+        storage::ACTIVE_TTL_EXTEND_TO
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #21)")]
+fn test_extend_request_ttl_not_found() {
+    let (_env, _admin, client, _contract_id) = create_test_contract();
+
+    client.extend_request_ttl(&999);
+}
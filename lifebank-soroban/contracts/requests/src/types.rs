@@ -1,5 +1,7 @@
 use crate::error::ContractError;
-use soroban_sdk::{contracttype, Address, Map, String, Symbol, Vec};
+use crate::inventory::BloodComponent;
+use crate::validation;
+use soroban_sdk::{contracttype, Address, BytesN, Map, String, Vec};
 
 /// Blood type enumeration supporting all major blood groups
 ///
@@ -23,22 +25,105 @@ pub enum BloodType {
     /// Type O positive (O+)
     OPositive,
     /// Type O negative (O-) - Universal blood donor
-use soroban_sdk::{contracttype, Address, Vec};
-
-/// Blood type enumeration supporting all major blood groups
-#[contracttype]
-#[derive(Clone, Debug, Eq, PartialEq, Copy)]
-pub enum BloodType {
-    APositive,
-    ANegative,
-    BPositive,
-    BNegative,
-    ABPositive,
-    ABNegative,
-    OPositive,
     ONegative,
 }
 
+impl BloodType {
+    /// Check if this blood type can donate red cells to the recipient type
+    ///
+    /// Same ABO/Rh rules as the inventory contract (kept in sync by
+    /// `test_red_cell_rules_match_inventory`): O- is the universal donor,
+    /// AB+ the universal recipient and Rh- can donate to Rh+.
+    pub fn can_donate_to(&self, recipient: &BloodType) -> bool {
+        use BloodType::*;
+
+        match (self, recipient) {
+            // O- is universal donor
+            (ONegative, _) => true,
+
+            // O+ can donate to all positive types
+            (OPositive, APositive | BPositive | ABPositive | OPositive) => true,
+
+            // A- can donate to A and AB (both + and -)
+            (ANegative, APositive | ANegative | ABPositive | ABNegative) => true,
+
+            // A+ can donate to A+ and AB+
+            (APositive, APositive | ABPositive) => true,
+
+            // B- can donate to B and AB (both + and -)
+            (BNegative, BPositive | BNegative | ABPositive | ABNegative) => true,
+
+            // B+ can donate to B+ and AB+
+            (BPositive, BPositive | ABPositive) => true,
+
+            // AB- can donate to AB+ and AB-
+            (ABNegative, ABPositive | ABNegative) => true,
+
+            // AB+ can only donate to AB+
+            (ABPositive, ABPositive) => true,
+
+            // All other combinations are incompatible
+            _ => false,
+        }
+    }
+
+    /// Check if this blood type can donate plasma to the recipient type
+    ///
+    /// Plasma carries the donor's antibodies, so the ABO rules run the
+    /// other way: AB is the universal plasma donor and O plasma only suits
+    /// O recipients. Rh doesn't apply.
+    pub fn can_donate_plasma_to(&self, recipient: &BloodType) -> bool {
+        use BloodType::*;
+
+        match (self, recipient) {
+            // AB plasma has neither anti-A nor anti-B
+            (ABPositive | ABNegative, _) => true,
+
+            // A plasma carries anti-B
+            (APositive | ANegative, APositive | ANegative | OPositive | ONegative) => true,
+
+            // B plasma carries anti-A
+            (BPositive | BNegative, BPositive | BNegative | OPositive | ONegative) => true,
+
+            // O plasma carries both
+            (OPositive | ONegative, OPositive | ONegative) => true,
+
+            _ => false,
+        }
+    }
+
+    /// Check if a unit of `component` from this blood type can be given to
+    /// the recipient type
+    ///
+    /// Red cells and whole blood follow the red cell rules, plasma and
+    /// cryoprecipitate the plasma rules. Platelets are suspended in plasma
+    /// but carry residual red cells, so they need both ABO plasma
+    /// compatibility and Rh- for Rh- recipients.
+    pub fn can_donate_component_to(
+        &self,
+        component: BloodComponent,
+        recipient: &BloodType,
+    ) -> bool {
+        match component {
+            BloodComponent::WholeBlood | BloodComponent::RedCells => self.can_donate_to(recipient),
+            BloodComponent::Plasma | BloodComponent::Cryoprecipitate => {
+                self.can_donate_plasma_to(recipient)
+            }
+            BloodComponent::Platelets => {
+                self.can_donate_plasma_to(recipient)
+                    && (self.is_rh_negative() || !recipient.is_rh_negative())
+            }
+        }
+    }
+
+    /// Whether this blood type is Rh negative
+    pub fn is_rh_negative(&self) -> bool {
+        use BloodType::*;
+
+        matches!(self, ANegative | BNegative | ABNegative | ONegative)
+    }
+}
+
 /// Urgency level for blood requests
 ///
 /// Determines priority in request processing and fulfillment:
@@ -62,9 +147,10 @@ pub enum UrgencyLevel {
 /// Request status representing the lifecycle of a blood request
 ///
 /// Status transitions follow this flow:
-/// Pending -> Approved -> Fulfilled -> InDelivery -> Completed
-///        \-> Cancelled (from Pending, Approved, Fulfilled)
-///        \-> Expired (from Pending, Approved if required_by passes)
+/// Pending -> Approved -> [PartiallyFulfilled ->] Fulfilled -> InDelivery -> Completed
+///        \-> Cancelled (from Pending, Approved, PartiallyFulfilled, Fulfilled)
+///        \-> Expired (from Pending, Approved, PartiallyFulfilled if required_by passes)
+///        \-> Rejected (from Pending, Approved, by a bank or admin)
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq, Copy)]
 pub enum RequestStatus {
@@ -72,6 +158,8 @@ pub enum RequestStatus {
     Pending,
     /// Request approved, waiting for blood unit assignment
     Approved,
+    /// Some shipments delivered, but not yet the full requested volume
+    PartiallyFulfilled,
     /// Blood units have been assigned to fulfill the request
     Fulfilled,
     /// Blood units are in transit to the hospital
@@ -82,52 +170,22 @@ pub enum RequestStatus {
     Cancelled,
     /// Request expired before fulfillment (required_by timestamp passed)
     Expired,
+    /// Request was declined by a bank or admin
+    Rejected,
 }
 
-/// Complete blood request record from a hospital
-///
-/// Represents a request for blood units with full tracking information
-/// from creation through delivery or cancellation.
-///
-/// # Storage Keys
-/// - Primary key: `id` (u64)
-/// - Secondary indexes: `hospital_id`, `blood_type`, `status`, `urgency`
-/// Determines priority and fulfillment timeline:
-/// - Critical: Life-threatening, immediate fulfillment required
-/// - Urgent: High priority, fulfillment within hours
-/// - Normal: Standard priority, fulfillment within days
-#[contracttype]
-#[derive(Clone, Debug, Eq, PartialEq, Copy, PartialOrd, Ord)]
-pub enum UrgencyLevel {
-    /// Life-threatening situation, immediate fulfillment required
-    Critical,
-    /// High priority, fulfillment within hours
-    Urgent,
-    /// Standard priority, fulfillment within days
-    Normal,
-}
-
-/// Request status representing its lifecycle
-///
-/// Status transitions:
-/// Pending -> Approved -> Fulfilled -> Completed
-///        \-> Rejected
-///        \-> Cancelled
+/// Structured reason for declining a blood request
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq, Copy)]
-pub enum RequestStatus {
-    /// Initial state, awaiting approval
-    Pending,
-    /// Approved by blood bank, awaiting fulfillment
-    Approved,
-    /// Blood units assigned and being prepared
-    Fulfilled,
-    /// Request completed successfully
-    Completed,
-    /// Request rejected by blood bank
-    Rejected,
-    /// Request cancelled by hospital
-    Cancelled,
+pub enum RejectionReason {
+    /// No compatible stock to serve the request
+    NoStock,
+    /// Supporting documentation is missing or invalid
+    InvalidDocumentation,
+    /// Delivery address is outside the service area
+    OutOfServiceArea,
+    /// Any other reason, explained in the note
+    Other,
 }
 
 /// Request metadata containing additional context
@@ -137,15 +195,19 @@ pub struct RequestMetadata {
     /// Patient name or identifier
     pub patient_id: Address,
     /// Medical procedure or reason for request
-    pub procedure: soroban_sdk::String,
+    pub procedure: String,
     /// Special notes or requirements
-    pub notes: soroban_sdk::String,
+    pub notes: String,
 }
 
-/// Complete blood request record
+/// Complete blood request record from a hospital
 ///
-/// Represents a hospital's request for blood units with full tracking
-/// from creation through fulfillment or cancellation.
+/// Represents a request for blood units with full tracking information
+/// from creation through delivery or cancellation.
+///
+/// # Storage Keys
+/// - Primary key: `id` (u64)
+/// - Secondary indexes: `hospital_id`, `blood_type`, `status`, `urgency`
 #[contracttype]
 #[derive(Clone, Debug)]
 pub struct BloodRequest {
@@ -179,6 +241,9 @@ pub struct BloodRequest {
     /// None if not yet fulfilled
     pub fulfilled_at: Option<u64>,
 
+    /// Volume delivered so far across all shipments in milliliters
+    pub fulfilled_quantity_ml: u32,
+
     /// IDs of blood units assigned to fulfill this request
     /// References blood unit IDs from the inventory contract
     pub assigned_units: Vec<u64>,
@@ -186,9 +251,8 @@ pub struct BloodRequest {
     /// Physical delivery address for the blood units
     pub delivery_address: String,
 
-    /// Extensible metadata for additional attributes
-    /// Examples: contact_person, phone_number, special_instructions, department
-    pub metadata: Map<Symbol, String>,
+    /// Request metadata (patient info, procedure, notes)
+    pub metadata: RequestMetadata,
 }
 
 impl UrgencyLevel {
@@ -210,64 +274,68 @@ impl UrgencyLevel {
     pub fn is_higher_than(&self, other: &UrgencyLevel) -> bool {
         self.priority_weight() > other.priority_weight()
     }
-    /// Hospital address requesting blood
-    pub hospital_id: Address,
-
-    /// Blood type requested
-    pub blood_type: BloodType,
-
-    /// Quantity requested in milliliters
-    pub quantity_ml: u32,
-
-    /// Urgency level of the request
-    pub urgency: UrgencyLevel,
 
-    /// Current status of the request
-    pub status: RequestStatus,
-
-    /// Unix timestamp when request was created
-    pub created_at: u64,
-
-    /// Unix timestamp when blood is required by
-    pub required_by: u64,
-
-    /// Unix timestamp when request was fulfilled (if applicable)
-    pub fulfilled_at: Option<u64>,
-
-    /// Vector of blood unit IDs assigned to this request
-    pub assigned_units: Vec<u64>,
-
-    /// Delivery address for the blood units
-    pub delivery_address: soroban_sdk::String,
-
-    /// Request metadata (patient info, procedure, notes)
-    pub metadata: RequestMetadata,
+    /// Get the maximum time allowed for fulfillment in seconds
+    ///
+    /// - Critical: 1 hour (3600 seconds)
+    /// - Urgent: 6 hours (21600 seconds)
+    /// - Normal: 24 hours (86400 seconds)
+    pub fn max_fulfillment_time(&self) -> u64 {
+        match self {
+            UrgencyLevel::Critical => 3600,
+            UrgencyLevel::Urgent => 21600,
+            UrgencyLevel::Normal => 86400,
+        }
+    }
 }
 
 impl RequestStatus {
+    /// Every status, in declaration order
+    pub const ALL: [RequestStatus; 9] = [
+        RequestStatus::Pending,
+        RequestStatus::Approved,
+        RequestStatus::PartiallyFulfilled,
+        RequestStatus::Fulfilled,
+        RequestStatus::InDelivery,
+        RequestStatus::Completed,
+        RequestStatus::Cancelled,
+        RequestStatus::Expired,
+        RequestStatus::Rejected,
+    ];
+
     /// Check if transition from current status to new status is valid
     ///
     /// Valid transitions:
-    /// - Pending -> Approved, Cancelled, Expired
-    /// - Approved -> Fulfilled, Cancelled, Expired
+    /// - Pending -> Approved, Cancelled, Expired, Rejected
+    /// - Approved -> PartiallyFulfilled, Fulfilled, Cancelled, Expired, Rejected
+    /// - PartiallyFulfilled -> Fulfilled, Cancelled, Expired
     /// - Fulfilled -> InDelivery, Cancelled
     /// - InDelivery -> Completed, Cancelled
     /// - Completed -> (terminal state)
     /// - Cancelled -> (terminal state)
     /// - Expired -> (terminal state)
+    /// - Rejected -> (terminal state)
     pub fn can_transition_to(&self, new_status: &RequestStatus) -> bool {
         use RequestStatus::*;
 
         match (self, new_status) {
-            // Pending can go to Approved, Cancelled, or Expired
+            // Pending can go to Approved, Cancelled, Expired, or Rejected
             (Pending, Approved) => true,
             (Pending, Cancelled) => true,
             (Pending, Expired) => true,
+            (Pending, Rejected) => true,
 
-            // Approved can go to Fulfilled, Cancelled, or Expired
+            // Approved can go to PartiallyFulfilled, Fulfilled, Cancelled, Expired, or Rejected
+            (Approved, PartiallyFulfilled) => true,
             (Approved, Fulfilled) => true,
             (Approved, Cancelled) => true,
             (Approved, Expired) => true,
+            (Approved, Rejected) => true,
+
+            // PartiallyFulfilled can go to Fulfilled, Cancelled, or Expired
+            (PartiallyFulfilled, Fulfilled) => true,
+            (PartiallyFulfilled, Cancelled) => true,
+            (PartiallyFulfilled, Expired) => true,
 
             // Fulfilled can go to InDelivery or Cancelled
             (Fulfilled, InDelivery) => true,
@@ -281,6 +349,7 @@ impl RequestStatus {
             (Completed, _) => false,
             (Cancelled, _) => false,
             (Expired, _) => false,
+            (Rejected, _) => false,
 
             // No other transitions allowed
             _ => false,
@@ -291,7 +360,10 @@ impl RequestStatus {
     pub fn is_terminal(&self) -> bool {
         matches!(
             self,
-            RequestStatus::Completed | RequestStatus::Cancelled | RequestStatus::Expired
+            RequestStatus::Completed
+                | RequestStatus::Cancelled
+                | RequestStatus::Expired
+                | RequestStatus::Rejected
         )
     }
 
@@ -301,6 +373,7 @@ impl RequestStatus {
             self,
             RequestStatus::Pending
                 | RequestStatus::Approved
+                | RequestStatus::PartiallyFulfilled
                 | RequestStatus::Fulfilled
                 | RequestStatus::InDelivery
         )
@@ -312,27 +385,10 @@ impl RequestStatus {
             self,
             RequestStatus::Pending
                 | RequestStatus::Approved
+                | RequestStatus::PartiallyFulfilled
                 | RequestStatus::Fulfilled
                 | RequestStatus::InDelivery
         )
-    /// Check if this status is a terminal state
-    pub fn is_terminal(&self) -> bool {
-        matches!(self, RequestStatus::Rejected | RequestStatus::Completed | RequestStatus::Cancelled)
-    }
-}
-
-impl UrgencyLevel {
-    /// Get the maximum time allowed for fulfillment in seconds
-    ///
-    /// - Critical: 1 hour (3600 seconds)
-    /// - Urgent: 6 hours (21600 seconds)
-    /// - Normal: 24 hours (86400 seconds)
-    pub fn max_fulfillment_time(&self) -> u64 {
-        match self {
-            UrgencyLevel::Critical => 3600,      // 1 hour
-            UrgencyLevel::Urgent => 21600,       // 6 hours
-            UrgencyLevel::Normal => 86400,       // 24 hours
-        }
     }
 }
 
@@ -364,40 +420,6 @@ impl BloodRequest {
             if fulfilled < self.created_at {
                 return Err(ContractError::InvalidTimestamp);
             }
-    /// Validate the blood request
-    ///
-    /// Checks:
-    /// - Quantity is within acceptable range (50-5000ml)
-    /// - Required_by is in the future
-    /// - Required_by is reasonable relative to created_at
-    /// - Delivery address is not empty
-    pub fn validate(&self, current_time: u64) -> Result<(), crate::error::ContractError> {
-        use crate::error::ContractError;
-
-        // Validate quantity (50-5000ml for hospital requests)
-        if self.quantity_ml < 50 || self.quantity_ml > 5000 {
-            return Err(ContractError::InvalidQuantity);
-        }
-
-        // Required_by must be in the future
-        if self.required_by <= current_time {
-            return Err(ContractError::InvalidTimestamp);
-        }
-
-        // Required_by should be reasonable (not more than 30 days in future)
-        let max_future = current_time + (30 * 86400);
-        if self.required_by > max_future {
-            return Err(ContractError::InvalidTimestamp);
-        }
-
-        // Created_at should be before required_by
-        if self.created_at >= self.required_by {
-            return Err(ContractError::InvalidTimestamp);
-        }
-
-        // Delivery address should not be empty
-        if self.delivery_address.len() == 0 {
-            return Err(ContractError::InvalidInput);
         }
 
         Ok(())
@@ -411,15 +433,199 @@ impl BloodRequest {
     /// Calculate time remaining until required_by in seconds
     /// Returns negative value if already past required_by
     pub fn time_remaining(&self, current_time: u64) -> i64 {
-        (self.required_by as i64) - (current_time as i64)
+        validation::time_until_deadline(self.required_by, current_time)
     }
 
     /// Check if the request has been fulfilled (has assigned units)
     pub fn has_assigned_units(&self) -> bool {
-        self.assigned_units.len() > 0
+        !self.assigned_units.is_empty()
+    }
+
+    /// Check if request has exceeded its required_by deadline
+    pub fn is_overdue(&self, current_time: u64) -> bool {
+        validation::is_request_overdue(self.required_by, current_time)
+    }
+
+    /// Check if request can be fulfilled based on status and deadline
+    pub fn can_fulfill(&self, current_time: u64) -> bool {
+        !self.is_overdue(current_time)
+            && matches!(
+                self.status,
+                RequestStatus::Approved | RequestStatus::PartiallyFulfilled
+            )
+    }
+
+    /// Fields used to detect duplicate submissions of this request
+    pub fn fingerprint(&self) -> RequestFingerprint {
+        RequestFingerprint {
+            hospital_id: self.hospital_id.clone(),
+            patient_id: self.metadata.patient_id.clone(),
+            blood_type: self.blood_type,
+            quantity_ml: self.quantity_ml,
+            required_by: self.required_by,
+        }
+    }
+
+    /// Entry for this request in the pending priority queue
+    pub fn queue_entry(&self) -> PendingQueueEntry {
+        PendingQueueEntry {
+            request_id: self.id,
+            priority: self.urgency.priority_weight(),
+            required_by: self.required_by,
+            created_at: self.created_at,
+        }
+    }
+
+    /// Volume still to be delivered in milliliters
+    pub fn outstanding_quantity_ml(&self) -> u32 {
+        self.quantity_ml.saturating_sub(self.fulfilled_quantity_ml)
+    }
+}
+
+/// Fields that identify a resubmission of the same request
+///
+/// Two requests with the same fingerprint from the same hospital within
+/// `DUPLICATE_WINDOW_SECONDS` are treated as a retried submission.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RequestFingerprint {
+    pub hospital_id: Address,
+    pub patient_id: Address,
+    pub blood_type: BloodType,
+    pub quantity_ml: u32,
+    pub required_by: u64,
+}
+
+/// Admin-configured request limits for a hospital
+///
+/// `None` leaves that limit unbounded.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HospitalQuota {
+    /// Most requests the hospital may have open at once
+    pub max_open_requests: Option<u32>,
+
+    /// Most volume still awaited for any single blood type, in milliliters
+    pub max_outstanding_ml: Option<u32>,
+
+    /// Most requests the hospital may create per rolling 24 hours
+    pub max_requests_per_day: Option<u32>,
+}
+
+/// Current usage of a hospital against its quota
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HospitalUsage {
+    /// Requests not yet completed, cancelled, expired or rejected
+    pub open_requests: u32,
+
+    /// Requests created in the last 24 hours
+    pub requests_last_day: u32,
+
+    /// Volume still awaited per blood type, in milliliters
+    pub outstanding_ml: Map<BloodType, u32>,
+}
+
+/// Running usage counters of a hospital
+///
+/// Updated whenever one of its requests is created or changes, so quota
+/// checks don't have to load the hospital's requests.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HospitalCounters {
+    /// Requests not yet completed, cancelled, expired or rejected
+    pub open_requests: u32,
+
+    /// Volume still awaited per blood type, in milliliters
+    pub outstanding_ml: Map<BloodType, u32>,
+}
+
+/// A single limit within a hospital quota
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq, Copy)]
+pub enum QuotaLimit {
+    /// `max_open_requests`
+    OpenRequests,
+    /// `max_outstanding_ml`
+    OutstandingVolume,
+    /// `max_requests_per_day`
+    DailyRequests,
+}
+
+/// Result of one `expire_overdue_requests` call
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct ExpirySweep {
+    /// IDs of the requests expired by this call
+    pub expired: Vec<u64>,
+
+    /// Cursor to continue the sweep from (None once every open request
+    /// has been checked)
+    pub next_cursor: Option<u32>,
+}
+
+/// Position of a pending request in the priority queue
+///
+/// Carries the sort fields so the queue can be kept ordered without loading
+/// the requests themselves. Entries order by urgency (highest first), then
+/// `required_by` (soonest first), then `created_at`, then request ID.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PendingQueueEntry {
+    pub request_id: u64,
+    pub priority: u32,
+    pub required_by: u64,
+    pub created_at: u64,
+}
+
+impl Ord for PendingQueueEntry {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        other
+            .priority
+            .cmp(&self.priority)
+            .then(self.required_by.cmp(&other.required_by))
+            .then(self.created_at.cmp(&other.created_at))
+            .then(self.request_id.cmp(&other.request_id))
     }
 }
 
+impl PartialOrd for PendingQueueEntry {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Directory entry for one page of a pending queue shard
+///
+/// Pages hold consecutive runs of the shard's entries in queue order, so
+/// `last` is enough to find the page an entry belongs in.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PendingQueuePage {
+    /// Storage ID of the page
+    pub page_id: u32,
+
+    /// Last entry on the page
+    pub last: PendingQueueEntry,
+
+    /// Number of entries on the page
+    pub len: u32,
+}
+
+/// Pending requests of one urgency level, split into bounded pages
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PendingQueueShard {
+    /// Number of entries across all pages
+    pub len: u32,
+
+    /// ID to give the next page created
+    pub next_page_id: u32,
+
+    /// Pages in queue order
+    pub pages: Vec<PendingQueuePage>,
+}
+
 /// Storage key types for efficient querying of blood requests
 #[contracttype]
 #[derive(Clone, Debug)]
@@ -447,6 +653,51 @@ pub enum DataKey {
 
     /// Authorized hospitals set
     AuthorizedHospital(Address),
+
+    /// Inventory contract holding the blood units
+    InventoryContract,
+
+    /// Reverse index: blood unit ID -> request ID it is assigned to
+    UnitAssignment(u64),
+
+    /// Urgency escalations for a request -> Vec<UrgencyEscalation>
+    Escalations(u64),
+
+    /// Amendments made to a request -> Vec<RequestAmendment>
+    Amendments(u64),
+
+    /// Shipments delivered against a request -> Vec<FulfillmentRecord>
+    Fulfillments(u64),
+
+    /// Rejection details for a declined request
+    Rejection(u64),
+
+    /// Request fingerprint hash -> ID of the request it was taken from
+    Fingerprint(BytesN<32>),
+
+    /// Request limits configured for a hospital
+    HospitalQuota(Address),
+
+    /// Quota limits a critical request exceeded -> Vec<QuotaLimit>
+    QuotaReview(u64),
+
+    /// IDs of requests flagged for quota review
+    QuotaReviewQueue,
+
+    /// Running usage counters of a hospital -> HospitalCounters
+    HospitalCounters(Address),
+
+    /// Creation times of a hospital's requests on one day
+    /// (Address, day number) -> Vec<u64>
+    HospitalDailyRequests(Address, u64),
+
+    /// Page directory of the pending queue for one urgency level
+    /// -> PendingQueueShard
+    PendingQueueShard(UrgencyLevel),
+
+    /// One page of a pending queue shard
+    /// (UrgencyLevel, page ID) -> Vec<PendingQueueEntry>
+    PendingQueuePage(UrgencyLevel, u32),
 }
 
 /// Event emitted when a new blood request is created
@@ -492,6 +743,168 @@ pub struct RequestStatusChangedEvent {
     pub changed_at: u64,
 }
 
+/// Record of a request's urgency being raised
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UrgencyEscalation {
+    /// Urgency before the escalation
+    pub from_urgency: UrgencyLevel,
+
+    /// Urgency after the escalation
+    pub to_urgency: UrgencyLevel,
+
+    /// Why the hospital raised the urgency
+    pub reason: String,
+
+    /// Timestamp of the escalation
+    pub escalated_at: u64,
+}
+
+/// Event emitted when a hospital raises a request's urgency
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct RequestEscalatedEvent {
+    /// Request ID
+    pub request_id: u64,
+
+    /// Urgency before the escalation
+    pub from_urgency: UrgencyLevel,
+
+    /// Urgency after the escalation
+    pub to_urgency: UrgencyLevel,
+
+    /// Why the hospital raised the urgency
+    pub reason: String,
+
+    /// Timestamp of the escalation
+    pub escalated_at: u64,
+}
+
+/// Record of a hospital amending a pending request, with old and new values
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RequestAmendment {
+    /// Blood type before the amendment
+    pub old_blood_type: BloodType,
+
+    /// Blood type after the amendment
+    pub new_blood_type: BloodType,
+
+    /// Quantity in milliliters before the amendment
+    pub old_quantity_ml: u32,
+
+    /// Quantity in milliliters after the amendment
+    pub new_quantity_ml: u32,
+
+    /// Deadline before the amendment
+    pub old_required_by: u64,
+
+    /// Deadline after the amendment
+    pub new_required_by: u64,
+
+    /// Delivery address before the amendment
+    pub old_delivery_address: String,
+
+    /// Delivery address after the amendment
+    pub new_delivery_address: String,
+
+    /// Timestamp of the amendment
+    pub amended_at: u64,
+}
+
+/// Event emitted when a hospital amends a pending request
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct RequestAmendedEvent {
+    /// Request ID
+    pub request_id: u64,
+
+    /// Hospital that owns the request
+    pub hospital_id: Address,
+
+    /// The recorded amendment with old and new values
+    pub amendment: RequestAmendment,
+}
+
+/// Details recorded when a bank or admin declines a request
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RequestRejection {
+    /// Bank or admin that declined the request
+    pub rejected_by: Address,
+
+    /// Structured reason for the rejection
+    pub reason: RejectionReason,
+
+    /// Free-text explanation for the hospital
+    pub note: String,
+
+    /// Timestamp of the rejection
+    pub rejected_at: u64,
+}
+
+/// Event emitted when a bank or admin declines a request
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct RequestRejectedEvent {
+    /// Request ID
+    pub request_id: u64,
+
+    /// Hospital that owns the request
+    pub hospital_id: Address,
+
+    /// The recorded rejection
+    pub rejection: RequestRejection,
+}
+
+/// Event emitted when a critical request goes over its hospital's quota
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct QuotaExceededEvent {
+    /// Request ID, now flagged for review
+    pub request_id: u64,
+
+    /// Hospital that created the request
+    pub hospital_id: Address,
+
+    /// Limits the request went over
+    pub exceeded: Vec<QuotaLimit>,
+}
+
+/// One shipment delivered against a request by a single bank
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FulfillmentRecord {
+    /// Bank that supplied the shipment
+    pub bank_id: Address,
+
+    /// Blood units in the shipment
+    pub unit_ids: Vec<u64>,
+
+    /// Combined volume of the shipment in milliliters
+    pub quantity_ml: u32,
+
+    /// Timestamp the shipment was delivered
+    pub delivered_at: u64,
+}
+
+/// Event emitted when a shipment is recorded against a request
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct FulfillmentRecordedEvent {
+    /// Request ID
+    pub request_id: u64,
+
+    /// The recorded shipment
+    pub fulfillment: FulfillmentRecord,
+
+    /// Volume delivered so far across all shipments
+    pub fulfilled_quantity_ml: u32,
+
+    /// Volume still to be delivered
+    pub outstanding_quantity_ml: u32,
+}
+
 /// Event emitted when blood units are assigned to a request
 #[contracttype]
 #[derive(Clone, Debug)]
@@ -502,7 +915,7 @@ pub struct UnitsAssignedEvent {
     /// IDs of assigned blood units
     pub unit_ids: Vec<u64>,
 
-    /// Total quantity assigned in milliliters
+    /// Combined volume of the assigned units in milliliters
     pub total_quantity_ml: u32,
 
     /// Timestamp when units were assigned
@@ -514,6 +927,67 @@ mod tests {
     use super::*;
     use soroban_sdk::{testutils::Address as _, Env};
 
+    #[test]
+    fn test_all_statuses_listed() {
+        // A new status stops this match compiling; add it to ALL as well
+        for (i, status) in RequestStatus::ALL.iter().enumerate() {
+            let position = match status {
+                RequestStatus::Pending => 0,
+                RequestStatus::Approved => 1,
+                RequestStatus::PartiallyFulfilled => 2,
+                RequestStatus::Fulfilled => 3,
+                RequestStatus::InDelivery => 4,
+                RequestStatus::Completed => 5,
+                RequestStatus::Cancelled => 6,
+                RequestStatus::Expired => 7,
+                RequestStatus::Rejected => 8,
+            };
+            assert_eq!(position, i);
+        }
+    }
+
+    fn test_metadata(env: &Env) -> RequestMetadata {
+        RequestMetadata {
+            patient_id: Address::generate(env),
+            procedure: String::from_str(env, "Surgery"),
+            notes: String::from_str(env, "Notes"),
+        }
+    }
+
+    #[test]
+    fn test_plasma_compatibility_runs_opposite_to_red_cells() {
+        use BloodType::*;
+
+        for recipient in [APositive, BNegative, ABPositive, ONegative] {
+            assert!(ABNegative.can_donate_plasma_to(&recipient));
+        }
+        assert!(ONegative.can_donate_plasma_to(&OPositive));
+        assert!(!ONegative.can_donate_plasma_to(&APositive));
+        assert!(APositive.can_donate_plasma_to(&ONegative));
+        assert!(!APositive.can_donate_plasma_to(&BPositive));
+        assert!(!BNegative.can_donate_plasma_to(&ABNegative));
+    }
+
+    #[test]
+    fn test_component_compatibility() {
+        use BloodType::*;
+
+        // O- red cells go anywhere, O- plasma doesn't
+        assert!(ONegative.can_donate_component_to(BloodComponent::RedCells, &ABPositive));
+        assert!(!ONegative.can_donate_component_to(BloodComponent::Plasma, &ABPositive));
+
+        // AB+ plasma and cryo go anywhere, AB+ red cells don't
+        assert!(ABPositive.can_donate_component_to(BloodComponent::Plasma, &ONegative));
+        assert!(ABPositive.can_donate_component_to(BloodComponent::Cryoprecipitate, &ONegative));
+        assert!(!ABPositive.can_donate_component_to(BloodComponent::WholeBlood, &ONegative));
+
+        // Platelets need plasma compatibility and Rh- for Rh- recipients
+        assert!(ABNegative.can_donate_component_to(BloodComponent::Platelets, &ONegative));
+        assert!(!ABPositive.can_donate_component_to(BloodComponent::Platelets, &ONegative));
+        assert!(ABPositive.can_donate_component_to(BloodComponent::Platelets, &OPositive));
+        assert!(!ONegative.can_donate_component_to(BloodComponent::Platelets, &APositive));
+    }
+
     #[test]
     fn test_urgency_level_priority() {
         assert_eq!(UrgencyLevel::Critical.priority_weight(), 3);
@@ -525,6 +999,25 @@ mod tests {
         assert!(!UrgencyLevel::Normal.is_higher_than(&UrgencyLevel::Critical));
     }
 
+    #[test]
+    fn test_pending_queue_entry_order() {
+        let entry = |request_id, priority, required_by, created_at| PendingQueueEntry {
+            request_id,
+            priority,
+            required_by,
+            created_at,
+        };
+
+        // Higher urgency first, regardless of deadline
+        assert!(entry(2, 3, 9000, 100) < entry(1, 2, 5000, 100));
+        // Same urgency: sooner deadline first
+        assert!(entry(2, 2, 5000, 200) < entry(1, 2, 9000, 100));
+        // Same urgency and deadline: older request first
+        assert!(entry(2, 2, 5000, 100) < entry(1, 2, 5000, 200));
+        // Fully tied: lower ID first
+        assert!(entry(1, 2, 5000, 100) < entry(2, 2, 5000, 100));
+    }
+
     #[test]
     fn test_request_status_transitions_from_pending() {
         use RequestStatus::*;
@@ -532,6 +1025,7 @@ mod tests {
         assert!(Pending.can_transition_to(&Approved));
         assert!(Pending.can_transition_to(&Cancelled));
         assert!(Pending.can_transition_to(&Expired));
+        assert!(Pending.can_transition_to(&Rejected));
         assert!(!Pending.can_transition_to(&Fulfilled));
         assert!(!Pending.can_transition_to(&InDelivery));
         assert!(!Pending.can_transition_to(&Completed));
@@ -547,6 +1041,22 @@ mod tests {
         assert!(!Approved.can_transition_to(&Pending));
         assert!(!Approved.can_transition_to(&InDelivery));
         assert!(!Approved.can_transition_to(&Completed));
+        assert!(Approved.can_transition_to(&PartiallyFulfilled));
+        assert!(Approved.can_transition_to(&Rejected));
+    }
+
+    #[test]
+    fn test_request_status_transitions_from_partially_fulfilled() {
+        use RequestStatus::*;
+
+        assert!(PartiallyFulfilled.can_transition_to(&Fulfilled));
+        assert!(PartiallyFulfilled.can_transition_to(&Cancelled));
+        assert!(PartiallyFulfilled.can_transition_to(&Expired));
+        assert!(!PartiallyFulfilled.can_transition_to(&Approved));
+        assert!(!PartiallyFulfilled.can_transition_to(&InDelivery));
+        assert!(!PartiallyFulfilled.can_transition_to(&Rejected));
+        assert!(PartiallyFulfilled.is_active());
+        assert!(PartiallyFulfilled.can_cancel());
     }
 
     #[test]
@@ -578,6 +1088,7 @@ mod tests {
         assert!(RequestStatus::Completed.is_terminal());
         assert!(RequestStatus::Cancelled.is_terminal());
         assert!(RequestStatus::Expired.is_terminal());
+        assert!(RequestStatus::Rejected.is_terminal());
         assert!(!RequestStatus::Pending.is_terminal());
         assert!(!RequestStatus::Approved.is_terminal());
         assert!(!RequestStatus::Fulfilled.is_terminal());
@@ -599,6 +1110,10 @@ mod tests {
         // Expired cannot transition to anything
         assert!(!Expired.can_transition_to(&Pending));
         assert!(!Expired.can_transition_to(&Approved));
+
+        // Rejected cannot transition to anything
+        assert!(!Rejected.can_transition_to(&Pending));
+        assert!(!Rejected.can_transition_to(&Approved));
     }
 
     #[test]
@@ -639,9 +1154,10 @@ mod tests {
             created_at: current_time,
             required_by: current_time + (7 * 24 * 60 * 60), // 7 days
             fulfilled_at: None,
+            fulfilled_quantity_ml: 0,
             assigned_units: Vec::new(&env),
             delivery_address: String::from_str(&env, "123 Hospital St"),
-            metadata: Map::new(&env),
+            metadata: test_metadata(&env),
         };
 
         assert!(request.validate(current_time).is_ok());
@@ -663,9 +1179,10 @@ mod tests {
             created_at: current_time,
             required_by: current_time + (7 * 24 * 60 * 60),
             fulfilled_at: None,
+            fulfilled_quantity_ml: 0,
             assigned_units: Vec::new(&env),
             delivery_address: String::from_str(&env, "123 Hospital St"),
-            metadata: Map::new(&env),
+            metadata: test_metadata(&env),
         };
 
         assert_eq!(
@@ -690,9 +1207,10 @@ mod tests {
             created_at: current_time,
             required_by: current_time + (7 * 24 * 60 * 60),
             fulfilled_at: None,
+            fulfilled_quantity_ml: 0,
             assigned_units: Vec::new(&env),
             delivery_address: String::from_str(&env, "123 Hospital St"),
-            metadata: Map::new(&env),
+            metadata: test_metadata(&env),
         };
 
         assert_eq!(
@@ -717,9 +1235,10 @@ mod tests {
             created_at: current_time,
             required_by: current_time - 100, // Before created_at
             fulfilled_at: None,
+            fulfilled_quantity_ml: 0,
             assigned_units: Vec::new(&env),
             delivery_address: String::from_str(&env, "123 Hospital St"),
-            metadata: Map::new(&env),
+            metadata: test_metadata(&env),
         };
 
         assert_eq!(
@@ -745,9 +1264,10 @@ mod tests {
             created_at: created_time,
             required_by,
             fulfilled_at: None,
+            fulfilled_quantity_ml: 0,
             assigned_units: Vec::new(&env),
             delivery_address: String::from_str(&env, "123 Hospital St"),
-            metadata: Map::new(&env),
+            metadata: test_metadata(&env),
         };
 
         // Not expired before required_by
@@ -777,9 +1297,10 @@ mod tests {
             created_at: created_time,
             required_by,
             fulfilled_at: None,
+            fulfilled_quantity_ml: 0,
             assigned_units: Vec::new(&env),
             delivery_address: String::from_str(&env, "123 Hospital St"),
-            metadata: Map::new(&env),
+            metadata: test_metadata(&env),
         };
 
         // 30 minutes before required_by
@@ -808,9 +1329,10 @@ mod tests {
             created_at: current_time,
             required_by: current_time + 3600,
             fulfilled_at: None,
+            fulfilled_quantity_ml: 0,
             assigned_units: Vec::new(&env),
             delivery_address: String::from_str(&env, "123 Hospital St"),
-            metadata: Map::new(&env),
+            metadata: test_metadata(&env),
         };
 
         assert!(!request.has_assigned_units());
@@ -818,39 +1340,4 @@ mod tests {
         request.assigned_units.push_back(1);
         assert!(request.has_assigned_units());
     }
-    /// Check if request has exceeded its required_by deadline
-    pub fn is_overdue(&self, current_time: u64) -> bool {
-        current_time > self.required_by
-    }
-
-    /// Get time remaining until required_by deadline in seconds
-    /// Returns negative value if overdue
-    pub fn time_remaining(&self, current_time: u64) -> i64 {
-        self.required_by as i64 - current_time as i64
-    }
-
-    /// Check if request can be fulfilled based on urgency and time
-    pub fn can_fulfill(&self, current_time: u64) -> bool {
-        !self.is_overdue(current_time) && self.status == RequestStatus::Approved
-    }
-}
-
-/// Storage key enumeration for efficient indexing
-#[contracttype]
-#[derive(Clone, Debug)]
-pub enum DataKey {
-    /// Primary key for blood requests
-    BloodRequest(u64),
-    /// Counter for generating request IDs
-    RequestCounter,
-    /// Index by hospital ID
-    HospitalIndex(Address),
-    /// Index by blood type
-    BloodTypeIndex(BloodType),
-    /// Index by status
-    StatusIndex(RequestStatus),
-    /// Index by urgency level
-    UrgencyIndex(UrgencyLevel),
-    /// Admin address
-    Admin,
 }
@@ -1,5 +1,6 @@
 use crate::error::ContractError;
 use crate::storage::{MAX_REQUEST_WINDOW_DAYS, MIN_REQUEST_WINDOW_SECONDS, SECONDS_PER_DAY};
+use crate::types::BloodType;
 use soroban_sdk::{Env, String};
 
 /// Minimum quantity for a blood request (100ml = partial unit)
@@ -16,23 +17,6 @@ pub const MAX_REQUEST_QUANTITY_ML: u32 = 10000;
 /// - required_by is in the future
 /// - required_by is not too far in the future (max 30 days)
 /// - Delivery address is not empty
-use crate::types::BloodType;
-use soroban_sdk::Env;
-
-/// Maximum request quantity (5000ml)
-pub const MAX_REQUEST_QUANTITY_ML: u32 = 5000;
-/// Minimum request quantity (50ml)
-pub const MIN_REQUEST_QUANTITY_ML: u32 = 50;
-/// Maximum days in future for required_by timestamp
-pub const MAX_DAYS_IN_FUTURE: u64 = 30;
-pub const SECONDS_PER_DAY: u64 = 86400;
-
-/// Validate blood request parameters
-///
-/// Checks:
-/// - Quantity is within acceptable range (50-5000ml)
-/// - Required_by is in the future
-/// - Required_by is not too far in the future (max 30 days)
 pub fn validate_request_creation(
     env: &Env,
     quantity_ml: u32,
@@ -59,7 +43,7 @@ pub fn validate_request_creation(
 /// # Returns
 /// Ok(()) if valid, Err(InvalidQuantity) if out of range
 pub fn validate_quantity(quantity_ml: u32) -> Result<(), ContractError> {
-    if quantity_ml < MIN_REQUEST_QUANTITY_ML || quantity_ml > MAX_REQUEST_QUANTITY_ML {
+    if !(MIN_REQUEST_QUANTITY_ML..=MAX_REQUEST_QUANTITY_ML).contains(&quantity_ml) {
         return Err(ContractError::InvalidQuantity);
     }
     Ok(())
@@ -95,6 +79,22 @@ pub fn validate_required_by(env: &Env, required_by: u64) -> Result<(), ContractE
     Ok(())
 }
 
+/// Validate blood type is valid
+pub fn validate_blood_type(_blood_type: &BloodType) -> Result<(), ContractError> {
+    // All BloodType variants are valid by construction
+    Ok(())
+}
+
+/// Check if request has exceeded its deadline
+pub fn is_request_overdue(required_by: u64, current_time: u64) -> bool {
+    current_time > required_by
+}
+
+/// Calculate time remaining until deadline in seconds
+pub fn time_until_deadline(required_by: u64, current_time: u64) -> i64 {
+    required_by as i64 - current_time as i64
+}
+
 /// Validate that a required_by timestamp hasn't passed
 ///
 /// Used when checking if a request has expired
@@ -110,23 +110,6 @@ pub fn validate_not_expired(env: &Env, required_by: u64) -> Result<(), ContractE
 
     if current_time >= required_by {
         return Err(ContractError::RequestExpired);
-) -> Result<(), ContractError> {
-    // Validate quantity
-    if quantity_ml < MIN_REQUEST_QUANTITY_ML || quantity_ml > MAX_REQUEST_QUANTITY_ML {
-        return Err(ContractError::InvalidQuantity);
-    }
-
-    let current_time = env.ledger().timestamp();
-
-    // Required_by must be in the future
-    if required_by <= current_time {
-        return Err(ContractError::InvalidTimestamp);
-    }
-
-    // Required_by shouldn't be too far in the future
-    let max_future = current_time + (MAX_DAYS_IN_FUTURE * SECONDS_PER_DAY);
-    if required_by > max_future {
-        return Err(ContractError::InvalidTimestamp);
     }
 
     Ok(())
@@ -140,11 +123,8 @@ pub fn validate_not_expired(env: &Env, required_by: u64) -> Result<(), ContractE
 /// # Returns
 /// Ok(()) if valid, Err(InvalidDeliveryAddress) if empty
 pub fn validate_delivery_address(delivery_address: &String) -> Result<(), ContractError> {
-    if delivery_address.len() == 0 {
+    if delivery_address.is_empty() {
         return Err(ContractError::InvalidDeliveryAddress);
-pub fn validate_delivery_address(address: &soroban_sdk::String) -> Result<(), ContractError> {
-    if address.len() == 0 {
-        return Err(ContractError::InvalidInput);
     }
     Ok(())
 }
@@ -175,9 +155,9 @@ pub fn validate_urgency_time_window(
     // Urgent (weight 2): 4 hours minimum
     // Normal (weight 1): 24 hours minimum
     let min_time = match urgency_weight {
-        3 => 3600,       // 1 hour for critical
-        2 => 4 * 3600,   // 4 hours for urgent
-        _ => 24 * 3600,  // 24 hours for normal
+        3 => 3600,      // 1 hour for critical
+        2 => 4 * 3600,  // 4 hours for urgent
+        _ => 24 * 3600, // 24 hours for normal
     };
 
     if time_available < min_time {
@@ -317,6 +297,20 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_is_request_overdue() {
+        assert!(!is_request_overdue(1000, 999));
+        assert!(!is_request_overdue(1000, 1000));
+        assert!(is_request_overdue(1000, 1001));
+    }
+
+    #[test]
+    fn test_time_until_deadline() {
+        assert_eq!(time_until_deadline(1000, 400), 600);
+        assert_eq!(time_until_deadline(1000, 1000), 0);
+        assert_eq!(time_until_deadline(1000, 1600), -600);
+    }
+
     #[test]
     fn test_validate_delivery_address_valid() {
         let env = Env::default();
@@ -394,7 +388,9 @@ mod tests {
         let required_by = current_time + 7 * SECONDS_PER_DAY;
         let delivery_address = String::from_str(&env, "123 Hospital Street");
 
-        assert!(validate_request_creation(&env, quantity_ml, required_by, &delivery_address).is_ok());
+        assert!(
+            validate_request_creation(&env, quantity_ml, required_by, &delivery_address).is_ok()
+        );
     }
 
     #[test]
@@ -440,18 +436,4 @@ mod tests {
             Err(ContractError::InvalidDeliveryAddress)
         );
     }
-/// Validate blood type is valid
-pub fn validate_blood_type(_blood_type: &BloodType) -> Result<(), ContractError> {
-    // All BloodType variants are valid by construction
-    Ok(())
-}
-
-/// Check if request has exceeded its deadline
-pub fn is_request_overdue(required_by: u64, current_time: u64) -> bool {
-    current_time > required_by
-}
-
-/// Calculate time remaining until deadline in seconds
-pub fn time_until_deadline(required_by: u64, current_time: u64) -> i64 {
-    required_by as i64 - current_time as i64
 }